@@ -1,4 +1,4 @@
-use compiler_rs::transform;
+use compiler_rs::{Compiler, Session, transform};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 fn bench_vapor(b: &mut Criterion) {
@@ -34,6 +34,11 @@ fn bench_vapor(b: &mut Criterion) {
   );
 
   b.bench_function("vapor", |b| b.iter(|| transform(&source, None)));
+
+  let mut session = Session::new(Compiler::default());
+  b.bench_function("vapor (session)", |b| {
+    b.iter(|| session.compile("index.jsx", &source).unwrap())
+  });
 }
 
 criterion_group!(benches, bench_vapor);
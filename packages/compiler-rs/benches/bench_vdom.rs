@@ -1,4 +1,4 @@
-use compiler_rs::{TransformOptions, transform};
+use compiler_rs::{Compiler, Session, TransformOptions, transform};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 fn bench_vdom(b: &mut Criterion) {
@@ -44,6 +44,14 @@ fn bench_vdom(b: &mut Criterion) {
       )
     })
   });
+
+  let mut session = Session::new(Compiler {
+    interop: true,
+    ..Default::default()
+  });
+  b.bench_function("vdom (session)", |b| {
+    b.iter(|| session.compile("index.jsx", &source).unwrap())
+  });
 }
 
 criterion_group!(benches, bench_vdom);
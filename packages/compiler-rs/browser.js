@@ -1,7 +1,18 @@
 import compiler, {
+  compileMany,
   ErrorCodes,
+  sharedTemplateModule,
   transform,
+  virtualCode,
+  WarningCodes,
 } from '@vue-jsx-vapor/compiler-rs-wasm32-wasi'
 
 export default compiler
-export { ErrorCodes, transform }
+export {
+  compileMany,
+  ErrorCodes,
+  sharedTemplateModule,
+  transform,
+  virtualCode,
+  WarningCodes,
+}
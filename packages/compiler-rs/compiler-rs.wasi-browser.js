@@ -601,5 +601,9 @@ try {
   throw __attachCleanupErrors(error, cleanupErrors)
 }
 export default __napiModule.exports
+export const compileMany = __napiModule.exports.compileMany
 export const ErrorCodes = __napiModule.exports.ErrorCodes
+export const sharedTemplateModule = __napiModule.exports.sharedTemplateModule
 export const transform = __napiModule.exports.transform
+export const virtualCode = __napiModule.exports.virtualCode
+export const WarningCodes = __napiModule.exports.WarningCodes
@@ -1,4 +1,4 @@
-// napi-rs-artifact-metadata:{"version":2,"rootEntry":"index.js","exports":["ErrorCodes","transform"],"managedRootEntries":["browser.js","index.js","compiler-rs.wasm","compiler-rs.debug.wasm"]}
+// napi-rs-artifact-metadata:{"version":2,"rootEntry":"index.js","exports":["compileMany","ErrorCodes","sharedTemplateModule","transform","virtualCode","WarningCodes"],"managedRootEntries":["browser.js","index.js","compiler-rs.wasm","compiler-rs.debug.wasm"]}
 /* eslint-disable */
 /* prettier-ignore */
 
@@ -921,5 +921,9 @@ try {
   throw rollback.error
 }
 module.exports = __napiModule.exports
+module.exports.compileMany = __napiModule.exports.compileMany
 module.exports.ErrorCodes = __napiModule.exports.ErrorCodes
+module.exports.sharedTemplateModule = __napiModule.exports.sharedTemplateModule
 module.exports.transform = __napiModule.exports.transform
+module.exports.virtualCode = __napiModule.exports.virtualCode
+module.exports.WarningCodes = __napiModule.exports.WarningCodes
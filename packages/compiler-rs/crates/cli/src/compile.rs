@@ -0,0 +1,193 @@
+use std::{
+  collections::HashSet,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use common::{
+  either::Either,
+  options::{Hmr, TagMatcher},
+};
+use compiler_rs::{TransformOptions, TransformOutput, try_transform};
+use serde_json::Value;
+
+use crate::Args;
+
+pub fn is_glob(input: &str) -> bool {
+  input.contains(['*', '?', '['])
+}
+
+// Expands globs and checks that plain paths exist, keeping the order the
+// inputs were given in.
+pub fn collect_inputs(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+  let mut files = vec![];
+  for input in inputs {
+    if is_glob(input) {
+      let paths = glob::glob(input).map_err(|e| format!("invalid pattern `{input}`: {e}"))?;
+      for path in paths.flatten() {
+        if path.is_file() {
+          files.push(path);
+        }
+      }
+    } else {
+      let path = PathBuf::from(input);
+      if !path.is_file() {
+        return Err(format!("no such file `{input}`"));
+      }
+      files.push(path);
+    }
+  }
+  let mut seen = HashSet::new();
+  files.retain(|file| seen.insert(file.clone()));
+  Ok(files)
+}
+
+pub struct Compiler {
+  out_dir: PathBuf,
+  root: PathBuf,
+  interop: bool,
+  ssr: bool,
+  hmr: Option<Vec<String>>,
+  optimize: bool,
+  runtime_module_name: Option<String>,
+  merge_props: bool,
+  source_map: bool,
+  dump_ir: bool,
+  custom_element: Vec<String>,
+}
+
+impl Compiler {
+  pub fn new(args: &Args) -> Self {
+    Compiler {
+      out_dir: args.out_dir.clone(),
+      root: args.root.canonicalize().unwrap_or(args.root.clone()),
+      interop: args.interop,
+      ssr: args.ssr,
+      hmr: if !args.define_component_name.is_empty() {
+        Some(args.define_component_name.clone())
+      } else if args.hmr {
+        Some(vec![])
+      } else {
+        None
+      },
+      optimize: !args.no_optimize,
+      runtime_module_name: args.runtime_module_name.clone(),
+      merge_props: !args.no_merge_props,
+      source_map: args.source_map,
+      dump_ir: args.dump_ir,
+      custom_element: args.custom_element.clone(),
+    }
+  }
+
+  // Compiles a single file, printing its diagnostics. Returns `false` when
+  // the file has errors or could not be read or written.
+  pub fn compile(&self, input: &Path) -> bool {
+    let source = match fs::read_to_string(input) {
+      Ok(source) => source,
+      Err(error) => {
+        eprintln!("error: failed to read `{}`: {error}", input.display());
+        return false;
+      }
+    };
+    let filename = input.to_string_lossy();
+    let TransformOutput {
+      mut code,
+      map,
+      diagnostics,
+      ir,
+      ..
+    } = match try_transform(&source, Some(self.options(&filename))) {
+      Ok(output) => output,
+      // nothing is written for a file that doesn't parse
+      Err(errors) => {
+        for error in &errors {
+          eprintln!("{}", error.render(&source, &filename));
+        }
+        return false;
+      }
+    };
+
+    let mut success = true;
+    for diagnostic in &diagnostics {
+      eprintln!("{}", diagnostic.render(&source, &filename));
+      success &= !diagnostic.is_error();
+    }
+
+    let output = self.output_path(input);
+    if let Err(error) = self.write(&output, &mut code, map, ir) {
+      eprintln!("error: failed to write `{}`: {error}", output.display());
+      return false;
+    }
+    success
+  }
+
+  fn options<'a>(&self, filename: &'a str) -> TransformOptions<'a> {
+    TransformOptions {
+      filename,
+      source_map: self.source_map,
+      interop: self.interop,
+      ssr: self.ssr,
+      hmr: match &self.hmr {
+        Some(names) if !names.is_empty() => Either::B(Hmr {
+          define_component_name: names.clone(),
+        }),
+        Some(_) => Either::A(true),
+        None => Either::A(false),
+      },
+      optimize: self.optimize,
+      runtime_module_name: self.runtime_module_name.clone(),
+      merge_props: self.merge_props,
+      dump_ir: self.dump_ir,
+      custom_element: (!self.custom_element.is_empty())
+        .then(|| TagMatcher::Patterns(self.custom_element.clone())),
+      ..Default::default()
+    }
+  }
+
+  // `<root>/src/App.tsx` is written to `<out_dir>/src/App.ts`.
+  fn output_path(&self, input: &Path) -> PathBuf {
+    let input = input.canonicalize().unwrap_or(input.to_path_buf());
+    let relative = match input.strip_prefix(&self.root) {
+      Ok(relative) => relative.to_path_buf(),
+      Err(_) => PathBuf::from(input.file_name().unwrap_or_default()),
+    };
+    let extension = match relative.extension().and_then(|ext| ext.to_str()) {
+      Some("jsx") => "js",
+      Some("tsx") => "ts",
+      Some(ext) => ext,
+      None => "js",
+    }
+    .to_string();
+    self.out_dir.join(relative.with_extension(extension))
+  }
+
+  fn write(
+    &self,
+    output: &Path,
+    code: &mut String,
+    map: Option<String>,
+    ir: Option<Value>,
+  ) -> std::io::Result<()> {
+    if let Some(parent) = output.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    if let Some(ir) = ir {
+      fs::write(
+        format!("{}.ir.json", output.display()),
+        serde_json::to_string_pretty(&ir)?,
+      )?;
+    }
+    if let Some(map) = map {
+      let map_path = PathBuf::from(format!("{}.map", output.display()));
+      fs::write(&map_path, map)?;
+      if !code.ends_with('\n') {
+        code.push('\n');
+      }
+      code.push_str(&format!(
+        "//# sourceMappingURL={}\n",
+        map_path.file_name().unwrap().to_string_lossy()
+      ));
+    }
+    fs::write(output, code)
+  }
+}
@@ -0,0 +1,91 @@
+mod compile;
+mod watch;
+
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+
+use crate::compile::{Compiler, collect_inputs};
+
+#[derive(Parser, Debug)]
+#[command(
+  name = "vue-jsx-vapor",
+  version,
+  about = "Compile JSX files with vue-jsx-vapor"
+)]
+pub struct Args {
+  /// Files or glob patterns to compile, e.g. `src/**/*.tsx`
+  #[arg(required = true)]
+  pub inputs: Vec<String>,
+  /// Output directory
+  #[arg(short, long, default_value = "dist")]
+  pub out_dir: PathBuf,
+  /// Directory that input paths are resolved against when writing to `out_dir`
+  #[arg(long, default_value = ".")]
+  pub root: PathBuf,
+  /// Transform JSX within `defineVaporComponent` to Vapor DOM and all other JSX to Virtual DOM
+  #[arg(long)]
+  pub interop: bool,
+  /// Enable SSR support
+  #[arg(long)]
+  pub ssr: bool,
+  /// Enable HMR support
+  #[arg(long)]
+  pub hmr: bool,
+  /// Names of the functions used for defining components, implies `--hmr`
+  #[arg(long, value_delimiter = ',')]
+  pub define_component_name: Vec<String>,
+  /// Disable compiler optimizations in interop mode
+  #[arg(long)]
+  pub no_optimize: bool,
+  /// Where to import runtime helpers from vue-jsx-vapor
+  #[arg(long)]
+  pub runtime_module_name: Option<String>,
+  /// Let later props override earlier ones instead of using `mergeProps`
+  #[arg(long)]
+  pub no_merge_props: bool,
+  /// Write `.map` files next to the output
+  #[arg(long)]
+  pub source_map: bool,
+  /// Write the Vapor IR next to the output as `.ir.json`
+  #[arg(long)]
+  pub dump_ir: bool,
+  /// Tags created as custom elements besides the ones with a dash, `*`
+  /// matches any characters, e.g. `ion-*`
+  #[arg(long, value_delimiter = ',')]
+  pub custom_element: Vec<String>,
+  /// Recompile files when they change
+  #[arg(short, long)]
+  pub watch: bool,
+}
+
+fn main() -> ExitCode {
+  let args = Args::parse();
+  let inputs = match collect_inputs(&args.inputs) {
+    Ok(inputs) => inputs,
+    Err(error) => {
+      eprintln!("error: {error}");
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let compiler = Compiler::new(&args);
+  let mut failed = false;
+  for input in &inputs {
+    failed |= !compiler.compile(input);
+  }
+
+  if args.watch {
+    if let Err(error) = watch::watch(&args.inputs, &compiler) {
+      eprintln!("error: {error}");
+      return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+  }
+
+  if failed {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
+  }
+}
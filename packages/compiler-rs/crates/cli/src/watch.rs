@@ -0,0 +1,92 @@
+use std::{
+  collections::BTreeSet,
+  env,
+  path::{Component, Path, PathBuf},
+  sync::mpsc,
+  time::Duration,
+};
+
+use glob::Pattern;
+use notify::{Event, EventKind, RecursiveMode, Watcher, recommended_watcher};
+
+use crate::compile::{Compiler, is_glob};
+
+// Events arriving within this window are compiled together, editors usually
+// emit several of them for a single save.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+pub fn watch(inputs: &[String], compiler: &Compiler) -> notify::Result<()> {
+  let cwd = env::current_dir()?;
+  let patterns = inputs
+    .iter()
+    .filter_map(|input| Pattern::new(input.trim_start_matches("./")).ok())
+    .collect::<Vec<_>>();
+
+  let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+  let mut watcher = recommended_watcher(tx)?;
+  for input in inputs {
+    let (dir, mode) = watch_target(input);
+    watcher.watch(&dir, mode)?;
+  }
+  println!("watching for changes...");
+
+  while let Ok(event) = rx.recv() {
+    let mut changed = BTreeSet::new();
+    collect_changed(event, &cwd, &patterns, &mut changed);
+    while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+      collect_changed(event, &cwd, &patterns, &mut changed);
+    }
+    for path in changed {
+      if path.is_file() && compiler.compile(&path) {
+        println!("compiled {}", path.display());
+      }
+    }
+  }
+  Ok(())
+}
+
+// The directory to watch for an input: the static prefix of a glob, watched
+// recursively, or the parent directory of a plain file.
+fn watch_target(input: &str) -> (PathBuf, RecursiveMode) {
+  let path = Path::new(input);
+  let (dir, mode) = if is_glob(input) {
+    let base = path
+      .components()
+      .take_while(|component| !is_glob(&component.as_os_str().to_string_lossy()))
+      .collect::<PathBuf>();
+    (base, RecursiveMode::Recursive)
+  } else {
+    (
+      path.parent().map(Path::to_path_buf).unwrap_or_default(),
+      RecursiveMode::NonRecursive,
+    )
+  };
+  if dir.as_os_str().is_empty() {
+    (PathBuf::from(Component::CurDir.as_os_str()), mode)
+  } else {
+    (dir, mode)
+  }
+}
+
+fn collect_changed(
+  event: notify::Result<Event>,
+  cwd: &Path,
+  patterns: &[Pattern],
+  changed: &mut BTreeSet<PathBuf>,
+) {
+  let Ok(event) = event else {
+    return;
+  };
+  if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+    return;
+  }
+  for path in event.paths {
+    let relative = path.strip_prefix(cwd).unwrap_or(&path).to_path_buf();
+    if patterns
+      .iter()
+      .any(|pattern| pattern.matches_path(&relative))
+    {
+      changed.insert(relative);
+    }
+  }
+}
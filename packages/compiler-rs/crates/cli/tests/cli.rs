@@ -0,0 +1,50 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+fn fixture(name: &str, files: &[(&str, &str)]) -> PathBuf {
+  let dir = env::temp_dir().join(format!("vue-jsx-vapor-cli-{name}-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  for (path, content) in files {
+    let path = dir.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+  }
+  dir
+}
+
+fn cli(dir: &PathBuf, args: &[&str]) -> std::process::Output {
+  Command::new(env!("CARGO_BIN_EXE_vue-jsx-vapor"))
+    .current_dir(dir)
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+#[test]
+fn compile_glob_with_source_map() {
+  let dir = fixture(
+    "glob",
+    &[
+      ("src/App.jsx", "export default () => <div>{msg}</div>"),
+      ("src/nested/Comp.tsx", "export const Comp = () => <span />"),
+    ],
+  );
+  let output = cli(&dir, &["src/**/*.[jt]sx", "--source-map", "-o", "dist"]);
+  assert!(output.status.success());
+
+  let code = fs::read_to_string(dir.join("dist/src/App.js")).unwrap();
+  assert!(code.contains("_template(\"<div> \""));
+  assert!(code.ends_with("//# sourceMappingURL=App.js.map\n"));
+  assert!(dir.join("dist/src/App.js.map").is_file());
+  assert!(dir.join("dist/src/nested/Comp.ts").is_file());
+}
+
+#[test]
+fn exit_with_failure_on_errors() {
+  let dir = fixture("error", &[("index.jsx", "<div v-show />")]);
+  let output = cli(&dir, &["index.jsx"]);
+  assert!(!output.status.success());
+
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("error[E61]: v-show is missing expression."));
+  assert!(stderr.contains(" --> index.jsx:1:6"));
+}
@@ -8,7 +8,10 @@ use oxc_ast::{
 use oxc_semantic::NodeId;
 use oxc_span::{GetSpan, SPAN, Span};
 
-use crate::{options::TransformOptions, text::is_empty_text};
+use crate::{
+  options::TransformOptions,
+  text::{is_empty_text, normalize_whitespace},
+};
 
 #[derive(Debug)]
 pub struct RootNode;
@@ -64,7 +67,7 @@ impl<'a> RootNode {
         ),
       )
     });
-    let children =
+    let mut children =
       match expression {
         Expression::JSXFragment(mut node) => {
           is_fragment = true;
@@ -107,6 +110,7 @@ impl<'a> RootNode {
         }
         _ => ast.vec(),
       };
+    normalize_whitespace(&mut children, options.whitespace, false, ast);
 
     let mut is_single_root = false;
     if !is_fragment {
@@ -182,6 +186,16 @@ impl<'a> RootNode {
   }
 }
 
+// Merges spans into the one generated code is mapped to. Synthesized nodes
+// use `SPAN` or markers with `start >= end` (see `RootNode`), those are skipped.
+pub fn source_span(spans: impl IntoIterator<Item = Span>) -> Span {
+  spans
+    .into_iter()
+    .filter(|span| span.start < span.end)
+    .reduce(|a, b| a.merge(b))
+    .unwrap_or(SPAN)
+}
+
 pub fn get_first_child<'a, 'b>(
   children: &'a oxc_allocator::Vec<JSXChild<'b>>,
 ) -> Option<&'a JSXChild<'b>> {
@@ -8,7 +8,7 @@ use oxc_ast::{
 use oxc_span::GetSpan;
 use phf::phf_set;
 
-use crate::expression::is_globally_allowed;
+use crate::{expression::is_globally_allowed, options::TransformOptions};
 
 pub fn is_template<'a>(node: &'a JSXElement<'a>) -> bool {
   if let JSXElementName::Identifier(name) = &node.opening_element.name {
@@ -140,20 +140,46 @@ pub fn is_jsx_component(node: &JSXElement) -> bool {
   }
 }
 
-pub fn is_custom_element(node: &JSXElement) -> bool {
+pub fn is_custom_element(node: &JSXElement, options: &TransformOptions) -> bool {
   match &node.opening_element.name {
     JSXElementName::Identifier(name) => {
       let tag_name = name.name.as_str();
-      tag_name
-        .chars()
-        .next()
-        .is_some_and(|c| c.is_ascii_lowercase())
-        && tag_name.contains("-")
+      tag_name != "template"
+        && tag_name
+          .chars()
+          .next()
+          .is_some_and(|c| c.is_ascii_lowercase())
+        && options.is_custom_element(tag_name)
     }
     _ => false,
   }
 }
 
+// Matches a tag against a name where `*` stands for any characters and `?`
+// for a single one, e.g. `ion-*`.
+pub fn is_tag_pattern_match(pattern: &str, tag: &str) -> bool {
+  let (pattern, tag) = (pattern.as_bytes(), tag.as_bytes());
+  let (mut p, mut t) = (0, 0);
+  // the last `*` and the position in the tag it currently matches up to
+  let mut star = None;
+  while t < tag.len() {
+    if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == tag[t]) {
+      p += 1;
+      t += 1;
+    } else if p < pattern.len() && pattern[p] == b'*' {
+      star = Some((p, t));
+      p += 1;
+    } else if let Some((star_p, star_t)) = star {
+      p = star_p + 1;
+      t = star_t + 1;
+      star = Some((star_p, t));
+    } else {
+      return false;
+    }
+  }
+  pattern[p..].iter().all(|c| *c == b'*')
+}
+
 pub fn is_native_tag(tag: &str) -> bool {
   !tag.contains("-")
     && tag
@@ -0,0 +1,310 @@
+use std::borrow::Cow;
+
+use oxc_ast::{
+  AstBuilder, AstKind,
+  ast::{
+    BinaryOperator, BindingPattern, CallExpression, Expression, IdentifierReference, NumberBase,
+    ObjectExpression, ObjectPropertyKind, PropertyKind, TSType, TSTypeName, UnaryOperator,
+  },
+};
+use oxc_span::GetSpan;
+
+use crate::options::TransformOptions;
+
+// how many bindings are followed, e.g. `const a = b` then `const b = 'foo'`
+const MAX_DEPTH: usize = 8;
+
+/// A value known at compile time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue<'a> {
+  String(Cow<'a, str>),
+  Number(f64),
+  Boolean(bool),
+  Null,
+  Undefined,
+}
+
+impl<'a> ConstantValue<'a> {
+  /// Like `String(value)` in JavaScript, `None` for numbers printed in
+  /// exponent notation.
+  pub fn to_js_string(&self) -> Option<Cow<'a, str>> {
+    Some(match self {
+      ConstantValue::String(value) => value.clone(),
+      ConstantValue::Number(value) => Cow::Owned(number_to_string(*value)?),
+      ConstantValue::Boolean(value) => Cow::Borrowed(if *value { "true" } else { "false" }),
+      ConstantValue::Null => Cow::Borrowed("null"),
+      ConstantValue::Undefined => Cow::Borrowed("undefined"),
+    })
+  }
+
+  fn is_truthy(&self) -> bool {
+    match self {
+      ConstantValue::String(value) => !value.is_empty(),
+      ConstantValue::Number(value) => *value != 0.0 && !value.is_nan(),
+      ConstantValue::Boolean(value) => *value,
+      ConstantValue::Null | ConstantValue::Undefined => false,
+    }
+  }
+}
+
+fn number_to_string(value: f64) -> Option<String> {
+  if value.is_nan() {
+    Some("NaN".to_string())
+  } else if value.is_infinite() {
+    Some(if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+  } else if value.fract() == 0.0 && value.abs() < 1e21 {
+    Some(format!("{}", value as i128))
+  } else if value.abs() >= 1e-6 && value.abs() < 1e21 {
+    Some(value.to_string())
+  } else {
+    None
+  }
+}
+
+/// Evaluates `node` when it only depends on literals and on `const` bindings
+/// initialized with constants, including the properties of `as const` and
+/// `Object.freeze` objects:
+///
+/// ```js
+/// const SIZES = { small: 'sm' } as const
+/// const cls = `card-${SIZES.small}`
+/// ```
+pub fn evaluate_constant<'a>(
+  node: &Expression<'a>,
+  options: &TransformOptions<'a>,
+) -> Option<ConstantValue<'a>> {
+  evaluate(node, options, 0)
+}
+
+/// Replaces `node` by the literal it evaluates to, so that the backends treat
+/// it as static.
+pub fn fold_constant<'a>(
+  node: &mut Expression<'a>,
+  options: &TransformOptions<'a>,
+  ast: &AstBuilder<'a>,
+) {
+  if node.is_literal() {
+    return;
+  }
+  let span = node.span();
+  let Some(value) = evaluate_constant(node, options) else {
+    return;
+  };
+  *node = match value {
+    ConstantValue::String(value) => ast.expression_string_literal(span, ast.str(&value), None),
+    // negative numbers aren't literals
+    ConstantValue::Number(value) if value.is_finite() && value.is_sign_positive() => {
+      ast.expression_numeric_literal(span, value, None, NumberBase::Decimal)
+    }
+    ConstantValue::Boolean(value) => ast.expression_boolean_literal(span, value),
+    ConstantValue::Null => ast.expression_null_literal(span),
+    _ => return,
+  };
+}
+
+fn evaluate<'a>(
+  node: &Expression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<ConstantValue<'a>> {
+  if depth > MAX_DEPTH {
+    return None;
+  }
+  Some(match node.without_parentheses().get_inner_expression() {
+    Expression::StringLiteral(node) => ConstantValue::String(Cow::Borrowed(node.value.as_str())),
+    Expression::NumericLiteral(node) => ConstantValue::Number(node.value),
+    Expression::BooleanLiteral(node) => ConstantValue::Boolean(node.value),
+    Expression::NullLiteral(_) => ConstantValue::Null,
+    Expression::Identifier(node) => {
+      if node.name == "undefined" && is_global(node, options) {
+        ConstantValue::Undefined
+      } else {
+        evaluate(resolve_const(node, options)?, options, depth + 1)?
+      }
+    }
+    Expression::TemplateLiteral(node) => {
+      let mut result = String::new();
+      for (index, quasi) in node.quasis.iter().enumerate() {
+        result += quasi.value.cooked?.as_str();
+        if let Some(expression) = node.expressions.get(index) {
+          result += &evaluate(expression, options, depth)?.to_js_string()?;
+        }
+      }
+      ConstantValue::String(Cow::Owned(result))
+    }
+    Expression::UnaryExpression(node) => {
+      let argument = evaluate(&node.argument, options, depth)?;
+      match (node.operator, argument) {
+        (UnaryOperator::LogicalNot, argument) => ConstantValue::Boolean(!argument.is_truthy()),
+        (UnaryOperator::UnaryNegation, ConstantValue::Number(value)) => {
+          ConstantValue::Number(-value)
+        }
+        (UnaryOperator::UnaryPlus, ConstantValue::Number(value)) => ConstantValue::Number(value),
+        (UnaryOperator::Void, _) => ConstantValue::Undefined,
+        _ => return None,
+      }
+    }
+    Expression::BinaryExpression(node) if node.operator == BinaryOperator::Addition => {
+      let left = evaluate(&node.left, options, depth)?;
+      let right = evaluate(&node.right, options, depth)?;
+      match (&left, &right) {
+        (ConstantValue::Number(left), ConstantValue::Number(right)) => {
+          ConstantValue::Number(left + right)
+        }
+        (ConstantValue::String(_), _) | (_, ConstantValue::String(_)) => ConstantValue::String(
+          Cow::Owned(format!("{}{}", left.to_js_string()?, right.to_js_string()?)),
+        ),
+        _ => return None,
+      }
+    }
+    Expression::StaticMemberExpression(node) => {
+      let (object, _) = resolve_object(&node.object, options, depth)?;
+      evaluate(
+        get_property(object, node.property.name.as_str())?,
+        options,
+        depth + 1,
+      )?
+    }
+    Expression::ComputedMemberExpression(node) => {
+      let key = evaluate(&node.expression, options, depth)?.to_js_string()?;
+      let (object, _) = resolve_object(&node.object, options, depth)?;
+      evaluate(get_property(object, &key)?, options, depth + 1)?
+    }
+    _ => return None,
+  })
+}
+
+// The object literal `node` refers to, if it can't be mutated. The flag tells
+// whether its nested objects can't be mutated either.
+fn resolve_object<'a, 'b>(
+  node: &'b Expression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<(&'b ObjectExpression<'a>, bool)> {
+  if depth > MAX_DEPTH {
+    return None;
+  }
+  match node.without_parentheses() {
+    // `{ ... } as const` is readonly all the way down
+    Expression::TSAsExpression(node) if is_const_type(&node.type_annotation) => {
+      match node.expression.without_parentheses() {
+        Expression::ObjectExpression(object) => Some((object, true)),
+        _ => None,
+      }
+    }
+    Expression::TSSatisfiesExpression(node) => resolve_object(&node.expression, options, depth),
+    // `Object.freeze({ ... })` only freezes the object itself
+    Expression::CallExpression(node) if is_object_freeze(node, options) => {
+      match node.arguments[0].as_expression()?.without_parentheses() {
+        Expression::ObjectExpression(object) => Some((object, false)),
+        _ => None,
+      }
+    }
+    Expression::Identifier(node) => {
+      resolve_object(resolve_const(node, options)?, options, depth + 1)
+    }
+    Expression::StaticMemberExpression(node) => {
+      let (object, deep) = resolve_object(&node.object, options, depth)?;
+      let value = get_property(object, node.property.name.as_str())?;
+      resolve_nested_object(value, deep, options, depth)
+    }
+    Expression::ComputedMemberExpression(node) => {
+      let key = evaluate(&node.expression, options, depth)?.to_js_string()?;
+      let (object, deep) = resolve_object(&node.object, options, depth)?;
+      resolve_nested_object(get_property(object, &key)?, deep, options, depth)
+    }
+    _ => None,
+  }
+}
+
+fn resolve_nested_object<'a, 'b>(
+  node: &'b Expression<'a>,
+  deep: bool,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<(&'b ObjectExpression<'a>, bool)> {
+  if deep && let Expression::ObjectExpression(object) = node.without_parentheses() {
+    Some((object, true))
+  } else {
+    resolve_object(node, options, depth + 1)
+  }
+}
+
+// The value of the last `key` property, unless a spread, a getter or a
+// computed key might override it.
+fn get_property<'a, 'b>(object: &'b ObjectExpression<'a>, key: &str) -> Option<&'b Expression<'a>> {
+  let mut value = None;
+  for property in &object.properties {
+    let ObjectPropertyKind::ObjectProperty(property) = property else {
+      return None;
+    };
+    let name = property.key.static_name()?;
+    if name == key {
+      if property.kind != PropertyKind::Init {
+        return None;
+      }
+      value = Some(&property.value);
+    }
+  }
+  value
+}
+
+/// The initializer of the `const` binding `node` refers to.
+pub fn resolve_const<'a>(
+  node: &IdentifierReference<'a>,
+  options: &TransformOptions<'a>,
+) -> Option<&'a Expression<'a>> {
+  let semantic = options.semantic.borrow();
+  let scoping = semantic.scoping();
+  let symbol_id = scoping
+    .get_reference(node.reference_id.get()?)
+    .symbol_id()?;
+  if !scoping.symbol_flags(symbol_id).is_const_variable() {
+    return None;
+  }
+  let AstKind::VariableDeclarator(declarator) =
+    semantic.nodes().kind(scoping.symbol_declaration(symbol_id))
+  else {
+    return None;
+  };
+  if !matches!(declarator.id, BindingPattern::BindingIdentifier(_)) {
+    return None;
+  }
+  let init = declarator.init.as_ref()?;
+  // an expression taken out of the tree to be transformed leaves an empty
+  // placeholder behind
+  if init.span().is_empty() {
+    return None;
+  }
+  Some(init)
+}
+
+/// Whether `node` refers to a global, i.e. a name declared nowhere in the file.
+pub fn is_global(node: &IdentifierReference, options: &TransformOptions) -> bool {
+  node.reference_id.get().is_some_and(|reference_id| {
+    options
+      .semantic
+      .borrow()
+      .scoping()
+      .get_reference(reference_id)
+      .symbol_id()
+      .is_none()
+  })
+}
+
+fn is_const_type(node: &TSType) -> bool {
+  matches!(node, TSType::TSTypeReference(node)
+    if matches!(&node.type_name, TSTypeName::IdentifierReference(name) if name.name == "const"))
+}
+
+fn is_object_freeze(node: &CallExpression, options: &TransformOptions) -> bool {
+  if node.arguments.len() == 1
+    && let Expression::StaticMemberExpression(callee) = &node.callee
+    && callee.property.name == "freeze"
+    && let Expression::Identifier(object) = &callee.object
+  {
+    object.name == "Object" && is_global(object, options)
+  } else {
+    false
+  }
+}
@@ -0,0 +1,294 @@
+use std::fmt::{self, Display, Write};
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use serde_json::{Value, json};
+
+use crate::error::{ERROR_MESSAGES, ErrorCodes, WARNING_MESSAGES, WarningCodes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  Error,
+  Warning,
+}
+
+impl Severity {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+  Error(ErrorCodes),
+  Warning(WarningCodes),
+}
+
+impl DiagnosticCode {
+  pub fn as_i32(&self) -> i32 {
+    match self {
+      DiagnosticCode::Error(code) => *code as i32,
+      DiagnosticCode::Warning(code) => *code as i32,
+    }
+  }
+}
+
+// Stable identifiers such as `E28` or `W100`, usable to filter or suppress
+// diagnostics by code.
+impl Display for DiagnosticCode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DiagnosticCode::Error(code) => write!(f, "E{}", *code as i32),
+      DiagnosticCode::Warning(code) => write!(f, "W{}", *code as i32),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+  pub span: Span,
+  pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+  pub message: String,
+  pub span: Span,
+  pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  pub severity: Severity,
+  pub code: DiagnosticCode,
+  pub message: String,
+  pub label: Label,
+  pub related: Vec<Label>,
+  pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+  pub fn error(code: ErrorCodes, span: Span) -> Self {
+    Self::new(
+      Severity::Error,
+      DiagnosticCode::Error(code),
+      // a code without a message still reports where it happened
+      ERROR_MESSAGES
+        .get(&code)
+        .map_or_else(|| format!("{code:?}"), |message| message.to_string()),
+      span,
+    )
+  }
+
+  pub fn warning(code: WarningCodes, span: Span) -> Self {
+    Self::new(
+      Severity::Warning,
+      DiagnosticCode::Warning(code),
+      WARNING_MESSAGES
+        .get(&code)
+        .map_or_else(|| format!("{code:?}"), |message| message.to_string()),
+      span,
+    )
+  }
+
+  fn new(severity: Severity, code: DiagnosticCode, message: String, span: Span) -> Self {
+    Self {
+      severity,
+      code,
+      message,
+      label: Label {
+        span,
+        message: None,
+      },
+      related: vec![],
+      fix: None,
+    }
+  }
+
+  /// A parse or semantic error of oxc. The primary label becomes the label of
+  /// the diagnostic and the others its related labels.
+  pub fn from_oxc(diagnostic: &OxcDiagnostic) -> Self {
+    let mut labels = diagnostic
+      .labels
+      .iter()
+      .flatten()
+      .map(|label| Label {
+        span: Span::new(label.offset() as u32, (label.offset() + label.len()) as u32),
+        message: label.label().map(ToString::to_string),
+      })
+      .collect::<Vec<_>>();
+    let primary = diagnostic
+      .labels
+      .iter()
+      .flatten()
+      .position(|label| label.primary())
+      .unwrap_or(0);
+    let mut result = Self::error(ErrorCodes::SyntaxError, Span::default())
+      .with_message(diagnostic.message.to_string());
+    if primary < labels.len() {
+      result.label = labels.remove(primary);
+    }
+    result.related = labels;
+    if let Some(help) = &diagnostic.help
+      && result.label.message.is_none()
+    {
+      result.label.message = Some(help.to_string());
+    }
+    result
+  }
+
+  pub fn with_message(mut self, message: impl Into<String>) -> Self {
+    self.message = message.into();
+    self
+  }
+
+  pub fn with_label(mut self, message: impl Into<String>) -> Self {
+    self.label.message = Some(message.into());
+    self
+  }
+
+  pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+    self.related.push(Label {
+      span,
+      message: Some(message.into()),
+    });
+    self
+  }
+
+  pub fn with_fix(
+    mut self,
+    message: impl Into<String>,
+    span: Span,
+    replacement: impl Into<String>,
+  ) -> Self {
+    self.fix = Some(Fix {
+      message: message.into(),
+      span,
+      replacement: replacement.into(),
+    });
+    self
+  }
+
+  pub fn is_error(&self) -> bool {
+    self.severity == Severity::Error
+  }
+
+  /// Converts the diagnostic into JSON, in the shape the Node binding returns
+  /// it. Offsets are in bytes, `line` and `column` are 1-based:
+  ///
+  /// ```json
+  /// {
+  ///   "severity": "error",
+  ///   "code": "E61",
+  ///   "message": "v-show is missing expression.",
+  ///   "label": { "start": 5, "end": 11, "line": 1, "column": 6, "message": null },
+  ///   "related": [],
+  ///   "fix": null
+  /// }
+  /// ```
+  pub fn to_json(&self, source: &str) -> Value {
+    let label_to_json = |label: &Label| {
+      let (line, column) = line_column(source, label.span.start);
+      json!({
+        "start": label.span.start,
+        "end": label.span.end,
+        "line": line,
+        "column": column,
+        "message": label.message,
+      })
+    };
+    json!({
+      "severity": self.severity.as_str(),
+      "code": self.code.to_string(),
+      "message": self.message,
+      "label": label_to_json(&self.label),
+      "related": self.related.iter().map(label_to_json).collect::<Vec<_>>(),
+      "fix": self.fix.as_ref().map(|fix| json!({
+        "message": fix.message,
+        "start": fix.span.start,
+        "end": fix.span.end,
+        "replacement": fix.replacement,
+      })),
+    })
+  }
+
+  /// Renders the diagnostic as a code frame:
+  ///
+  /// ```text
+  /// error[E61]: v-show is missing expression.
+  ///  --> index.jsx:1:6
+  ///   |
+  /// 1 | <div v-show />
+  ///   |      ^^^^^^
+  /// ```
+  pub fn render(&self, source: &str, filename: &str) -> String {
+    let labels =
+      std::iter::once((&self.label, '^')).chain(self.related.iter().map(|label| (label, '-')));
+    let gutter = labels
+      .clone()
+      .map(|(label, _)| line_column(source, label.span.start).0.to_string().len())
+      .max()
+      .unwrap_or(1);
+
+    let mut output = String::new();
+    let (line, column) = line_column(source, self.label.span.start);
+    writeln!(
+      output,
+      "{}[{}]: {}",
+      self.severity.as_str(),
+      self.code,
+      self.message
+    )
+    .unwrap();
+    writeln!(output, "{:gutter$}--> {filename}:{line}:{column}", "").unwrap();
+    writeln!(output, "{:gutter$} |", "").unwrap();
+    for (label, marker) in labels {
+      render_label(&mut output, source, label, marker, gutter);
+    }
+    if let Some(fix) = &self.fix {
+      writeln!(output, "{:gutter$} = help: {}", "", fix.message).unwrap();
+    }
+    output
+  }
+}
+
+fn render_label(output: &mut String, source: &str, label: &Label, marker: char, gutter: usize) {
+  let start = (label.span.start as usize).min(source.len());
+  let end = (label.span.end as usize).clamp(start, source.len());
+  let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+  let line_end = source[start..]
+    .find('\n')
+    .map_or(source.len(), |i| start + i);
+  let (line, _) = line_column(source, label.span.start);
+
+  // keep tabs so the markers line up with the source line
+  let indent = source[line_start..start]
+    .chars()
+    .map(|c| if c == '\t' { '\t' } else { ' ' })
+    .collect::<String>();
+  let width = source[start..end.min(line_end)].chars().count().max(1);
+  let markers = marker.to_string().repeat(width);
+
+  writeln!(
+    output,
+    "{line:>gutter$} | {}",
+    &source[line_start..line_end]
+  )
+  .unwrap();
+  match &label.message {
+    Some(message) => writeln!(output, "{:gutter$} | {indent}{markers} {message}", "").unwrap(),
+    None => writeln!(output, "{:gutter$} | {indent}{markers}", "").unwrap(),
+  }
+}
+
+/// 1-based line and column (in characters) of a byte offset.
+pub fn line_column(source: &str, offset: u32) -> (usize, usize) {
+  let offset = (offset as usize).min(source.len());
+  let before = &source[..offset];
+  let line = before.matches('\n').count() + 1;
+  let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+  (line, source[line_start..offset].chars().count() + 1)
+}
@@ -1,11 +1,11 @@
 use std::borrow::Cow;
 
-use oxc_allocator::TakeIn;
+use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
   AstBuilder,
   ast::{
     ArrayExpressionElement, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName,
-    JSXAttributeValue, JSXElement,
+    JSXAttributeValue, JSXElement, JSXExpression,
   },
 };
 use oxc_span::{SPAN, SourceType, Span};
@@ -15,6 +15,8 @@ use crate::{
     is_custom_element, is_event_option_modifier, is_jsx_component, is_keyboard_event,
     is_non_key_modifier, is_simple_identifier, maybe_key_modifier,
   },
+  diagnostic::Diagnostic,
+  error::ErrorCodes,
   expression::{jsx_attribute_value_to_expression, parse_expression},
   options::TransformOptions,
   text::get_tag_name,
@@ -260,7 +262,7 @@ impl<'a> Directives<'a> {
     let mut directives = Directives {
       tag_name: get_tag_name(element, options),
       is_component: is_jsx_component(element),
-      is_custom_element: is_custom_element(element),
+      is_custom_element: is_custom_element(element, options),
       ..Default::default()
     };
     for dir in element.opening_element.attributes.iter_mut() {
@@ -289,3 +291,86 @@ impl<'a> Directives<'a> {
     directives
   }
 }
+
+pub struct DirectiveTransformContext<'a> {
+  pub ast: AstBuilder<'a>,
+  pub source_text: &'a str,
+  pub tag: &'a str,
+  pub is_component: bool,
+}
+
+#[derive(Default)]
+pub struct CustomDirectiveResult<'a> {
+  // Attributes added to the element. They are transformed like the ones
+  // written in JSX, so `onClick` becomes a listener and `title` a prop.
+  pub props: Vec<(String, Expression<'a>)>,
+  // Whether the runtime directive should still be applied.
+  pub need_runtime: bool,
+  // Reported as a diagnostic, the runtime directive is then kept as is.
+  pub error: Option<String>,
+}
+
+// Compile-time transform for a custom directive, registered by name (without
+// the `v-` prefix) in `TransformOptions::directive_transforms`.
+pub trait DirectiveTransform {
+  fn transform<'a>(
+    &self,
+    dir: &DirectiveNode<'a>,
+    context: &DirectiveTransformContext<'a>,
+  ) -> CustomDirectiveResult<'a>;
+}
+
+impl<F> DirectiveTransform for F
+where
+  F: for<'a> Fn(&DirectiveNode<'a>, &DirectiveTransformContext<'a>) -> CustomDirectiveResult<'a>,
+{
+  fn transform<'a>(
+    &self,
+    dir: &DirectiveNode<'a>,
+    context: &DirectiveTransformContext<'a>,
+  ) -> CustomDirectiveResult<'a> {
+    self(dir, context)
+  }
+}
+
+// Runs the registered transform of a custom directive. Returns the generated
+// attributes and whether the runtime directive is still needed, or `None`
+// when no transform is registered for `dir_name` or it failed.
+pub fn apply_directive_transform<'a>(
+  prop: &JSXAttribute<'a>,
+  dir_name: &str,
+  directives: &Directives<'a>,
+  options: &'a TransformOptions<'a>,
+) -> Option<(Vec<&'a mut JSXAttribute<'a>>, bool)> {
+  let transform = options.directive_transforms.get(dir_name)?;
+  let ast = AstBuilder::new(&options.allocator);
+  // resolve a copy, the original attribute is still needed by the runtime directive
+  let dir = resolve_directive(ast.allocator.alloc(prop.clone_in(ast.allocator)), &ast);
+  let result = transform.transform(
+    &dir,
+    &DirectiveTransformContext {
+      ast,
+      source_text: *options.source_text.borrow(),
+      tag: directives.tag_name,
+      is_component: directives.is_component,
+    },
+  );
+  if let Some(message) = result.error {
+    options.report(
+      Diagnostic::error(ErrorCodes::DirectiveTransformFailed, prop.span).with_message(message),
+    );
+    return None;
+  }
+  let attributes = result
+    .props
+    .into_iter()
+    .map(|(name, value)| {
+      ast.allocator.alloc(ast.jsx_attribute(
+        prop.span,
+        ast.jsx_attribute_name_identifier(prop.span, ast.str(&name)),
+        Some(ast.jsx_attribute_value_expression_container(prop.span, JSXExpression::from(value))),
+      ))
+    })
+    .collect();
+  Some((attributes, result.need_runtime))
+}
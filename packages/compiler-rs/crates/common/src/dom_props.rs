@@ -0,0 +1,69 @@
+use phf::phf_map;
+
+// How a DOM property is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomProp {
+  // `''` is `true` like for a boolean attribute, other values are coerced
+  Boolean,
+  // `null` and `undefined` clear it, and the unstringified value is kept as
+  // `el._value` for `v-model`, like `setValue`
+  Value,
+}
+
+// DOM properties of native elements that always exist on them, so they can
+// be written without the `key in el` check of `setProp`. Form controls and
+// media only take their initial state from the attribute.
+static DOM_PROPS: phf::Map<&'static str, &'static [(&'static str, DomProp)]> = phf_map! {
+  "input" => &[
+    ("value", DomProp::Value),
+    ("checked", DomProp::Boolean),
+    ("indeterminate", DomProp::Boolean),
+    ("disabled", DomProp::Boolean),
+    ("readOnly", DomProp::Boolean),
+    ("required", DomProp::Boolean),
+    ("multiple", DomProp::Boolean),
+  ],
+  "textarea" => &[
+    ("value", DomProp::Value),
+    ("disabled", DomProp::Boolean),
+    ("readOnly", DomProp::Boolean),
+    ("required", DomProp::Boolean),
+  ],
+  "select" => &[
+    ("disabled", DomProp::Boolean),
+    ("required", DomProp::Boolean),
+    ("multiple", DomProp::Boolean),
+  ],
+  "option" => &[
+    ("selected", DomProp::Boolean),
+    ("disabled", DomProp::Boolean),
+  ],
+  "optgroup" => &[("disabled", DomProp::Boolean)],
+  "button" => &[("disabled", DomProp::Boolean)],
+  "fieldset" => &[("disabled", DomProp::Boolean)],
+  "audio" => &[
+    ("muted", DomProp::Boolean),
+    ("loop", DomProp::Boolean),
+    ("controls", DomProp::Boolean),
+    ("autoplay", DomProp::Boolean),
+  ],
+  "video" => &[
+    ("muted", DomProp::Boolean),
+    ("loop", DomProp::Boolean),
+    ("controls", DomProp::Boolean),
+    ("autoplay", DomProp::Boolean),
+    ("playsInline", DomProp::Boolean),
+  ],
+  "details" => &[("open", DomProp::Boolean)],
+  "dialog" => &[("open", DomProp::Boolean)],
+};
+
+// The name of the DOM property `key` writes, and how. Like the attributes, the
+// key is case-insensitive, so `readonly` writes `readOnly`.
+pub fn get_dom_prop(tag: &str, key: &str) -> Option<(&'static str, DomProp)> {
+  DOM_PROPS
+    .get(tag)?
+    .iter()
+    .find(|(name, _)| name.eq_ignore_ascii_case(key))
+    .copied()
+}
@@ -0,0 +1,23 @@
+// Plain sum types used across the IR, so the core crates don't depend on
+// `napi` for its `Either` family.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+  A(A),
+  B(B),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either3<A, B, C> {
+  A(A),
+  B(B),
+  C(C),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either4<A, B, C, D> {
+  A(A),
+  B(B),
+  C(C),
+  D(D),
+}
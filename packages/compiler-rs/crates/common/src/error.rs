@@ -1,12 +1,18 @@
 use std::{collections::HashMap, sync::LazyLock};
 
+#[cfg(feature = "napi")]
 use napi::{Env, Error, Result, bindgen_prelude::Object};
+#[cfg(feature = "napi")]
 use napi_derive::napi;
-use oxc_span::Span;
+
+#[cfg(feature = "napi")]
+use crate::diagnostic::Diagnostic;
 
 #[cfg_attr(feature = "napi", napi)]
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorCodes {
+  // parse and semantic errors reported by oxc
+  SyntaxError = 0,
   VIfNoExpression = 28,
   VElseNoAdjacentIf = 30,
   VForNoExpression = 31,
@@ -30,10 +36,17 @@ pub enum ErrorCodes {
   VModelUnnecessaryValue = 60,
   VShowNoExpression = 61,
   VSlotsNoExpression = 62,
+  TeleportMissingTarget = 63,
+  TeleportInvalidTarget = 64,
+  SuspenseInvalidSlot = 65,
+  SuspenseInvalidChildren = 66,
+  InvalidHtmlNesting = 67,
+  DirectiveTransformFailed = 68,
 }
 
 pub static ERROR_MESSAGES: LazyLock<HashMap<ErrorCodes, &str>> = LazyLock::new(|| {
   HashMap::from([
+    (ErrorCodes::SyntaxError, "Invalid JavaScript syntax."),
     (
       ErrorCodes::VIfNoExpression,
       "v-if/v-else-if is missing expression.",
@@ -120,25 +133,87 @@ pub static ERROR_MESSAGES: LazyLock<HashMap<ErrorCodes, &str>> = LazyLock::new(|
       ErrorCodes::KeepAliveInvalidChildren,
       "<KeepAlive> expects exactly one child component.",
     ),
+    (
+      ErrorCodes::TeleportMissingTarget,
+      "<Teleport> is missing a `to` target.",
+    ),
+    (
+      ErrorCodes::TeleportInvalidTarget,
+      "<Teleport> `to` must be a selector string or an element.",
+    ),
+    (
+      ErrorCodes::SuspenseInvalidSlot,
+      "<Suspense> only accepts `default` and `fallback` slots.",
+    ),
+    (
+      ErrorCodes::SuspenseInvalidChildren,
+      "<Suspense> slots expect a single root node.",
+    ),
+    (
+      ErrorCodes::InvalidHtmlNesting,
+      "Invalid HTML nesting, the browser will re-parent the element.",
+    ),
+    (
+      ErrorCodes::DirectiveTransformFailed,
+      "The transform of a custom directive failed.",
+    ),
+  ])
+});
+
+#[cfg_attr(feature = "napi", napi)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningCodes {
+  VOnDelegateOnComponent = 100,
+  VOnDelegateUnsupportedEvent = 101,
+  TeleportDisabledString = 102,
+  InvalidHtmlNesting = 103,
+}
+
+pub static WARNING_MESSAGES: LazyLock<HashMap<WarningCodes, &str>> = LazyLock::new(|| {
+  HashMap::from([
+    (
+      WarningCodes::VOnDelegateOnComponent,
+      ".delegate modifier is only supported on native DOM elements. The modifier will be ignored.",
+    ),
+    (
+      WarningCodes::VOnDelegateUnsupportedEvent,
+      ".delegate modifier is not supported on this event. The listener will be attached directly.",
+    ),
+    (
+      WarningCodes::TeleportDisabledString,
+      "`disabled=\"false\"` is a non-empty string and will disable the <Teleport>. Use `disabled={false}` instead.",
+    ),
+    (
+      WarningCodes::InvalidHtmlNesting,
+      "Invalid HTML nesting, the browser will re-parent the element.",
+    ),
   ])
 });
 
+#[cfg(feature = "napi")]
 #[napi(object, js_name = "CompilerError extends SyntaxError")]
 pub struct CompilerError {
   pub code: i32,
   pub loc: Option<(u32, u32)>,
+  pub severity: String,
+  pub frame: Option<String>,
 }
 
-pub fn create_compiler_error<'a>(env: &'a Env, code: ErrorCodes, loc: Span) -> Result<Object<'a>> {
-  let msg = ERROR_MESSAGES.get(&code).unwrap().to_string();
-  let mut error = env.create_error(Error::from_reason(&msg))?;
-  error.set("code", code as i32)?;
-  error.set("loc", (loc.start, loc.end))?;
+#[cfg(feature = "napi")]
+pub fn create_compiler_error<'a>(
+  env: &'a Env,
+  diagnostic: &Diagnostic,
+  frame: Option<String>,
+) -> Result<Object<'a>> {
+  let mut error = env.create_error(Error::from_reason(&diagnostic.message))?;
+  error.set("code", diagnostic.code.as_i32())?;
+  error.set(
+    "loc",
+    (diagnostic.label.span.start, diagnostic.label.span.end),
+  )?;
+  error.set("severity", diagnostic.severity.as_str())?;
+  if let Some(frame) = frame {
+    error.set("frame", frame)?;
+  }
   Ok(error)
 }
-
-pub fn create_compiler_warning<'a>(env: &'a Env, message: &str, loc: Span) -> Result<Object<'a>> {
-  let mut warning = env.create_error(Error::from_reason(message))?;
-  warning.set("loc", (loc.start, loc.end))?;
-  Ok(warning)
-}
@@ -1,11 +1,17 @@
 pub mod ast;
 pub mod check;
+pub mod constant;
+pub mod diagnostic;
 pub mod directive;
 pub mod dom;
+pub mod dom_props;
+pub mod either;
 pub mod error;
 pub mod expression;
+pub mod modifier;
 pub mod options;
 pub mod patch_flag;
 pub mod text;
+pub mod text_type;
 pub mod walk;
 pub mod walk_mut;
@@ -0,0 +1,293 @@
+use std::borrow::Cow;
+
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{
+    BinaryOperator, BindingPattern, Expression, FormalParameterKind, LogicalOperator, NumberBase,
+    Statement, UnaryOperator,
+  },
+};
+use oxc_span::SPAN;
+use phf::phf_map;
+
+use crate::text::{camelize, capitalize};
+
+// same as `keyNames` of `withKeys`, a key modifier also matches its alias
+static KEY_ALIASES: phf::Map<&'static str, &'static str> = phf_map! {
+  "esc" => "escape",
+  "space" => " ",
+  "up" => "arrow-up",
+  "left" => "arrow-left",
+  "right" => "arrow-right",
+  "down" => "arrow-down",
+  "delete" => "backspace",
+};
+
+const SYSTEM_MODIFIERS: [&str; 4] = ["ctrl", "shift", "alt", "meta"];
+
+/// Whether the guards of the modifiers are known at compile time. `left` and
+/// `right` on a dynamic event name are key or mouse modifiers depending on
+/// the event.
+pub fn can_inline_modifiers(keys: &[Cow<str>], non_keys: &[Cow<str>]) -> bool {
+  !keys.iter().any(|key| non_keys.contains(key))
+}
+
+/// Whether the guards can run `handler` itself on every event: an identifier
+/// or member expression is called, and the body of an arrow function taking
+/// the event as a plain parameter is inlined. Any other expression, like
+/// `getHandler()`, is evaluated once by `withModifiers`.
+pub fn is_inlinable_handler(handler: &Expression) -> bool {
+  match handler.without_parentheses() {
+    Expression::Identifier(_) => true,
+    Expression::ArrowFunctionExpression(arrow) => match arrow.params.items.first() {
+      Some(param) => param.pattern.is_binding_identifier() && param.initializer.is_none(),
+      None => arrow.params.rest.is_none(),
+    },
+    handler => handler.is_member_expression(),
+  }
+}
+
+/// Wraps `handler` in an arrow function running the guards of `withKeys`
+/// and `withModifiers`, in the order the runtime helpers would:
+///
+/// ```js
+/// (_e) => {
+///   if (_e.key !== "Enter") return;
+///   _e.stopPropagation();
+///   if (!_e.ctrlKey) return;
+///   return handler(_e);
+/// }
+/// ```
+///
+/// The body of an arrow function handler is inlined after the guards, under
+/// its own parameters. A member expression is called without binding `this`,
+/// like the helpers do.
+pub fn gen_modifier_guards<'a>(
+  mut handler: Expression<'a>,
+  keys: &[Cow<'a, str>],
+  non_keys: &[Cow<'a, str>],
+  ast: &AstBuilder<'a>,
+) -> Expression<'a> {
+  while let Expression::ParenthesizedExpression(parenthesized) = handler {
+    handler = parenthesized.unbox().expression;
+  }
+
+  let mut r#async = false;
+  let mut params = ast.vec();
+  let mut rest = None;
+  let mut body = ast.vec();
+  if let Expression::ArrowFunctionExpression(arrow) = handler {
+    let arrow = arrow.unbox();
+    r#async = arrow.r#async;
+    params = arrow.params.unbox().items;
+    let mut statements = arrow.body.unbox().statements;
+    if arrow.expression
+      && let Some(Statement::ExpressionStatement(statement)) = statements.pop()
+    {
+      statements.push(ast.statement_return(SPAN, Some(statement.unbox().expression)));
+    }
+    body = statements;
+  } else {
+    let mut arguments = ast.vec1(ast.expression_identifier(SPAN, "_e").into());
+    // `withKeys` only passes the event on
+    if keys.is_empty() {
+      arguments.push(ast.argument_spread_element(SPAN, ast.expression_identifier(SPAN, "_args")));
+      rest = Some(ast.alloc_formal_parameter_rest(
+        SPAN,
+        ast.vec(),
+        ast.binding_rest_element(SPAN, ast.binding_pattern_binding_identifier(SPAN, "_args")),
+        NONE,
+      ));
+    }
+    let callee = if handler.is_member_expression() {
+      // `(0, obj.method)(_e)`
+      ast.expression_sequence(
+        SPAN,
+        ast.vec_from_array([
+          ast.expression_numeric_literal(SPAN, 0.0, None, NumberBase::Decimal),
+          handler,
+        ]),
+      )
+    } else {
+      handler
+    };
+    body.push(ast.statement_return(
+      SPAN,
+      Some(ast.expression_call(SPAN, callee, NONE, arguments, false)),
+    ));
+  }
+  // the guards read the event from the first parameter
+  let event = match params.first().map(|param| &param.pattern) {
+    Some(BindingPattern::BindingIdentifier(event)) => event.name.as_str(),
+    _ => {
+      params
+        .push(ast.plain_formal_parameter(SPAN, ast.binding_pattern_binding_identifier(SPAN, "_e")));
+      "_e"
+    }
+  };
+
+  let mut statements = ast.vec();
+  if !keys.is_empty() {
+    statements.push(return_if(key_guard(keys, event, ast), ast));
+  }
+  for modifier in non_keys {
+    let test = match modifier.as_ref() {
+      "stop" | "prevent" => {
+        let method = if modifier == "stop" {
+          "stopPropagation"
+        } else {
+          "preventDefault"
+        };
+        statements.push(ast.statement_expression(
+          SPAN,
+          ast.expression_call(
+            SPAN,
+            event_member(event, method, ast),
+            NONE,
+            ast.vec(),
+            false,
+          ),
+        ));
+        continue;
+      }
+      "self" => ast.expression_binary(
+        SPAN,
+        event_member(event, "target", ast),
+        BinaryOperator::StrictInequality,
+        event_member(event, "currentTarget", ast),
+      ),
+      "ctrl" | "shift" | "alt" | "meta" => ast.expression_unary(
+        SPAN,
+        UnaryOperator::LogicalNot,
+        event_member(event, &format!("{modifier}Key"), ast),
+      ),
+      "left" | "middle" | "right" => {
+        let button = match modifier.as_ref() {
+          "left" => 0.0,
+          "middle" => 1.0,
+          _ => 2.0,
+        };
+        ast.expression_logical(
+          SPAN,
+          ast.expression_binary(
+            SPAN,
+            ast.expression_string_literal(SPAN, "button", None),
+            BinaryOperator::In,
+            ast.expression_identifier(SPAN, event),
+          ),
+          LogicalOperator::And,
+          ast.expression_binary(
+            SPAN,
+            event_member(event, "button", ast),
+            BinaryOperator::StrictInequality,
+            ast.expression_numeric_literal(SPAN, button, None, NumberBase::Decimal),
+          ),
+        )
+      }
+      "exact" => {
+        // any system key that isn't listed
+        let Some(test) = SYSTEM_MODIFIERS
+          .iter()
+          .filter(|system| !non_keys.iter().any(|modifier| modifier == *system))
+          .map(|system| event_member(event, &format!("{system}Key"), ast))
+          .reduce(|left, right| ast.expression_logical(SPAN, left, LogicalOperator::Or, right))
+        else {
+          continue;
+        };
+        test
+      }
+      // the runtime has no guard for it either
+      _ => continue,
+    };
+    statements.push(return_if(test, ast));
+  }
+  statements.extend(body);
+
+  ast.expression_arrow_function(
+    SPAN,
+    false,
+    r#async,
+    NONE,
+    ast.formal_parameters(
+      SPAN,
+      FormalParameterKind::ArrowFormalParameters,
+      params,
+      rest,
+    ),
+    NONE,
+    ast.function_body(SPAN, ast.vec(), statements),
+  )
+}
+
+// `withKeys` compares the hyphenated `event.key` with the modifiers, so
+// `page-down` matches `PageDown` and `a` matches `a` and `A`
+fn key_guard<'a>(keys: &[Cow<'a, str>], event: &'a str, ast: &AstBuilder<'a>) -> Expression<'a> {
+  let mut values: Vec<String> = vec![];
+  for key in keys {
+    for name in [key.as_ref()]
+      .into_iter()
+      .chain(KEY_ALIASES.get(key.as_ref()).copied())
+    {
+      // a hyphenated key is lowercase
+      if name.chars().any(|char| char.is_ascii_uppercase()) {
+        continue;
+      }
+      let matches = if name.chars().count() == 1 {
+        vec![name.to_string(), name.to_uppercase()]
+      } else {
+        vec![capitalize(camelize(Cow::Borrowed(name))).into_owned()]
+      };
+      for value in matches {
+        if !values.contains(&value) {
+          values.push(value);
+        }
+      }
+    }
+  }
+
+  if let [value] = values.as_slice() {
+    return ast.expression_binary(
+      SPAN,
+      event_member(event, "key", ast),
+      BinaryOperator::StrictInequality,
+      ast.expression_string_literal(SPAN, ast.str(value), None),
+    );
+  }
+  let includes = ast.alloc_static_member_expression(
+    SPAN,
+    ast.expression_array(
+      SPAN,
+      ast.vec_from_iter(values.iter().map(|value| {
+        ast
+          .expression_string_literal(SPAN, ast.str(value), None)
+          .into()
+      })),
+    ),
+    ast.identifier_name(SPAN, "includes"),
+    false,
+  );
+  ast.expression_unary(
+    SPAN,
+    UnaryOperator::LogicalNot,
+    ast.expression_call(
+      SPAN,
+      Expression::StaticMemberExpression(includes),
+      NONE,
+      ast.vec1(event_member(event, "key", ast).into()),
+      false,
+    ),
+  )
+}
+
+fn event_member<'a>(event: &'a str, property: &str, ast: &AstBuilder<'a>) -> Expression<'a> {
+  Expression::StaticMemberExpression(ast.alloc_static_member_expression(
+    SPAN,
+    ast.expression_identifier(SPAN, event),
+    ast.identifier_name(SPAN, ast.str(property)),
+    false,
+  ))
+}
+
+fn return_if<'a>(test: Expression<'a>, ast: &AstBuilder<'a>) -> Statement<'a> {
+  ast.statement_if(SPAN, test, ast.statement_return(SPAN, None), None)
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "napi")]
 use napi_derive::napi;
 use oxc_allocator::Allocator;
 use oxc_semantic::Semantic;
@@ -7,11 +8,20 @@ use std::{
 };
 
 use indexmap::IndexMap;
-use napi::Either;
 use oxc_ast::ast::Expression;
 use oxc_span::{SourceType, Span};
+use serde_json::Value;
 
-use crate::error::ErrorCodes;
+use crate::{
+  check::{is_native_tag, is_tag_pattern_match},
+  diagnostic::{Diagnostic, DiagnosticCode},
+  directive::DirectiveTransform,
+  dom::is_valid_html_nesting,
+  either::Either,
+  error::{ErrorCodes, WarningCodes},
+  patch_flag::TemplateFlags,
+  text::stable_hash,
+};
 
 pub struct RootJsx<'a> {
   pub node_ptr: *mut Expression<'a>,
@@ -23,6 +33,7 @@ type OnEnterExpression<'a> =
 type OnLeaveExpression<'a> = Box<dyn Fn(&Expression) + 'a>;
 type CreateRootJSX<'a> = Box<dyn Fn(*mut Expression<'a>, bool) -> RootJsx<'a> + 'a>;
 
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "napi", napi(object))]
 pub struct Hmr {
   /**
@@ -33,6 +44,59 @@ pub struct Hmr {
   pub define_component_name: Vec<String>,
 }
 
+// How a compile-time check is reported.
+#[cfg_attr(feature = "napi", napi(string_enum = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticLevel {
+  Off,
+  #[default]
+  Warn,
+  Error,
+}
+
+// How whitespace in JSX text is handled. `<pre>` and `<textarea>` always keep
+// their text as written.
+#[cfg_attr(feature = "napi", napi(string_enum = "kebab-case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Whitespace {
+  // trim text around line breaks and drop text made only of whitespace
+  #[default]
+  Condense,
+  // keep text as written
+  Preserve,
+  // join the lines of a text with a space, like React
+  JsxReact,
+}
+
+// How Vapor writes the DOM properties of `get_dom_prop`.
+#[cfg_attr(feature = "napi", napi(string_enum = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DomPropWrites {
+  // through the runtime helpers, for smaller bundles
+  #[default]
+  Compact,
+  // as guarded assignments like `el.checked !== value && (el.checked = value)`
+  Inline,
+}
+
+// Tags configured by the user, either by name (see `is_tag_pattern_match`) or
+// with a callback.
+pub enum TagMatcher<'a> {
+  Patterns(Vec<String>),
+  Predicate(Box<dyn Fn(&str) -> bool + 'a>),
+}
+
+impl TagMatcher<'_> {
+  pub fn matches(&self, tag: &str) -> bool {
+    match self {
+      TagMatcher::Patterns(patterns) => patterns
+        .iter()
+        .any(|pattern| is_tag_pattern_match(pattern, tag)),
+      TagMatcher::Predicate(predicate) => predicate(tag),
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct SlotScope<'a> {
   pub dynamic: bool,
@@ -40,12 +104,34 @@ pub struct SlotScope<'a> {
   pub identifiers: Vec<&'a str>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Template {
   pub content: String,
   pub ns: i32,
   pub root: bool,
   pub _static: bool,
+  // the JSX the template was first created for, used for source maps
+  pub span: Span,
+}
+
+impl Template {
+  pub fn flags(&self) -> i32 {
+    (if self.root {
+      TemplateFlags::ROOT as i32
+    } else {
+      0
+    }) | if self._static {
+      TemplateFlags::STATIC as i32
+    } else {
+      0
+    }
+  }
+
+  // Identifies a template across files, the flags and namespace are part of
+  // it since they change how `template()` creates the nodes.
+  pub fn hash(&self) -> String {
+    stable_hash(&format!("{}:{}:{}", self.flags(), self.ns, self.content))
+  }
 }
 
 pub struct TransformOptions<'a> {
@@ -56,7 +142,12 @@ pub struct TransformOptions<'a> {
   pub delegates: RefCell<BTreeSet<&'a str>>,
   pub hoists: RefCell<Vec<Expression<'a>>>,
   pub on_error: Box<dyn Fn(ErrorCodes, Span) + 'a>,
-  pub on_warn: Box<dyn Fn(&str, Span) + 'a>,
+  // Receives the code instead of the message since diagnostics were added.
+  // To migrate a `Fn(&str, Span)` callback, look the message up in
+  // `WARNING_MESSAGES`, or read the `diagnostics` after the transform, which
+  // carry the message, labels and fix of every warning.
+  pub on_warn: Box<dyn Fn(WarningCodes, Span) + 'a>,
+  pub diagnostics: RefCell<Vec<Diagnostic>>,
   pub create_root_jsx: RefCell<Option<CreateRootJSX<'a>>>,
   pub on_enter_expression: RefCell<Option<OnEnterExpression<'a>>>,
   pub on_leave_expression: RefCell<Option<OnLeaveExpression<'a>>>,
@@ -79,6 +170,29 @@ pub struct TransformOptions<'a> {
   pub runtime_module_name: Option<String>,
   pub merge_props: bool,
   pub scope_identifiers_map: RefCell<HashMap<Span, (bool, Vec<&'a str>)>>,
+  pub dump_ir: bool,
+  pub ir: RefCell<Vec<Value>>,
+  pub directive_transforms: HashMap<String, Box<dyn DirectiveTransform + 'a>>,
+  // return the template table in `TransformOutput::templates`
+  pub report_templates: bool,
+  // import the templates from this module by their hash instead of declaring
+  // them in every file, see `shared_template_module`
+  pub shared_templates: Option<String>,
+  // report children that the browser moves out of their parent, like a
+  // `<div>` in a `<p>`
+  pub html_nesting: DiagnosticLevel,
+  pub whitespace: Whitespace,
+  // lower children typed as strings or numbers to `setText` instead of
+  // `setNodes`, see `is_text_type_expression`
+  pub type_aware_text: bool,
+  pub dom_prop_writes: DomPropWrites,
+  // compile event modifiers to guards in the handler instead of wrapping it
+  // with `withModifiers` and `withKeys`
+  pub inline_modifiers: bool,
+  // extra tags created as custom elements, on top of the ones with a dash
+  pub custom_element: Option<TagMatcher<'a>>,
+  // when set, lowercase tags it doesn't match are custom elements
+  pub native_tag: Option<TagMatcher<'a>>,
 }
 
 impl<'a> Default for TransformOptions<'a> {
@@ -96,6 +210,7 @@ impl<'a> Default for TransformOptions<'a> {
       source_map: false,
       on_error: Box::new(|_, _| {}),
       on_warn: Box::new(|_, _| {}),
+      diagnostics: RefCell::new(vec![]),
       interop: false,
       hmr: Either::A(false),
       ssr: false,
@@ -114,6 +229,18 @@ impl<'a> Default for TransformOptions<'a> {
       runtime_module_name: None,
       merge_props: true,
       scope_identifiers_map: RefCell::new(HashMap::new()),
+      dump_ir: false,
+      ir: RefCell::new(vec![]),
+      directive_transforms: HashMap::new(),
+      report_templates: false,
+      shared_templates: None,
+      html_nesting: DiagnosticLevel::Warn,
+      whitespace: Whitespace::Condense,
+      type_aware_text: false,
+      dom_prop_writes: DomPropWrites::Compact,
+      inline_modifiers: false,
+      custom_element: None,
+      native_tag: None,
     }
   }
 }
@@ -135,4 +262,56 @@ impl<'a> TransformOptions<'a> {
       }
     }
   }
+
+  pub fn error(&self, code: ErrorCodes, span: Span) {
+    self.report(Diagnostic::error(code, span));
+  }
+
+  pub fn warn(&self, code: WarningCodes, span: Span) {
+    self.report(Diagnostic::warning(code, span));
+  }
+
+  pub fn check_html_nesting(&self, parent: &str, child: &str, span: Span) {
+    if is_valid_html_nesting(parent, child) {
+      return;
+    }
+    let diagnostic = match self.html_nesting {
+      DiagnosticLevel::Off => return,
+      DiagnosticLevel::Warn => Diagnostic::warning(WarningCodes::InvalidHtmlNesting, span),
+      DiagnosticLevel::Error => Diagnostic::error(ErrorCodes::InvalidHtmlNesting, span),
+    };
+    self.report(diagnostic.with_message(format!(
+      "`<{child}>` cannot be a child of `<{parent}>`; the browser will re-parent it."
+    )));
+  }
+
+  // Custom elements aren't part of templates and get their props set as DOM
+  // properties when possible.
+  pub fn is_custom_element(&self, tag: &str) -> bool {
+    if let Some(custom_element) = &self.custom_element
+      && custom_element.matches(tag)
+    {
+      return true;
+    }
+    match &self.native_tag {
+      Some(native_tag) => !native_tag.matches(tag),
+      None => tag.contains('-'),
+    }
+  }
+
+  pub fn is_native_tag(&self, tag: &str) -> bool {
+    let is_native_tag = match &self.native_tag {
+      Some(native_tag) => native_tag.matches(tag),
+      None => is_native_tag(tag),
+    };
+    is_native_tag && !self.is_custom_element(tag)
+  }
+
+  pub fn report(&self, diagnostic: Diagnostic) {
+    match diagnostic.code {
+      DiagnosticCode::Error(code) => self.on_error.as_ref()(code, diagnostic.label.span),
+      DiagnosticCode::Warning(code) => self.on_warn.as_ref()(code, diagnostic.label.span),
+    }
+    self.diagnostics.borrow_mut().push(diagnostic);
+  }
 }
@@ -2,9 +2,12 @@ use std::borrow::Cow;
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 use html_escape::decode_html_entities;
-use oxc_ast::ast::{Expression, JSXChild, JSXElement, JSXElementName, JSXExpression, JSXText};
+use oxc_ast::{
+  AstBuilder,
+  ast::{Expression, JSXChild, JSXElement, JSXElementName, JSXExpression, JSXText},
+};
 
-use crate::options::TransformOptions;
+use crate::options::{TransformOptions, Whitespace};
 
 fn is_all_empty_text(s: &str) -> bool {
   let mut has_newline = s.is_empty();
@@ -46,6 +49,10 @@ fn ends_with_newline_and_spaces(s: &str) -> bool {
 }
 
 pub fn resolve_jsx_text<'a>(node: &'a JSXText) -> Cow<'a, str> {
+  // already resolved by `normalize_whitespace`
+  if node.raw.is_none() {
+    return Cow::Borrowed(node.value.as_str());
+  }
   if is_all_empty_text(&node.value) {
     return Cow::Borrowed("");
   }
@@ -79,8 +86,96 @@ pub fn resolve_jsx_text<'a>(node: &'a JSXText) -> Cow<'a, str> {
   value
 }
 
+// Applies the `whitespace` option, and keeps the text of `<pre>` and
+// `<textarea>` as written. Text that `resolve_jsx_text` would condense is
+// replaced with a text holding its resolved value and no raw value, which
+// both backends then keep as is.
+pub fn normalize_whitespace<'a>(
+  children: &mut oxc_allocator::Vec<'a, JSXChild<'a>>,
+  whitespace: Whitespace,
+  preformatted: bool,
+  ast: &AstBuilder<'a>,
+) {
+  for child in children.iter_mut() {
+    match child {
+      JSXChild::Element(node) => {
+        let is_preformatted_tag = matches!(
+          &node.opening_element.name,
+          JSXElementName::Identifier(name) if matches!(name.name.as_str(), "pre" | "textarea")
+        );
+        // the HTML parser drops a newline right after `<pre>`, so it is only
+        // formatting
+        if is_preformatted_tag
+          && let Some(JSXChild::Text(text)) = node.children.first_mut()
+          && let Some(value) = text
+            .value
+            .strip_prefix("\r\n")
+            .or_else(|| text.value.strip_prefix('\n'))
+        {
+          text.value = ast.str(value);
+        }
+        normalize_whitespace(
+          &mut node.children,
+          whitespace,
+          preformatted || is_preformatted_tag,
+          ast,
+        );
+      }
+      JSXChild::Fragment(node) => {
+        normalize_whitespace(&mut node.children, whitespace, preformatted, ast)
+      }
+      JSXChild::Text(node) => {
+        let value = {
+          let value = match whitespace {
+            _ if preformatted => decode_html_entities(&node.value),
+            Whitespace::Condense => continue,
+            Whitespace::Preserve => decode_html_entities(&node.value),
+            Whitespace::JsxReact => {
+              Cow::Owned(decode_html_entities(&clean_jsx_text(&node.value)).into_owned())
+            }
+          };
+          ast.str(&value)
+        };
+        // an empty text is removed like the whitespace between tags
+        *child = ast.jsx_child_text(node.span, value, None);
+      }
+      _ => (),
+    }
+  }
+}
+
+// Same as React: lines are trimmed, empty lines removed and the rest joined
+// with a single space.
+fn clean_jsx_text(value: &str) -> String {
+  let lines = value
+    .split("\r\n")
+    .flat_map(|line| line.split(['\n', '\r']))
+    .collect::<Vec<_>>();
+  let last_non_empty_line = lines
+    .iter()
+    .rposition(|line| line.contains(|c: char| c != ' ' && c != '\t'));
+  let mut result = String::new();
+  for (index, line) in lines.iter().enumerate() {
+    let mut line = line.replace('\t', " ");
+    if index != 0 {
+      line = line.trim_start_matches(' ').to_string();
+    }
+    if index != lines.len() - 1 {
+      line = line.trim_end_matches(' ').to_string();
+    }
+    if !line.is_empty() {
+      result += &line;
+      if Some(index) != last_non_empty_line {
+        result.push(' ');
+      }
+    }
+  }
+  result
+}
+
 pub fn is_empty_text(node: &JSXChild) -> bool {
   match node {
+    JSXChild::Text(node) if node.raw.is_none() => node.value.is_empty(),
     JSXChild::Text(node) => is_all_empty_text(&node.value),
     JSXChild::ExpressionContainer(node) => {
       matches!(node.expression, JSXExpression::EmptyExpression(_))
@@ -212,8 +307,35 @@ pub fn escape_html<'a>(s: Cow<'a, str>) -> Cow<'a, str> {
   Cow::Owned(html)
 }
 
+// Serializes an attribute for an HTML template as ` name`, ` name=value` or
+// ` name="value"`.
+pub fn stringify_attribute(name: &str, value: &str) -> String {
+  if value.is_empty() {
+    return format!(" {name}");
+  }
+  // The attribute value can remain unquoted if it doesn't contain ASCII whitespace
+  // or any of " ' ` = < or >.
+  // https://html.spec.whatwg.org/multipage/introduction.html#intro-early-example
+  let needs_quotes =
+    value.contains(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '=' | '<' | '>'));
+  if needs_quotes {
+    format!(r#" {name}="{}""#, value.replace("\"", "&quot;"))
+  } else {
+    format!(" {name}={value}")
+  }
+}
+
 pub fn hash_string(s: &str) -> String {
   let mut hasher = DefaultHasher::new();
   s.hash(&mut hasher);
   format!("{:x}", hasher.finish())
 }
+
+// FNV-1a, unlike `DefaultHasher` it is the same across Rust versions and
+// platforms, so it can identify content shared between builds.
+pub fn stable_hash(s: &str) -> String {
+  let hash = s.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  });
+  format!("{hash:x}")
+}
@@ -0,0 +1,423 @@
+use oxc_allocator::Vec;
+use oxc_ast::{
+  AstKind,
+  ast::{
+    BindingPattern, CallExpression, Expression, Function, IdentifierReference, TSLiteral,
+    TSSignature, TSType, TSTypeName, UnaryOperator,
+  },
+};
+
+use crate::{
+  constant::{is_global, resolve_const},
+  options::TransformOptions,
+};
+
+// how many bindings and type aliases are followed
+const MAX_DEPTH: usize = 8;
+
+// reactive APIs whose `.value` has the type of their type argument
+const REF_APIS: [&str; 3] = ["ref", "shallowRef", "computed"];
+const REF_TYPES: [&str; 4] = ["Ref", "ShallowRef", "ComputedRef", "WritableComputedRef"];
+
+// methods of strings and numbers returning a string or a number
+const TEXT_METHODS: [&str; 22] = [
+  "at",
+  "charAt",
+  "charCodeAt",
+  "codePointAt",
+  "concat",
+  "indexOf",
+  "lastIndexOf",
+  "normalize",
+  "padEnd",
+  "padStart",
+  "repeat",
+  "replace",
+  "replaceAll",
+  "slice",
+  "substring",
+  "toExponential",
+  "toFixed",
+  "toLocaleString",
+  "toLowerCase",
+  "toPrecision",
+  "toString",
+  "toUpperCase",
+];
+
+enum Binding<'a> {
+  // declared with a type annotation
+  Type(&'a TSType<'a>),
+  // initializer of a `const` binding
+  Init(&'a Expression<'a>),
+  Function(&'a Function<'a>),
+}
+
+enum ResolvedType<'a> {
+  Alias(&'a TSType<'a>),
+  Interface(&'a Vec<'a, TSSignature<'a>>),
+}
+
+/// Whether `node` always evaluates to a string, a number, a bigint, `null` or
+/// `undefined`, which render the same as a text node as with `setNodes`.
+///
+/// Besides literals and operators, it reads TypeScript annotations of the
+/// bindings `node` refers to, including props and `ref<number>()`:
+///
+/// ```tsx
+/// const count = ref<number>(0)
+/// const label = (props: { name: string }) => <p>{props.name}: {count.value}</p>
+/// ```
+pub fn is_text_type_expression<'a>(node: &Expression<'a>, options: &TransformOptions<'a>) -> bool {
+  is_text(node, options, 0)
+}
+
+fn is_text<'a>(node: &Expression<'a>, options: &TransformOptions<'a>, depth: usize) -> bool {
+  if depth > MAX_DEPTH {
+    return false;
+  }
+  match node.without_parentheses() {
+    Expression::StringLiteral(_)
+    | Expression::NumericLiteral(_)
+    | Expression::BigIntLiteral(_)
+    | Expression::NullLiteral(_)
+    | Expression::TemplateLiteral(_)
+    | Expression::UpdateExpression(_) => true,
+    // `+` converts objects to primitives, the other operators to numbers
+    Expression::BinaryExpression(node) => {
+      node.operator.is_arithmetic() || node.operator.is_bitwise()
+    }
+    Expression::UnaryExpression(node) => matches!(
+      node.operator,
+      UnaryOperator::UnaryNegation
+        | UnaryOperator::UnaryPlus
+        | UnaryOperator::BitwiseNot
+        | UnaryOperator::Typeof
+        | UnaryOperator::Void
+    ),
+    Expression::LogicalExpression(node) => {
+      is_text(&node.left, options, depth) && is_text(&node.right, options, depth)
+    }
+    Expression::ConditionalExpression(node) => {
+      is_text(&node.consequent, options, depth) && is_text(&node.alternate, options, depth)
+    }
+    Expression::TSAsExpression(node) => is_text_type(&node.type_annotation, options, depth),
+    Expression::TSSatisfiesExpression(node) => is_text(&node.expression, options, depth),
+    Expression::TSNonNullExpression(node) => is_text(&node.expression, options, depth),
+    Expression::Identifier(node) => {
+      if node.name == "undefined" || node.name == "NaN" || node.name == "Infinity" {
+        return is_global(node, options);
+      }
+      match resolve_binding(node, options) {
+        Some(Binding::Type(ty)) => is_text_type(ty, options, depth + 1),
+        Some(Binding::Init(init)) => is_text(init, options, depth + 1),
+        _ => false,
+      }
+    }
+    Expression::StaticMemberExpression(member) => {
+      if member.property.name == "value"
+        && let Expression::Identifier(object) = &member.object
+        && let Some(Binding::Init(init)) = resolve_binding(object, options)
+        && let Some(text) = is_text_ref(init, options, depth + 1)
+      {
+        return text;
+      }
+      if member.property.name == "length" && is_text(&member.object, options, depth) {
+        return true;
+      }
+      declared_type(node, options, depth).is_some_and(|ty| is_text_type(ty, options, depth + 1))
+    }
+    Expression::CallExpression(node) => is_text_call(node, options, depth),
+    _ => false,
+  }
+}
+
+fn is_text_call<'a>(
+  node: &CallExpression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> bool {
+  match node.callee.without_parentheses() {
+    Expression::Identifier(callee) => {
+      if matches!(
+        callee.name.as_str(),
+        "String" | "Number" | "BigInt" | "parseInt" | "parseFloat"
+      ) && is_global(callee, options)
+      {
+        return true;
+      }
+      match resolve_binding(callee, options) {
+        Some(Binding::Function(function)) => function
+          .return_type
+          .as_ref()
+          .is_some_and(|ty| is_text_type(&ty.type_annotation, options, depth + 1)),
+        Some(Binding::Init(init)) => returns_text(init, options, depth + 1),
+        _ => false,
+      }
+    }
+    Expression::StaticMemberExpression(callee) => {
+      if let Expression::Identifier(object) = &callee.object
+        && object.name == "Math"
+        && is_global(object, options)
+      {
+        return true;
+      }
+      TEXT_METHODS.contains(&callee.property.name.as_str())
+        && is_text(&callee.object, options, depth)
+    }
+    _ => false,
+  }
+}
+
+// Whether the function `node` returns text, from its return type or the body
+// of an arrow function.
+fn returns_text<'a>(
+  node: &'a Expression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> bool {
+  match node.without_parentheses() {
+    Expression::ArrowFunctionExpression(node) => {
+      if let Some(ty) = &node.return_type {
+        is_text_type(&ty.type_annotation, options, depth)
+      } else {
+        node
+          .get_expression()
+          .is_some_and(|body| is_text(body, options, depth))
+      }
+    }
+    Expression::FunctionExpression(node) => node
+      .return_type
+      .as_ref()
+      .is_some_and(|ty| is_text_type(&ty.type_annotation, options, depth)),
+    _ => false,
+  }
+}
+
+// Whether the `.value` of `ref()`, `shallowRef()` or `computed()` is text,
+// `None` when `node` isn't one of them.
+fn is_text_ref<'a>(
+  node: &'a Expression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<bool> {
+  let Expression::CallExpression(node) = node.without_parentheses() else {
+    return None;
+  };
+  let Expression::Identifier(callee) = &node.callee else {
+    return None;
+  };
+  if !REF_APIS.contains(&callee.name.as_str()) || !is_import_or_global(callee, options) {
+    return None;
+  }
+  if let Some(type_arguments) = &node.type_arguments {
+    return Some(
+      type_arguments
+        .params
+        .first()
+        .is_some_and(|ty| is_text_type(ty, options, depth)),
+    );
+  }
+  let argument = node.arguments.first()?.as_expression()?;
+  Some(if callee.name == "computed" {
+    returns_text(argument, options, depth)
+  } else {
+    // without a type argument, only TypeScript keeps other values out of it
+    options.source_type.borrow().is_typescript() && is_text(argument, options, depth)
+  })
+}
+
+fn is_text_type<'a>(node: &TSType<'a>, options: &TransformOptions<'a>, depth: usize) -> bool {
+  if depth > MAX_DEPTH {
+    return false;
+  }
+  match node {
+    TSType::TSStringKeyword(_)
+    | TSType::TSNumberKeyword(_)
+    | TSType::TSBigIntKeyword(_)
+    | TSType::TSTemplateLiteralType(_) => true,
+    TSType::TSLiteralType(node) => !matches!(node.literal, TSLiteral::BooleanLiteral(_)),
+    // nullish values render nothing either way
+    TSType::TSUnionType(node) => node.types.iter().all(|ty| {
+      matches!(ty, TSType::TSNullKeyword(_) | TSType::TSUndefinedKeyword(_))
+        || is_text_type(ty, options, depth)
+    }),
+    TSType::TSParenthesizedType(node) => is_text_type(&node.type_annotation, options, depth),
+    TSType::TSTypeReference(_) => matches!(
+      resolve_type(node, options),
+      Some(ResolvedType::Alias(ty)) if is_text_type(ty, options, depth + 1)
+    ),
+    _ => false,
+  }
+}
+
+// The declared type of a binding or of a property of one, like `props.name`
+// for `props: { name: string }`.
+fn declared_type<'a>(
+  node: &Expression<'a>,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<&'a TSType<'a>> {
+  if depth > MAX_DEPTH {
+    return None;
+  }
+  match node.without_parentheses() {
+    Expression::Identifier(node) => match resolve_binding(node, options)? {
+      Binding::Type(ty) => Some(ty),
+      _ => None,
+    },
+    Expression::StaticMemberExpression(node) => {
+      let object = declared_type(&node.object, options, depth + 1)?;
+      property_type(object, node.property.name.as_str(), options, depth + 1)
+    }
+    Expression::TSNonNullExpression(node) => declared_type(&node.expression, options, depth),
+    _ => None,
+  }
+}
+
+fn property_type<'a>(
+  node: &'a TSType<'a>,
+  key: &str,
+  options: &TransformOptions<'a>,
+  depth: usize,
+) -> Option<&'a TSType<'a>> {
+  if depth > MAX_DEPTH {
+    return None;
+  }
+  let members = match node {
+    TSType::TSTypeLiteral(node) => &node.members,
+    TSType::TSParenthesizedType(node) => {
+      return property_type(&node.type_annotation, key, options, depth);
+    }
+    TSType::TSTypeReference(reference) => {
+      if key == "value"
+        && let TSTypeName::IdentifierReference(name) = &reference.type_name
+        && REF_TYPES.contains(&name.name.as_str())
+      {
+        return reference.type_arguments.as_ref()?.params.first();
+      }
+      return match resolve_type(node, options)? {
+        ResolvedType::Alias(ty) => property_type(ty, key, options, depth + 1),
+        ResolvedType::Interface(members) => find_property(members, key),
+      };
+    }
+    _ => return None,
+  };
+  find_property(members, key)
+}
+
+fn find_property<'a>(members: &'a Vec<'a, TSSignature<'a>>, key: &str) -> Option<&'a TSType<'a>> {
+  members.iter().rev().find_map(|member| match member {
+    TSSignature::TSPropertySignature(member)
+      if !member.computed && member.key.static_name().is_some_and(|name| name == key) =>
+    {
+      member
+        .type_annotation
+        .as_ref()
+        .map(|ty| &ty.type_annotation)
+    }
+    _ => None,
+  })
+}
+
+// The binding `node` refers to, if its value is known from its declaration.
+fn resolve_binding<'a>(
+  node: &IdentifierReference<'a>,
+  options: &TransformOptions<'a>,
+) -> Option<Binding<'a>> {
+  let semantic = options.semantic.borrow();
+  let scoping = semantic.scoping();
+  let symbol_id = scoping
+    .get_reference(node.reference_id.get()?)
+    .symbol_id()?;
+  let name = node.name.as_str();
+  match semantic.nodes().kind(scoping.symbol_declaration(symbol_id)) {
+    AstKind::VariableDeclarator(declarator) => {
+      if let Some(ty) = &declarator.type_annotation {
+        return pattern_type(&declarator.id, &ty.type_annotation, name, options).map(Binding::Type);
+      }
+      resolve_const(node, options).map(Binding::Init)
+    }
+    AstKind::FormalParameter(param) => {
+      let ty = &param.type_annotation.as_ref()?.type_annotation;
+      pattern_type(&param.pattern, ty, name, options).map(Binding::Type)
+    }
+    AstKind::Function(function) => Some(Binding::Function(function)),
+    _ => None,
+  }
+}
+
+// The type of `name` in `pattern` annotated with `ty`, following object
+// destructuring like `{ count }: { count: number }`.
+fn pattern_type<'a>(
+  pattern: &'a BindingPattern<'a>,
+  ty: &'a TSType<'a>,
+  name: &str,
+  options: &TransformOptions<'a>,
+) -> Option<&'a TSType<'a>> {
+  match pattern {
+    BindingPattern::BindingIdentifier(_) => Some(ty),
+    BindingPattern::AssignmentPattern(pattern) => pattern_type(&pattern.left, ty, name, options),
+    BindingPattern::ObjectPattern(pattern) => pattern.properties.iter().find_map(|property| {
+      if property.computed || !binds(&property.value, name) {
+        return None;
+      }
+      let key = property.key.static_name()?;
+      let ty = property_type(ty, &key, options, 0)?;
+      pattern_type(&property.value, ty, name, options)
+    }),
+    _ => None,
+  }
+}
+
+fn binds(pattern: &BindingPattern, name: &str) -> bool {
+  match pattern {
+    BindingPattern::BindingIdentifier(id) => id.name == name,
+    BindingPattern::AssignmentPattern(pattern) => binds(&pattern.left, name),
+    BindingPattern::ObjectPattern(pattern) => pattern
+      .properties
+      .iter()
+      .any(|property| binds(&property.value, name)),
+    _ => false,
+  }
+}
+
+// The type alias or the interface a type reference without type arguments
+// refers to.
+fn resolve_type<'a>(node: &TSType<'a>, options: &TransformOptions<'a>) -> Option<ResolvedType<'a>> {
+  let TSType::TSTypeReference(node) = node else {
+    return None;
+  };
+  let TSTypeName::IdentifierReference(name) = &node.type_name else {
+    return None;
+  };
+  if node.type_arguments.is_some() {
+    return None;
+  }
+  let semantic = options.semantic.borrow();
+  let scoping = semantic.scoping();
+  let symbol_id = scoping
+    .get_reference(name.reference_id.get()?)
+    .symbol_id()?;
+  match semantic.nodes().kind(scoping.symbol_declaration(symbol_id)) {
+    AstKind::TSTypeAliasDeclaration(alias) if alias.type_parameters.is_none() => {
+      Some(ResolvedType::Alias(&alias.type_annotation))
+    }
+    AstKind::TSInterfaceDeclaration(interface) if interface.type_parameters.is_none() => {
+      Some(ResolvedType::Interface(&interface.body.body))
+    }
+    _ => None,
+  }
+}
+
+// `ref` and friends are either imported or auto-imported
+fn is_import_or_global(node: &IdentifierReference, options: &TransformOptions) -> bool {
+  let semantic = options.semantic.borrow();
+  let scoping = semantic.scoping();
+  node.reference_id.get().is_some_and(|reference_id| {
+    scoping
+      .get_reference(reference_id)
+      .symbol_id()
+      .is_none_or(|symbol_id| scoping.symbol_flags(symbol_id).is_import())
+  })
+}
@@ -45,6 +45,12 @@ impl<'a, 'opt> WalkIdentifiers<'a, 'opt> {
     if id.span.eq(&SPAN) && !id.name.eq("_cache") {
       return;
     }
+    // generated helper calls keep the span of their JSX for source maps
+    if let Some(helper) = id.name.strip_prefix('_')
+      && self.options.helpers.borrow().contains(helper)
+    {
+      return;
+    }
     let semantic = &self.options.semantic.borrow();
     let mut is_local = false;
     let mut scope_parent_id = Some(semantic.nodes().get_node(id.node_id()).scope_id());
@@ -43,6 +43,8 @@ pub struct CodegenContext<'a> {
   pub block: RefCell<BlockIRNode<'a>>,
   pub scope_level: RefCell<i32>,
   pub in_slot_block: RefCell<bool>,
+  // the slots being generated belong to a built-in component
+  pub in_built_in_slots: RefCell<bool>,
   pub ast: &'a AstBuilder<'a>,
 }
 
@@ -59,6 +61,7 @@ impl<'a> CodegenContext<'a> {
       block: RefCell::new(block),
       scope_level: RefCell::new(0),
       in_slot_block: RefCell::new(false),
+      in_built_in_slots: RefCell::new(false),
       ir,
       ast: context.ast,
     }
@@ -97,6 +100,11 @@ impl<'a> CodegenContext<'a> {
     move || *self.in_slot_block.borrow_mut() = parent
   }
 
+  pub fn enter_component_slots(&self, built_in: bool) -> impl FnOnce() {
+    let parent = self.in_built_in_slots.replace(built_in);
+    move || *self.in_built_in_slots.borrow_mut() = parent
+  }
+
   pub fn enter_block(
     &self,
     block: BlockIRNode<'a>,
@@ -219,7 +227,7 @@ impl<'a> CodegenContext<'a> {
     if !self.options.delegates.borrow().is_empty() {
       self.options.helper("_delegateEvents");
     }
-    if !&self.options.templates.borrow().is_empty() {
+    if !&self.options.templates.borrow().is_empty() && self.options.shared_templates.is_none() {
       self.options.helper("_template");
     }
 
@@ -2,18 +2,20 @@ use std::cell::RefCell;
 use std::mem::{self};
 use std::rc::Rc;
 
-use napi::Either;
 use oxc_ast::NONE;
 use oxc_ast::ast::{
   ArrayExpressionElement, Expression, FormalParameter, FormalParameterKind, Statement,
 };
-use oxc_span::SPAN;
+use oxc_span::{GetSpan, SPAN};
 
 use crate::generate::CodegenContext;
+use crate::generate::expression::gen_expression;
 use crate::generate::operation::gen_operations;
 use crate::generate::template::gen_self;
-use crate::ir::index::{BlockIRNode, ForIRNode, IRDynamicInfo, IREffect, IfIRNode, OperationNode};
-use common::patch_flag::VaporSlotFlags;
+use crate::ir::index::{
+  BlockIRNode, ForIRNode, IRDynamicInfo, IREffect, IRMemo, IfIRNode, OperationNode,
+};
+use common::{ast::source_span, either::Either, patch_flag::VaporSlotFlags};
 
 pub fn gen_block<'a>(
   oper: BlockIRNode<'a>,
@@ -100,16 +102,15 @@ pub fn gen_block_content<'a>(
         }
 
         if effect_index < effect_end {
-          if let Some(statement) = gen_effects(
+          gen_effects(
+            statements,
             unsafe { &mut *context_block }
               .effect
               .drain(0..effect_end - effect_index)
               .collect::<_>(),
             context,
             context_block,
-          ) {
-            statements.push(statement);
-          };
+          );
           effect_index = effect_end
         }
       };
@@ -133,13 +134,12 @@ pub fn gen_block_content<'a>(
     context,
     unsafe { &mut *context_block },
   );
-  if let Some(statement) = gen_effects(
+  gen_effects(
+    &mut statements,
     mem::take(&mut unsafe { &mut *context_block }.effect),
     context,
     context_block,
-  ) {
-    statements.push(statement);
-  }
+  );
   if let Some(gen_extra_frag) = gen_effects_extra_frag {
     gen_extra_frag(&mut statements, unsafe { &mut *context_block })
   }
@@ -177,13 +177,69 @@ pub fn gen_block_content<'a>(
 }
 
 fn gen_effects<'a>(
+  statements: &mut oxc_allocator::Vec<'a, Statement<'a>>,
   effects: Vec<IREffect<'a>>,
   context: &'a CodegenContext<'a>,
   context_block: *mut BlockIRNode<'a>,
+) {
+  // an effect that reads nothing reactive only runs once, so its operations
+  // don't need a render effect
+  let (once, effects): (Vec<_>, Vec<_>) = effects
+    .into_iter()
+    .partition(|effect| effect.is_static(context.options));
+  for effect in once {
+    gen_operations(statements, effect.operations, None, context, unsafe {
+      &mut *context_block
+    });
+  }
+  // an effect reading the same state as one after the next dynamic node is
+  // merged into it, so e.g. fields disabled by `props.disabled` around a
+  // component share a render effect. Its first write then happens after the
+  // dynamic nodes in between are created.
+  let later = &mut unsafe { &mut *context_block }.effect;
+  let effects = effects
+    .into_iter()
+    .filter_map(|mut effect| {
+      if let Some(dependencies) = effect.dependencies(context.source_text)
+        && let Some(later) = later
+          .iter_mut()
+          .find(|later| later.dependencies(context.source_text).as_ref() == Some(&dependencies))
+      {
+        effect.operations.append(&mut later.operations);
+        later.operations = effect.operations;
+        None
+      } else {
+        Some(effect)
+      }
+    })
+    .collect::<Vec<_>>();
+  // effects under the same v-memo share a render effect, so the memo is
+  // checked once for the whole subtree
+  let mut groups: Vec<(Vec<u32>, Vec<IREffect<'a>>)> = vec![];
+  for effect in effects {
+    let ids = effect.memo.iter().map(|memo| memo.id).collect::<Vec<_>>();
+    if let Some((_, group)) = groups.iter_mut().find(|(group_ids, _)| *group_ids == ids) {
+      group.push(effect);
+    } else {
+      groups.push((ids, vec![effect]));
+    }
+  }
+  for (_, effects) in groups {
+    if let Some(statement) = gen_effect_group(effects, context, context_block) {
+      statements.push(statement);
+    }
+  }
+}
+
+fn gen_effect_group<'a>(
+  mut effects: Vec<IREffect<'a>>,
+  context: &'a CodegenContext<'a>,
+  context_block: *mut BlockIRNode<'a>,
 ) -> Option<Statement<'a>> {
   let ast = &context.ast;
   let mut statements = ast.vec();
   let mut operations_count = 0;
+  let memo = mem::take(&mut effects[0].memo);
 
   for effect in effects {
     operations_count += effect.operations.len();
@@ -193,40 +249,86 @@ fn gen_effects<'a>(
   }
 
   if operations_count > 0 {
-    Some(
-      ast.statement_expression(
+    let span = source_span(statements.iter().map(GetSpan::span));
+    // a single inline write becomes the body of the effect
+    if statements.len() == 1
+      && let Some(Statement::BlockStatement(block)) = statements.first_mut()
+    {
+      statements = mem::replace(&mut block.body, ast.vec());
+    }
+    let mut effect = ast.expression_arrow_function(
+      SPAN,
+      statements.len() == 1 && matches!(statements[0], Statement::ExpressionStatement(_)),
+      false,
+      NONE,
+      ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec(),
+        NONE,
+      ),
+      NONE,
+      ast.function_body(SPAN, ast.vec(), statements),
+    );
+    effect = gen_with_memo(effect, memo, context);
+    Some(ast.statement_expression(
+      span,
+      ast.expression_call(
         SPAN,
-        ast.expression_call(
-          SPAN,
-          ast.expression_identifier(SPAN, ast.str(context.options.helper("_renderEffect"))),
-          NONE,
-          ast.vec1(
-            ast
-              .expression_arrow_function(
-                SPAN,
-                operations_count == 1,
-                false,
-                NONE,
-                ast.formal_parameters(
-                  SPAN,
-                  FormalParameterKind::ArrowFormalParameters,
-                  ast.vec(),
-                  NONE,
-                ),
-                NONE,
-                ast.function_body(SPAN, ast.vec(), statements),
-              )
-              .into(),
-          ),
-          false,
-        ),
+        ast.expression_identifier(SPAN, ast.str(context.options.helper("_renderEffect"))),
+        NONE,
+        ast.vec1(effect.into()),
+        false,
       ),
-    )
+    ))
   } else {
     None
   }
 }
 
+// _withVaporMemo(() => [deps], () => {}), the innermost memo is checked last
+pub fn gen_with_memo<'a>(
+  mut effect: Expression<'a>,
+  memo: Vec<IRMemo<'a>>,
+  context: &'a CodegenContext<'a>,
+) -> Expression<'a> {
+  let ast = &context.ast;
+  for memo in memo.into_iter().rev() {
+    effect = ast.expression_call(
+      SPAN,
+      ast.expression_identifier(SPAN, ast.str(context.options.helper("_withVaporMemo"))),
+      NONE,
+      ast.vec_from_array([
+        ast
+          .expression_arrow_function(
+            SPAN,
+            true,
+            false,
+            NONE,
+            ast.formal_parameters(
+              SPAN,
+              FormalParameterKind::ArrowFormalParameters,
+              ast.vec(),
+              NONE,
+            ),
+            NONE,
+            ast.function_body(
+              SPAN,
+              ast.vec(),
+              ast.vec1(
+                ast.statement_expression(SPAN, gen_expression(memo.deps, context, None, false)),
+              ),
+            ),
+          )
+          .into(),
+        effect.into(),
+      ]),
+      false,
+    );
+  }
+  effect
+}
+
 pub fn mark_slot_root_operations<'a>(
   block: &mut BlockIRNode<'a>,
   context: &CodegenContext<'a>,
@@ -339,18 +441,13 @@ pub fn has_stable_slot_root<'a>(block: &mut BlockIRNode<'a>, context: &CodegenCo
       continue;
     };
 
-    match operation.as_mut() {
-      OperationNode::CreateComponent(_) => {
-        has_valid_root = true;
-        continue;
-      }
-      OperationNode::Key(operation) => {
-        if has_stable_slot_root(&mut operation.block, context) {
-          has_valid_root = true;
-          continue;
-        }
-      }
-      _ => {}
+    let is_valid_root = match operation.as_mut() {
+      OperationNode::CreateComponent(_) => true,
+      OperationNode::Key(operation) => has_stable_slot_root(&mut operation.block, context),
+      _ => false,
+    };
+    if is_valid_root {
+      has_valid_root = true;
     }
   }
   has_valid_root
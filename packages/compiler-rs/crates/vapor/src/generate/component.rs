@@ -1,12 +1,11 @@
 use std::borrow::Cow;
 use std::mem;
 
-use common::check::is_constant_node;
 use common::directive::Modifiers;
 use common::directive::get_modifier_prop_name;
 use common::text::capitalize;
+use common::{check::is_constant_node, either::Either3};
 use indexmap::IndexMap;
-use napi::bindgen_prelude::Either3;
 use oxc_allocator::CloneIn;
 use oxc_ast::NONE;
 use oxc_ast::ast::BinaryOperator;
@@ -52,6 +51,7 @@ pub fn gen_create_component<'a>(
     id,
     asset,
     is_custom_element,
+    built_in,
     ..
   } = operation;
 
@@ -75,7 +75,9 @@ pub fn gen_create_component<'a>(
 
   let raw_props = gen_raw_props(props, context);
   let _context_block = context_block as *mut BlockIRNode;
+  let exit_slots = context.enter_component_slots(built_in);
   let raw_slots = gen_raw_slots(slots, context, unsafe { &mut *_context_block });
+  exit_slots();
 
   let mut arguments = ast.vec1(tag);
   if let Some(raw_props) = raw_props {
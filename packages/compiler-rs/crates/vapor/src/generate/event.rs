@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 use common::directive::Modifiers;
+use common::modifier::{can_inline_modifiers, gen_modifier_guards, is_inlinable_handler};
 use oxc_ast::NONE;
 use oxc_ast::ast::{Expression, FormalParameterKind, ObjectPropertyKind, PropertyKind, Statement};
 use oxc_span::SPAN;
@@ -64,7 +65,22 @@ pub fn gen_set_event<'a>(
     }));
     Some(ast.expression_object(SPAN, properties))
   };
-  let handler = gen_event_handler(context, vec![value], &keys, &non_keys, false);
+  // a listener bound once can run inlined guards, a rebound listener or a
+  // component prop would create them again each time
+  let handler = if context.options.inline_modifiers
+    && !effect
+    && (!keys.is_empty() || !non_keys.is_empty())
+    && can_inline_modifiers(&keys, &non_keys)
+  {
+    let handler = gen_expression(value, context, None, false);
+    if is_inlinable_handler(&handler) {
+      gen_modifier_guards(handler, &keys, &non_keys, ast)
+    } else {
+      gen_runtime_guards(context, handler, &keys, &non_keys)
+    }
+  } else {
+    gen_event_handler(context, vec![value], &keys, &non_keys, false)
+  };
 
   if delegate {
     // key is static
@@ -123,6 +139,38 @@ pub fn gen_event_handler<'a>(
     values.next().unwrap()
   };
 
+  handler_exp = gen_runtime_guards(context, handler_exp, keys, non_keys);
+
+  if extra_wrap {
+    handler_exp = ast.expression_arrow_function(
+      SPAN,
+      true,
+      false,
+      NONE,
+      ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec(),
+        NONE,
+      ),
+      NONE,
+      ast.function_body(
+        SPAN,
+        ast.vec(),
+        ast.vec1(ast.statement_expression(SPAN, handler_exp)),
+      ),
+    )
+  }
+  handler_exp
+}
+
+fn gen_runtime_guards<'a>(
+  context: &'a CodegenContext<'a>,
+  mut handler_exp: Expression<'a>,
+  keys: &[Cow<'a, str>],
+  non_keys: &[Cow<'a, str>],
+) -> Expression<'a> {
+  let ast = &context.ast;
   if !non_keys.is_empty() {
     handler_exp = ast.expression_call(
       SPAN,
@@ -166,27 +214,6 @@ pub fn gen_event_handler<'a>(
       false,
     )
   }
-
-  if extra_wrap {
-    handler_exp = ast.expression_arrow_function(
-      SPAN,
-      true,
-      false,
-      NONE,
-      ast.formal_parameters(
-        SPAN,
-        FormalParameterKind::ArrowFormalParameters,
-        ast.vec(),
-        NONE,
-      ),
-      NONE,
-      ast.function_body(
-        SPAN,
-        ast.vec(),
-        ast.vec1(ast.statement_expression(SPAN, handler_exp)),
-      ),
-    )
-  }
   handler_exp
 }
 
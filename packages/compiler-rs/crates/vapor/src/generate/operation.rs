@@ -1,6 +1,6 @@
 use oxc_ast::NONE;
 use oxc_ast::ast::{Argument, NumberBase, Statement};
-use oxc_span::SPAN;
+use oxc_span::{GetSpan, GetSpanMut, SPAN};
 
 use crate::generate::CodegenContext;
 use crate::generate::component::gen_create_component;
@@ -54,6 +54,7 @@ pub fn gen_operation_with_insertion_state<'a>(
   context: &'a CodegenContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) {
+  let start = statements.len();
   match &oper {
     OperationNode::If(if_ir_node) => {
       if let Some(parent) = if_ir_node.parent {
@@ -107,6 +108,9 @@ pub fn gen_operation_with_insertion_state<'a>(
     }
     _ => (),
   };
+  if let Some(insertion_state) = statements.get_mut(start) {
+    *insertion_state.span_mut() = oper.span();
+  }
 
   gen_operation(statements, oper, context, context_block);
 }
@@ -116,6 +120,24 @@ pub fn gen_operation<'a>(
   oper: OperationNode<'a>,
   context: &'a CodegenContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
+) {
+  let span = oper.span();
+  let start = statements.len();
+  gen_operation_statements(statements, oper, context, context_block);
+  if span != SPAN {
+    for statement in statements.iter_mut().skip(start) {
+      if statement.span() == SPAN {
+        *statement.span_mut() = span;
+      }
+    }
+  }
+}
+
+fn gen_operation_statements<'a>(
+  statements: &mut oxc_allocator::Vec<'a, Statement<'a>>,
+  oper: OperationNode<'a>,
+  context: &'a CodegenContext<'a>,
+  context_block: &'a mut BlockIRNode<'a>,
 ) {
   match oper {
     OperationNode::If(oper) => statements.push(gen_if(oper, context, context_block, false)),
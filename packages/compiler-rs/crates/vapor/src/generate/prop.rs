@@ -1,21 +1,30 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 
-use common::text::capitalize;
+use common::dom_props::{DomProp, get_dom_prop};
+use common::options::DomPropWrites;
 use common::text::get_text_like_value;
-use napi::bindgen_prelude::Either3;
+use common::{either::Either3, text::capitalize};
 use oxc_allocator::CloneIn;
 use oxc_allocator::TakeIn;
 use oxc_ast::NONE;
 use oxc_ast::ast::ArrayExpressionElement;
+use oxc_ast::ast::AssignmentOperator;
+use oxc_ast::ast::AssignmentTarget;
 use oxc_ast::ast::BinaryOperator;
 use oxc_ast::ast::ConditionalExpression;
 use oxc_ast::ast::Expression;
+use oxc_ast::ast::IdentifierReference;
+use oxc_ast::ast::LogicalOperator;
 use oxc_ast::ast::NumberBase;
 use oxc_ast::ast::ObjectExpression;
 use oxc_ast::ast::ObjectPropertyKind;
 use oxc_ast::ast::PropertyKey;
 use oxc_ast::ast::PropertyKind;
 use oxc_ast::ast::Statement;
+use oxc_ast::ast::UnaryOperator;
+use oxc_ast::ast::VariableDeclarationKind;
+use oxc_ast_visit::Visit;
 use oxc_span::SPAN;
 
 use crate::generate::CodegenContext;
@@ -99,6 +108,7 @@ pub fn gen_set_prop<'a>(oper: SetPropIRNode<'a>, context: &'a CodegenContext<'a>
       ..
     },
     tag,
+    root,
     ..
   } = oper;
 
@@ -113,6 +123,16 @@ pub fn gen_set_prop<'a>(oper: SetPropIRNode<'a>, context: &'a CodegenContext<'a>
   } else {
     ""
   };
+  // the root element skips props overridden by fallthrough attrs, which only
+  // the helpers know about
+  if context.options.dom_prop_writes == DomPropWrites::Inline
+    && modifier.is_none()
+    && !root
+    && values.len() == 1
+    && let Some((property, prop)) = get_dom_prop(tag, key_value)
+  {
+    return gen_inline_dom_prop(element, property, prop, values.remove(0), context);
+  }
   let resolved_helper = get_runtime_helper(tag, key_value, modifier);
   if key_value == "class"
     && !resolved_helper.is_svg
@@ -141,6 +161,171 @@ pub fn gen_set_prop<'a>(oper: SetPropIRNode<'a>, context: &'a CodegenContext<'a>
   )
 }
 
+// {
+//   const _checked = checked === "" || !!checked;
+//   _n0.checked !== _checked && (_n0.checked = _checked);
+// }
+//
+// {
+//   _n0._value = text;
+//   const _value = text == null ? "" : text;
+//   _n0.value !== _value && (_n0.value = _value);
+//   text == null && _n0.removeAttribute("value");
+// }
+fn gen_inline_dom_prop<'a>(
+  element: i32,
+  key: &'a str,
+  prop: DomProp,
+  value: Expression<'a>,
+  context: &'a CodegenContext<'a>,
+) -> Statement<'a> {
+  let ast = &context.ast;
+  let mut statements = ast.vec();
+  let declare = |name: &'a str, init: Expression<'a>| {
+    Statement::VariableDeclaration(ast.alloc_variable_declaration(
+      SPAN,
+      VariableDeclarationKind::Const,
+      ast.vec1(ast.variable_declarator(
+        SPAN,
+        VariableDeclarationKind::Const,
+        ast.binding_pattern_binding_identifier(SPAN, name),
+        NONE,
+        Some(init),
+        false,
+      )),
+      false,
+    ))
+  };
+  let property = |key: &'a str| {
+    ast.alloc_static_member_expression(
+      SPAN,
+      ast.expression_identifier(SPAN, ast.str(&format!("_n{element}"))),
+      ast.identifier_name(SPAN, key),
+      false,
+    )
+  };
+  let is_nullish = |value: &Expression<'a>| {
+    ast.expression_binary(
+      SPAN,
+      value.clone_in(ast.allocator),
+      BinaryOperator::Equality,
+      ast.expression_null_literal(SPAN),
+    )
+  };
+
+  let mut value = gen_expression(value, context, None, false);
+  // the declared names must not shadow what the value reads, e.g. a
+  // `_value` of the user would be read before it's initialized
+  let mut references = ReferenceNames::default();
+  references.visit_expression(&value);
+  let mut unique_name = |base: String| {
+    let mut name = base.clone();
+    let mut index = 0;
+    while references.0.contains(name.as_str()) {
+      index += 1;
+      name = format!("{base}{index}");
+    }
+    let name = ast.str(&name).as_str();
+    references.0.insert(name);
+    name
+  };
+  // evaluate the value once
+  if !matches!(value, Expression::Identifier(_)) {
+    let name = unique_name("_value".to_string());
+    statements.push(declare(name, value));
+    value = ast.expression_identifier(SPAN, name);
+  }
+  let name = unique_name(format!("_{key}"));
+  let resolved = match prop {
+    DomProp::Boolean => ast.expression_logical(
+      SPAN,
+      ast.expression_binary(
+        SPAN,
+        value.clone_in(ast.allocator),
+        BinaryOperator::StrictEquality,
+        ast.expression_string_literal(SPAN, "", None),
+      ),
+      LogicalOperator::Or,
+      ast.expression_unary(
+        SPAN,
+        UnaryOperator::LogicalNot,
+        ast.expression_unary(
+          SPAN,
+          UnaryOperator::LogicalNot,
+          value.clone_in(ast.allocator),
+        ),
+      ),
+    ),
+    DomProp::Value => {
+      statements.push(ast.statement_expression(
+        SPAN,
+        ast.expression_assignment(
+          SPAN,
+          AssignmentOperator::Assign,
+          AssignmentTarget::StaticMemberExpression(property("_value")),
+          value.clone_in(ast.allocator),
+        ),
+      ));
+      ast.expression_conditional(
+        SPAN,
+        is_nullish(&value),
+        ast.expression_string_literal(SPAN, "", None),
+        value.clone_in(ast.allocator),
+      )
+    }
+  };
+  statements.push(declare(name, resolved));
+  statements.push(ast.statement_expression(
+    SPAN,
+    ast.expression_logical(
+      SPAN,
+      ast.expression_binary(
+        SPAN,
+        Expression::StaticMemberExpression(property(key)),
+        BinaryOperator::StrictInequality,
+        ast.expression_identifier(SPAN, name),
+      ),
+      LogicalOperator::And,
+      ast.expression_parenthesized(
+        SPAN,
+        ast.expression_assignment(
+          SPAN,
+          AssignmentOperator::Assign,
+          AssignmentTarget::StaticMemberExpression(property(key)),
+          ast.expression_identifier(SPAN, name),
+        ),
+      ),
+    ),
+  ));
+  if prop == DomProp::Value {
+    statements.push(ast.statement_expression(
+      SPAN,
+      ast.expression_logical(
+        SPAN,
+        is_nullish(&value),
+        LogicalOperator::And,
+        ast.expression_call(
+          SPAN,
+          Expression::StaticMemberExpression(property("removeAttribute")),
+          NONE,
+          ast.vec1(ast.expression_string_literal(SPAN, "value", None).into()),
+          false,
+        ),
+      ),
+    ));
+  }
+  ast.statement_block(SPAN, statements)
+}
+
+#[derive(Default)]
+struct ReferenceNames<'a>(HashSet<&'a str>);
+
+impl<'a> Visit<'a> for ReferenceNames<'a> {
+  fn visit_identifier_reference(&mut self, node: &IdentifierReference<'a>) {
+    self.0.insert(node.name.as_str());
+  }
+}
+
 struct ClassNameEntry<'a> {
   class_name: Cow<'a, str>,
   condition: Option<Expression<'a>>,
@@ -1,7 +1,6 @@
 use std::borrow::Cow;
 
 use indexmap::IndexMap;
-use napi::bindgen_prelude::{Either, Either4};
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -21,7 +20,11 @@ use crate::{
   },
 };
 
-use common::{check::is_simple_identifier, patch_flag::VaporSlotFlags};
+use common::{
+  check::is_simple_identifier,
+  either::{Either, Either4},
+  patch_flag::VaporSlotFlags,
+};
 
 pub fn gen_raw_slots<'a>(
   mut slots: Vec<IRSlots<'a>>,
@@ -439,7 +442,10 @@ fn gen_slot_block_with_props<'a>(
   );
 
   let ast = &context.ast;
-  let has_stable_root = has_stable_slot_root(&mut oper, context);
+  // built-ins render their slots directly rather than through an outlet, so
+  // there is no fallback to track and the roots are treated as stable
+  let has_stable_root =
+    *context.in_built_in_slots.borrow() || has_stable_slot_root(&mut oper, context);
   if !has_stable_root {
     mark_slot_root_operations(&mut oper, context, false);
   }
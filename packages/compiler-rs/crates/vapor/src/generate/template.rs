@@ -42,9 +42,15 @@ pub fn gen_self<'a>(
   if let Some(id) = id
     && let Some(template) = template
   {
+    let span = context
+      .options
+      .templates
+      .borrow()
+      .get(template as usize)
+      .map_or(SPAN, |template| template.span);
     statements.push(Statement::VariableDeclaration(
       ast.alloc_variable_declaration(
-        SPAN,
+        span,
         VariableDeclarationKind::Const,
         ast.vec1(ast.variable_declarator(
           SPAN,
@@ -1,4 +1,5 @@
 use common::text::get_text_like_value;
+use oxc_allocator::CloneIn;
 use oxc_ast::NONE;
 use oxc_ast::ast::Argument;
 use oxc_ast::ast::Expression;
@@ -7,9 +8,11 @@ use oxc_ast::ast::VariableDeclarationKind;
 use oxc_span::SPAN;
 
 use crate::generate::CodegenContext;
+use crate::generate::block::gen_with_memo;
 use crate::generate::expression::gen_expression;
 use crate::ir::index::CreateNodesIRNode;
 use crate::ir::index::GetTextChildIRNode;
+use crate::ir::index::IRMemo;
 use crate::ir::index::SetNodesIRNode;
 use crate::ir::index::SetTextIRNode;
 use common::check::is_constant_node;
@@ -35,7 +38,7 @@ pub fn gen_set_text<'a>(oper: SetTextIRNode<'a>, context: &'a CodegenContext<'a>
       )
       .into(),
   );
-  combine_values(&mut arguments, values, context, true, true);
+  combine_values(&mut arguments, values, &[], context, true, true);
   ast.statement_expression(
     SPAN,
     ast.expression_call(
@@ -96,6 +99,7 @@ pub fn gen_set_nodes<'a>(
     values,
     generated,
     once,
+    memo,
     ..
   } = oper;
 
@@ -112,7 +116,7 @@ pub fn gen_set_nodes<'a>(
       )
       .into(),
   );
-  combine_values(&mut arguments, values, context, once, false);
+  combine_values(&mut arguments, values, &memo, context, once, false);
 
   ast.statement_expression(
     SPAN,
@@ -133,11 +137,15 @@ pub fn gen_create_nodes<'a>(
   let ast = &context.ast;
 
   let CreateNodesIRNode {
-    id, values, once, ..
+    id,
+    values,
+    once,
+    memo,
+    ..
   } = oper;
 
   let mut arguments = ast.vec();
-  combine_values(&mut arguments, values, context, once, false);
+  combine_values(&mut arguments, values, &memo, context, once, false);
 
   Statement::VariableDeclaration(ast.alloc_variable_declaration(
     SPAN,
@@ -163,6 +171,7 @@ pub fn gen_create_nodes<'a>(
 fn combine_values<'a>(
   arguments: &mut oxc_allocator::Vec<'a, Argument<'a>>,
   values: Vec<Expression<'a>>,
+  memo: &[IRMemo<'a>],
   context: &'a CodegenContext<'a>,
   once: bool,
   is_set_text: bool,
@@ -175,7 +184,21 @@ fn combine_values<'a>(
       && !matches!(value, Expression::StringLiteral(_))
       && !is_constant_node(&value);
     let literal_expression_value = get_text_like_value(&value);
-    let exp = gen_expression(value, context, None, should_wrap);
+    let mut exp = gen_expression(value, context, None, should_wrap);
+    // the nodes are only updated when the v-memo changes, like render effects
+    if should_wrap && !memo.is_empty() {
+      exp = gen_with_memo(
+        exp,
+        memo
+          .iter()
+          .map(|memo| IRMemo {
+            id: memo.id,
+            deps: memo.deps.clone_in(ast.allocator),
+          })
+          .collect(),
+        context,
+      );
+    }
     if is_set_text && literal_expression_value.is_none() {
       // dynamic, wrap with toDisplayString
       arguments.push(
@@ -501,6 +501,10 @@ fn match_patterns<'a>(
   if let Some(key_prop) = key_prop {
     let key_content = key_prop.span().source_text(context.source_text);
     for effect in render.effect.iter_mut() {
+      // a memoized row is only updated when its memo changes
+      if !effect.memo.is_empty() {
+        continue;
+      }
       let effect_ptr = effect as *mut _;
       if let Some(selector) =
         match_selector_pattern(unsafe { &*effect_ptr }, key_content, id_map, context)
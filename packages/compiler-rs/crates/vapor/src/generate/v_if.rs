@@ -1,6 +1,5 @@
-use common::patch_flag::VaporBlockShape;
 use common::patch_flag::VaporIfFlags;
-use napi::Either;
+use common::{either::Either, patch_flag::VaporBlockShape};
 use oxc_ast::NONE;
 use oxc_ast::ast::FormalParameterKind;
 use oxc_ast::ast::NumberBase;
@@ -1,9 +1,8 @@
-use common::directive::Modifiers;
-use indexmap::IndexMap;
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either4},
+use common::{
+  directive::Modifiers,
+  either::{Either, Either3, Either4},
 };
+use indexmap::IndexMap;
 use oxc_ast::ast::{Expression, Str};
 
 use crate::ir::index::{BlockIRNode, IRFor};
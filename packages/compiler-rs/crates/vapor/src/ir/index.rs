@@ -1,11 +1,17 @@
-use std::borrow::Cow;
-
-use common::directive::{DirectiveNode, Modifiers};
+use std::{borrow::Cow, collections::BTreeSet};
+
+use common::{
+  ast::source_span,
+  check::is_constant_node,
+  constant::evaluate_constant,
+  directive::{DirectiveNode, Modifiers},
+  either::{Either, Either3},
+  options::TransformOptions,
+};
 use indexmap::IndexSet;
-use napi::Either;
 
-use oxc_ast::ast::Expression;
-use oxc_span::Span;
+use oxc_ast::ast::{Expression, ObjectPropertyKind, UnaryOperator};
+use oxc_span::{GetSpan, SPAN, Span};
 
 use crate::ir::component::{IRProp, IRProps, IRSlots};
 
@@ -117,6 +123,8 @@ pub struct SetPropIRNode<'a> {
   pub element: i32,
   pub prop: IRProp<'a>,
   pub tag: &'a str,
+  // the root element of a component, which receives fallthrough attrs
+  pub root: bool,
 }
 
 #[derive(Debug)]
@@ -148,6 +156,8 @@ pub struct SetNodesIRNode<'a> {
   pub once: bool,
   pub values: Vec<Expression<'a>>,
   pub generated: bool, // whether this is a generated empty text node by `processTextLikeContainer`
+  // v-memo of the enclosing elements, outermost first
+  pub memo: Vec<IRMemo<'a>>,
 }
 
 #[derive(Debug)]
@@ -183,6 +193,8 @@ pub struct CreateNodesIRNode<'a> {
   pub id: i32,
   pub once: bool,
   pub values: Vec<Expression<'a>>,
+  // v-memo of the enclosing elements, outermost first
+  pub memo: Vec<IRMemo<'a>>,
 }
 
 #[derive(Debug)]
@@ -215,6 +227,8 @@ pub struct CreateComponentIRNode<'a> {
   pub root: bool,
   pub once: bool,
   pub is_custom_element: bool,
+  // Teleport, KeepAlive and Suspense render their slots themselves
+  pub built_in: bool,
 
   pub parent: Option<i32>,
   pub anchor: Option<i32>,
@@ -274,6 +288,79 @@ pub enum OperationNode<'a> {
   SetBlockKey(SetBlockKeyIRNode<'a>),
 }
 
+impl<'a> OperationNode<'a> {
+  // The JSX an operation was created from, used to map the generated
+  // statements back to it in source maps.
+  pub fn span(&self) -> Span {
+    let props_span = |props: &Vec<IRProps<'a>>| {
+      source_span(props.iter().flat_map(|props| match props {
+        Either3::A(props) => props.iter().map(prop_span).collect(),
+        Either3::B(prop) => vec![prop_span(prop)],
+        Either3::C(props) => vec![props.value.span()],
+      }))
+    };
+    let span = match self {
+      OperationNode::If(node) => node.condition.span(),
+      OperationNode::For(node) => node.source.span(),
+      OperationNode::SetText(node) => source_span(node.values.iter().map(GetSpan::span)),
+      OperationNode::SetProp(node) => prop_span(&node.prop),
+      OperationNode::SetDynamicProps(node) => props_span(&node.props),
+      OperationNode::SetDynamicEvents(node) => node.value.span(),
+      OperationNode::SetNodes(node) => source_span(node.values.iter().map(GetSpan::span)),
+      OperationNode::SetHtml(node) => node.value.span(),
+      OperationNode::SetEvent(node) => source_span([node.key.span(), node.value.span()]),
+      OperationNode::SetTemplateRef(node) => node.value.span(),
+      OperationNode::CreateNodes(node) => source_span(node.values.iter().map(GetSpan::span)),
+      OperationNode::Directive(node) => node.dir.span,
+      OperationNode::CreateComponent(node) => node.tag_span,
+      OperationNode::SlotOutlet(node) => source_span(
+        [node.name.span()]
+          .into_iter()
+          .chain([props_span(&node.props)]),
+      ),
+      OperationNode::Key(node) => node.value.span(),
+      OperationNode::SetBlockKey(node) => node.value.span(),
+      OperationNode::InsertNode(_) | OperationNode::GetTextChild(_) => SPAN,
+    };
+    source_span([span])
+  }
+
+  // The expressions evaluated when the operation runs in a render effect,
+  // `None` for operations that aren't put in one.
+  pub fn expressions(&self) -> Option<Vec<&Expression<'a>>> {
+    Some(match self {
+      OperationNode::SetText(node) => node.values.iter().collect(),
+      OperationNode::SetProp(node) => prop_expressions(&node.prop).collect(),
+      OperationNode::SetDynamicProps(node) => node
+        .props
+        .iter()
+        .flat_map(|props| match props {
+          Either3::A(props) => props.iter().flat_map(prop_expressions).collect(),
+          Either3::B(prop) => prop_expressions(prop).collect(),
+          Either3::C(props) => vec![&props.value],
+        })
+        .collect(),
+      OperationNode::SetDynamicEvents(node) => vec![&node.value],
+      OperationNode::SetNodes(node) => node.values.iter().collect(),
+      OperationNode::SetHtml(node) => vec![&node.value],
+      OperationNode::SetTemplateRef(node) => vec![&node.value],
+      _ => return None,
+    })
+  }
+}
+
+fn prop_expressions<'a, 'b>(prop: &'b IRProp<'a>) -> impl Iterator<Item = &'b Expression<'a>> {
+  [&prop.key].into_iter().chain(prop.values.iter())
+}
+
+fn prop_span(prop: &IRProp) -> Span {
+  source_span(
+    [prop.key.span()]
+      .into_iter()
+      .chain(prop.values.iter().map(GetSpan::span)),
+  )
+}
+
 pub enum DynamicFlag {
   None = 0,
   // This node is referenced and needs to be saved as a variable.
@@ -320,4 +407,124 @@ impl<'a> Default for IRDynamicInfo<'a> {
 #[derive(Debug, Default)]
 pub struct IREffect<'a> {
   pub operations: Vec<OperationNode<'a>>,
+  // v-memo of the enclosing elements, outermost first
+  pub memo: Vec<IRMemo<'a>>,
+}
+
+impl<'a> IREffect<'a> {
+  // Whether the effect reads no reactive state, only literals and `const`
+  // bindings of constants, so it would never run again.
+  pub fn is_static(&self, options: &TransformOptions<'a>) -> bool {
+    self.operations.iter().all(|operation| {
+      operation.expressions().is_some_and(|expressions| {
+        expressions.into_iter().all(|expression| {
+          is_constant_node(expression) || evaluate_constant(expression, options).is_some()
+        })
+      })
+    })
+  }
+
+  // The bindings and member chains the effect reads, like `props.disabled`,
+  // or `None` when it may do more than read them, e.g. call a function.
+  // They are compared by source text, which names the same binding for all
+  // effects of a block, since the reads never enter a nested scope.
+  pub fn dependencies(&self, source_text: &'a str) -> Option<BTreeSet<&'a str>> {
+    if !self.memo.is_empty() {
+      return None;
+    }
+    let mut dependencies = BTreeSet::new();
+    for operation in &self.operations {
+      // writes whose order relative to created blocks doesn't matter
+      if !matches!(
+        operation,
+        OperationNode::SetProp(_)
+          | OperationNode::SetDynamicProps(_)
+          | OperationNode::SetText(_)
+          | OperationNode::SetHtml(_)
+      ) {
+        return None;
+      }
+      for expression in operation.expressions()? {
+        collect_reads(expression, source_text, &mut dependencies)?;
+      }
+    }
+    Some(dependencies)
+  }
+}
+
+fn collect_reads<'a>(
+  expression: &Expression<'a>,
+  source_text: &'a str,
+  reads: &mut BTreeSet<&'a str>,
+) -> Option<()> {
+  let mut collect = |expression| collect_reads(expression, source_text, reads);
+  match expression {
+    Expression::Identifier(_) | Expression::StaticMemberExpression(_) => {
+      if !is_member_chain(expression) || expression.span().is_empty() {
+        return None;
+      }
+      reads.insert(expression.span().source_text(source_text));
+    }
+    Expression::TemplateLiteral(node) => {
+      for expression in &node.expressions {
+        collect(expression)?;
+      }
+    }
+    Expression::UnaryExpression(node) if node.operator != UnaryOperator::Delete => {
+      collect(&node.argument)?;
+    }
+    Expression::BinaryExpression(node) => {
+      collect(&node.left)?;
+      collect(&node.right)?;
+    }
+    Expression::LogicalExpression(node) => {
+      collect(&node.left)?;
+      collect(&node.right)?;
+    }
+    Expression::ConditionalExpression(node) => {
+      collect(&node.test)?;
+      collect(&node.consequent)?;
+      collect(&node.alternate)?;
+    }
+    Expression::ParenthesizedExpression(node) => collect(&node.expression)?,
+    Expression::ArrayExpression(node) => {
+      for element in &node.elements {
+        if let Some(element) = element.as_expression() {
+          collect(element)?;
+        } else if !element.is_elision() {
+          return None;
+        }
+      }
+    }
+    Expression::ObjectExpression(node) => {
+      for property in &node.properties {
+        let ObjectPropertyKind::ObjectProperty(property) = property else {
+          return None;
+        };
+        if property.computed {
+          collect(property.key.as_expression()?)?;
+        }
+        collect(&property.value)?;
+      }
+    }
+    _ if expression.is_literal() => {}
+    _ => return None,
+  }
+  Some(())
+}
+
+// `foo` or `foo.bar.baz`
+fn is_member_chain(expression: &Expression) -> bool {
+  match expression {
+    Expression::Identifier(_) => true,
+    Expression::StaticMemberExpression(node) => is_member_chain(&node.object),
+    _ => false,
+  }
+}
+
+#[derive(Debug)]
+pub struct IRMemo<'a> {
+  // start of the v-memo attribute, shared by all effects of the subtree
+  pub id: u32,
+  pub deps: Expression<'a>,
 }
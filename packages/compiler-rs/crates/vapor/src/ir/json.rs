@@ -0,0 +1,426 @@
+use common::{
+  directive::Modifiers,
+  either::{Either, Either3, Either4},
+  options::Template,
+};
+use oxc_ast::ast::Expression;
+use oxc_codegen::Codegen;
+use oxc_span::{GetSpan, Span};
+use serde_json::{Map, Value, json};
+
+use crate::ir::{
+  component::{IRProp, IRProps, IRSlotDynamicBasic, IRSlotDynamicConditional, IRSlotType, IRSlots},
+  index::{
+    BlockIRNode, DynamicFlag, IRDynamicInfo, IREffect, IRMemo, IfIRNode, OperationNode, RootIRNode,
+  },
+};
+
+// Bumped whenever the shape of the dumped IR changes.
+pub const IR_SCHEMA_VERSION: u32 = 1;
+
+// Serializes the Vapor IR into JSON. Expressions are dumped as their source
+// span plus the printed code, generated nodes have a `null` span.
+pub struct IRSerializer<'a> {
+  source_text: &'a str,
+}
+
+impl<'a> IRSerializer<'a> {
+  pub fn new(source_text: &'a str) -> Self {
+    IRSerializer { source_text }
+  }
+
+  pub fn root(&self, ir: &RootIRNode, block: &BlockIRNode, span: Span) -> Value {
+    json!({
+      "span": self.span(span),
+      "source": self.source(span),
+      "components": ir.components.iter().collect::<Vec<_>>(),
+      "directives": ir.directives.iter().collect::<Vec<_>>(),
+      "hasTemplateRef": ir.has_template_ref,
+      "block": self.block(block),
+    })
+  }
+
+  pub fn block(&self, block: &BlockIRNode) -> Value {
+    json!({
+      "dynamic": self.dynamic(&block.dynamic),
+      "tempId": block.temp_id,
+      "effect": block.effect.iter().map(|effect| self.effect(effect)).collect::<Vec<_>>(),
+      "operation": self.operations(&block.operation),
+      "returns": block.returns,
+      "slots": block.slots.iter().map(|slots| self.slots(slots)).collect::<Vec<_>>(),
+      "props": block.props.as_ref().map(|props| self.expression(props)),
+      "root": block.root,
+    })
+  }
+
+  pub fn dynamic(&self, dynamic: &IRDynamicInfo) -> Value {
+    json!({
+      "id": dynamic.id,
+      "flags": dynamic.flags,
+      "flagNames": dynamic_flag_names(dynamic.flags),
+      "anchor": dynamic.anchor,
+      "template": dynamic.template,
+      "hasDynamicChild": dynamic.has_dynamic_child,
+      "operation": dynamic.operation.as_ref().map(|operation| self.operation(operation)),
+      "children": dynamic.children.iter().map(|child| self.dynamic(child)).collect::<Vec<_>>(),
+    })
+  }
+
+  pub fn effect(&self, effect: &IREffect) -> Value {
+    json!({
+      "operations": self.operations(&effect.operations),
+      "memo": self.memo(&effect.memo),
+    })
+  }
+
+  fn memo(&self, memo: &[IRMemo]) -> Vec<Value> {
+    memo
+      .iter()
+      .map(|memo| self.expression(&memo.deps))
+      .collect()
+  }
+
+  fn operations(&self, operations: &[OperationNode]) -> Vec<Value> {
+    operations
+      .iter()
+      .map(|operation| self.operation(operation))
+      .collect()
+  }
+
+  pub fn operation(&self, operation: &OperationNode) -> Value {
+    let (kind, fields) = match operation {
+      OperationNode::If(node) => ("If", self.if_node(node)),
+      OperationNode::For(node) => (
+        "For",
+        json!({
+          "id": node.id,
+          "source": self.expression(&node.source),
+          "value": self.optional_expression(&node.value),
+          "key": self.optional_expression(&node.key),
+          "index": self.optional_expression(&node.index),
+          "keyProp": self.optional_expression(&node.key_prop),
+          "render": self.block(&node.render),
+          "once": node.once,
+          "component": node.component,
+          "onlyChild": node.only_child,
+          "parent": node.parent,
+          "anchor": node.anchor,
+        }),
+      ),
+      OperationNode::SetText(node) => (
+        "SetText",
+        json!({
+          "element": node.element,
+          "values": self.expressions(&node.values),
+          "generated": node.generated,
+        }),
+      ),
+      OperationNode::SetProp(node) => (
+        "SetProp",
+        json!({ "element": node.element, "tag": node.tag, "prop": self.prop(&node.prop) }),
+      ),
+      OperationNode::SetDynamicProps(node) => (
+        "SetDynamicProps",
+        json!({ "element": node.element, "tag": node.tag, "props": self.props(&node.props) }),
+      ),
+      OperationNode::SetDynamicEvents(node) => (
+        "SetDynamicEvents",
+        json!({ "element": node.element, "value": self.expression(&node.value) }),
+      ),
+      OperationNode::SetNodes(node) => (
+        "SetNodes",
+        json!({
+          "element": node.element,
+          "once": node.once,
+          "values": self.expressions(&node.values),
+          "generated": node.generated,
+          "memo": self.memo(&node.memo),
+        }),
+      ),
+      OperationNode::SetHtml(node) => (
+        "SetHtml",
+        json!({ "element": node.element, "value": self.expression(&node.value) }),
+      ),
+      OperationNode::SetEvent(node) => (
+        "SetEvent",
+        json!({
+          "element": node.element,
+          "key": self.expression(&node.key),
+          "value": self.expression(&node.value),
+          "modifiers": modifiers(&node.modifiers),
+          "delegate": node.delegate,
+          "effect": node.effect,
+        }),
+      ),
+      OperationNode::SetTemplateRef(node) => (
+        "SetTemplateRef",
+        json!({
+          "element": node.element,
+          "value": self.expression(&node.value),
+          "refFor": node.ref_for,
+        }),
+      ),
+      OperationNode::CreateNodes(node) => (
+        "CreateNodes",
+        json!({
+          "id": node.id,
+          "once": node.once,
+          "values": self.expressions(&node.values),
+          "memo": self.memo(&node.memo),
+        }),
+      ),
+      OperationNode::InsertNode(node) => (
+        "InsertNode",
+        json!({ "elements": node.elements, "parent": node.parent, "anchor": node.anchor }),
+      ),
+      OperationNode::Directive(node) => (
+        "Directive",
+        json!({
+          "element": node.element,
+          "name": node.name,
+          "span": self.span(node.dir.span),
+          "exp": self.optional_expression(&node.dir.exp),
+          "arg": self.optional_expression(&node.dir.arg),
+          "modifiers": node.dir.modifiers,
+          "builtin": node.builtin,
+          "asset": node.asset,
+          "modelType": node.model_type,
+        }),
+      ),
+      OperationNode::CreateComponent(node) => (
+        "CreateComponent",
+        json!({
+          "id": node.id,
+          "tag": node.tag,
+          "span": self.span(node.tag_span),
+          "props": self.props(&node.props),
+          "slots": node.slots.iter().map(|slots| self.slots(slots)).collect::<Vec<_>>(),
+          "asset": node.asset,
+          "root": node.root,
+          "once": node.once,
+          "isCustomElement": node.is_custom_element,
+          "parent": node.parent,
+          "anchor": node.anchor,
+        }),
+      ),
+      OperationNode::SlotOutlet(node) => (
+        "SlotOutlet",
+        json!({
+          "id": node.id,
+          "name": self.expression(&node.name),
+          "props": self.props(&node.props),
+          "fallback": node.fallback.as_ref().map(|block| self.block(block)),
+          "flags": node.flags,
+          "parent": node.parent,
+          "anchor": node.anchor,
+        }),
+      ),
+      OperationNode::GetTextChild(node) => ("GetTextChild", json!({ "parent": node.parent })),
+      OperationNode::Key(node) => (
+        "Key",
+        json!({
+          "id": node.id,
+          "value": self.expression(&node.value),
+          "block": self.block(&node.block),
+          "parent": node.parent,
+          "anchor": node.anchor,
+        }),
+      ),
+      OperationNode::SetBlockKey(node) => (
+        "SetBlockKey",
+        json!({ "element": node.element, "value": self.expression(&node.value) }),
+      ),
+    };
+    let mut object = Map::new();
+    object.insert("type".to_string(), Value::from(kind));
+    if let Value::Object(fields) = fields {
+      object.extend(fields);
+    }
+    Value::Object(object)
+  }
+
+  fn if_node(&self, node: &IfIRNode) -> Value {
+    json!({
+      "id": node.id,
+      "condition": self.expression(&node.condition),
+      "positive": self.block(&node.positive),
+      "negative": node.negative.as_ref().map(|negative| match negative.as_ref() {
+        Either::A(block) => self.block(block),
+        Either::B(node) => self.operation_if(node),
+      }),
+      "once": node.once,
+      "parent": node.parent,
+      "anchor": node.anchor,
+    })
+  }
+
+  fn operation_if(&self, node: &IfIRNode) -> Value {
+    let mut value = self.if_node(node);
+    value["type"] = Value::from("If");
+    value
+  }
+
+  fn prop(&self, prop: &IRProp) -> Value {
+    json!({
+      "key": self.expression(&prop.key),
+      "values": self.expressions(&prop.values),
+      "dynamic": prop.dynamic,
+      "modifier": prop.modifier,
+      "runtimeCamelize": prop.runtime_camelize,
+      "handler": prop.handler,
+      "handlerModifiers": prop.handler_modifiers.as_ref().map(modifiers),
+      "model": prop.model,
+      "modelModifiers": prop.model_modifiers,
+    })
+  }
+
+  fn props(&self, props: &[IRProps]) -> Vec<Value> {
+    props
+      .iter()
+      .map(|props| match props {
+        Either3::A(props) => json!({
+          "type": "Static",
+          "props": props.iter().map(|prop| self.prop(prop)).collect::<Vec<_>>(),
+        }),
+        Either3::B(prop) => json!({ "type": "Dynamic", "prop": self.prop(prop) }),
+        Either3::C(props) => json!({
+          "type": "Expression",
+          "value": self.expression(&props.value),
+          "handler": props.handler,
+        }),
+      })
+      .collect()
+  }
+
+  fn slots(&self, slots: &IRSlots) -> Value {
+    match slots {
+      Either4::A(slots) => json!({
+        "type": slot_type(&slots.slot_type),
+        "slots": slots
+          .slots
+          .iter()
+          .map(|(name, block)| (name.as_str().to_string(), self.block(block)))
+          .collect::<Map<_, _>>(),
+      }),
+      Either4::B(slot) => self.dynamic_slot(slot),
+      Either4::C(slot) => self.conditional_slot(slot),
+      Either4::D(slots) => json!({
+        "type": slot_type(&slots.slot_type),
+        "dynamic": slots.dynamic,
+        "slots": self.expression(&slots.slots),
+      }),
+    }
+  }
+
+  fn dynamic_slot(&self, slot: &IRSlotDynamicBasic) -> Value {
+    json!({
+      "type": slot_type(&slot.slot_type),
+      "name": self.expression(&slot.name),
+      "fn": self.block(&slot._fn),
+      "loop": slot._loop.as_ref().map(|_loop| json!({
+        "source": self.optional_expression(&_loop.source),
+        "value": self.optional_expression(&_loop.value),
+        "key": self.optional_expression(&_loop.key),
+        "index": self.optional_expression(&_loop.index),
+      })),
+    })
+  }
+
+  fn conditional_slot(&self, slot: &IRSlotDynamicConditional) -> Value {
+    json!({
+      "type": slot_type(&slot.slot_type),
+      "condition": self.expression(&slot.condition),
+      "positive": self.dynamic_slot(&slot.positive),
+      "negative": slot.negative.as_ref().map(|negative| match negative.as_ref() {
+        Either::A(slot) => self.dynamic_slot(slot),
+        Either::B(slot) => self.conditional_slot(slot),
+      }),
+    })
+  }
+
+  pub fn expression(&self, expression: &Expression) -> Value {
+    let mut codegen = Codegen::new();
+    codegen.print_expression(expression);
+    json!({
+      "span": self.span(expression.span()),
+      "code": codegen.into_source_text(),
+    })
+  }
+
+  fn optional_expression(&self, expression: &Option<Expression>) -> Value {
+    expression
+      .as_ref()
+      .map_or(Value::Null, |expression| self.expression(expression))
+  }
+
+  fn expressions(&self, expressions: &[Expression]) -> Vec<Value> {
+    expressions
+      .iter()
+      .map(|expression| self.expression(expression))
+      .collect()
+  }
+
+  fn span(&self, span: Span) -> Value {
+    if span.is_empty() {
+      Value::Null
+    } else {
+      json!([span.start, span.end])
+    }
+  }
+
+  fn source(&self, span: Span) -> Value {
+    if span.is_empty() {
+      Value::Null
+    } else {
+      Value::from(span.source_text(self.source_text))
+    }
+  }
+}
+
+// The whole dump: the templates shared by every root, and the IR of each
+// root in source order.
+pub fn ir_to_json(templates: &[Template], roots: Vec<Value>) -> Value {
+  json!({
+    "version": IR_SCHEMA_VERSION,
+    "templates": templates.iter().map(template_to_json).collect::<Vec<_>>(),
+    "roots": roots,
+  })
+}
+
+fn template_to_json(template: &Template) -> Value {
+  json!({
+    "content": template.content,
+    "ns": template.ns,
+    "root": template.root,
+    "static": template._static,
+  })
+}
+
+fn dynamic_flag_names(flags: i32) -> Vec<&'static str> {
+  [
+    (DynamicFlag::Referenced as i32, "Referenced"),
+    (DynamicFlag::NonTemplate as i32, "NonTemplate"),
+    (DynamicFlag::Insert as i32, "Insert"),
+  ]
+  .into_iter()
+  .filter(|(flag, _)| flags & flag != 0)
+  .map(|(_, name)| name)
+  .collect()
+}
+
+fn modifiers(modifiers: &Modifiers) -> Value {
+  json!({
+    "options": modifiers.options,
+    "keys": modifiers.keys,
+    "nonKeys": modifiers.non_keys,
+  })
+}
+
+fn slot_type(slot_type: &IRSlotType) -> &'static str {
+  match slot_type {
+    IRSlotType::STATIC => "Static",
+    IRSlotType::DYNAMIC => "Dynamic",
+    IRSlotType::CONDITIONAL => "Conditional",
+    IRSlotType::EXPRESSION => "Expression",
+  }
+}
@@ -1,3 +1,4 @@
 pub mod generate;
 pub mod ir;
+pub mod ssr;
 pub mod transform;
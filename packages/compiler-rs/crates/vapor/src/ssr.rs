@@ -0,0 +1,190 @@
+pub mod block;
+pub mod component;
+pub mod element;
+
+use std::mem;
+
+use common::text::to_valid_asset_id;
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{
+    Argument, Expression, FormalParameterKind, FormalParameters, Statement, TemplateElementValue,
+    VariableDeclarationKind,
+  },
+};
+use oxc_span::SPAN;
+
+use crate::{generate::CodegenContext, ssr::block::gen_block};
+
+// Collects static markup and `${}` interpolations into a single
+// `_push(`...`)` call, flushing it whenever a statement has to be emitted.
+pub struct SSRBuffer<'a> {
+  ast: &'a AstBuilder<'a>,
+  statements: oxc_allocator::Vec<'a, Statement<'a>>,
+  quasis: Vec<String>,
+  expressions: Vec<Expression<'a>>,
+}
+
+impl<'a> SSRBuffer<'a> {
+  pub fn new(ast: &'a AstBuilder<'a>) -> Self {
+    SSRBuffer {
+      ast,
+      statements: ast.vec(),
+      quasis: vec![String::new()],
+      expressions: vec![],
+    }
+  }
+
+  pub fn push_string(&mut self, value: &str) {
+    self.quasis.last_mut().unwrap().push_str(value);
+  }
+
+  pub fn push_interpolation(&mut self, expression: Expression<'a>) {
+    self.expressions.push(expression);
+    self.quasis.push(String::new());
+  }
+
+  // Pushes a buffer produced at runtime, e.g. `_ssrRenderComponent(...)`.
+  pub fn push_item(&mut self, item: Expression<'a>) {
+    self.flush();
+    let statement = self.gen_push(item);
+    self.statements.push(statement);
+  }
+
+  pub fn push_statement(&mut self, statement: Statement<'a>) {
+    self.flush();
+    self.statements.push(statement);
+  }
+
+  pub fn finish(mut self) -> oxc_allocator::Vec<'a, Statement<'a>> {
+    self.flush();
+    self.statements
+  }
+
+  fn flush(&mut self) {
+    if self.expressions.is_empty() && self.quasis[0].is_empty() {
+      return;
+    }
+    let ast = self.ast;
+    let quasis = mem::replace(&mut self.quasis, vec![String::new()]);
+    let tail = quasis.len() - 1;
+    let literal = ast.expression_template_literal(
+      SPAN,
+      ast.vec_from_iter(quasis.into_iter().enumerate().map(|(i, quasi)| {
+        ast.template_element(
+          SPAN,
+          TemplateElementValue {
+            raw: ast.str(&escape_template_raw(&quasi)),
+            cooked: Some(ast.str(&quasi)),
+          },
+          i == tail,
+          false,
+        )
+      })),
+      ast.vec_from_iter(mem::take(&mut self.expressions)),
+    );
+    let statement = self.gen_push(literal);
+    self.statements.push(statement);
+  }
+
+  fn gen_push(&self, item: Expression<'a>) -> Statement<'a> {
+    let ast = self.ast;
+    ast.statement_expression(
+      SPAN,
+      ast.expression_call(
+        SPAN,
+        ast.expression_identifier(SPAN, "_push"),
+        NONE,
+        ast.vec1(item.into()),
+        false,
+      ),
+    )
+  }
+}
+
+fn escape_template_raw(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('`', "\\`")
+    .replace("${", "\\${")
+}
+
+pub fn gen_call<'a>(
+  helper: &'a str,
+  arguments: Vec<Expression<'a>>,
+  context: &'a CodegenContext<'a>,
+) -> Expression<'a> {
+  let ast = context.ast;
+  ast.expression_call(
+    SPAN,
+    ast.expression_identifier(SPAN, ast.str(context.options.helper(helper))),
+    NONE,
+    ast.vec_from_iter(arguments.into_iter().map(Argument::from)),
+    false,
+  )
+}
+
+// `(params, _push, _parent)`, the signature shared by render functions and slots.
+pub fn gen_render_params<'a>(params: &[&str], ast: &'a AstBuilder<'a>) -> FormalParameters<'a> {
+  ast.formal_parameters(
+    SPAN,
+    FormalParameterKind::ArrowFormalParameters,
+    ast.vec_from_iter(
+      params
+        .iter()
+        .chain(["_push", "_parent"].iter())
+        .map(|name| {
+          ast.plain_formal_parameter(
+            SPAN,
+            ast.binding_pattern_binding_identifier(SPAN, ast.str(name)),
+          )
+        }),
+    ),
+    NONE,
+  )
+}
+
+impl<'a> CodegenContext<'a> {
+  pub fn generate_ssr(self: CodegenContext<'a>) -> Expression<'a> {
+    let ast = self.ast;
+    let context = unsafe { &*(&self as *const CodegenContext) };
+    let mut buffer = SSRBuffer::new(ast);
+
+    for name in &self.ir.components {
+      buffer.push_statement(Statement::VariableDeclaration(
+        ast.alloc_variable_declaration(
+          SPAN,
+          VariableDeclarationKind::Const,
+          ast.vec1(ast.variable_declarator(
+            SPAN,
+            VariableDeclarationKind::Const,
+            ast.binding_pattern_binding_identifier(
+              SPAN,
+              ast.str(&to_valid_asset_id(name, "component")),
+            ),
+            NONE,
+            Some(gen_call(
+              "_resolveComponent",
+              vec![ast.expression_string_literal(SPAN, ast.str(name), None)],
+              context,
+            )),
+            false,
+          )),
+          false,
+        ),
+      ));
+    }
+
+    gen_block(self.block.take(), context, &mut buffer);
+
+    ast.expression_arrow_function(
+      SPAN,
+      false,
+      false,
+      NONE,
+      gen_render_params(&[], ast),
+      NONE,
+      ast.function_body(SPAN, ast.vec(), buffer.finish()),
+    )
+  }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use common::either::Either;
+use oxc_ast::{
+  NONE,
+  ast::{Expression, FormalParameterKind, Statement},
+};
+use oxc_span::{GetSpan, SPAN};
+
+use crate::{
+  generate::{CodegenContext, expression::gen_expression},
+  ir::index::{BlockIRNode, ForIRNode, IRDynamicInfo, IREffect, IfIRNode, OperationNode},
+  ssr::{
+    SSRBuffer,
+    component::{gen_create_component, gen_slot_outlet},
+    element::{gen_nodes, gen_template},
+    gen_call,
+  },
+};
+
+// Operations of a block, keyed by the element (or node) they target.
+pub type ElementOperations<'a> = HashMap<i32, Vec<OperationNode<'a>>>;
+
+// A block is a single logical unit for hydration, so multiple (or zero)
+// roots are wrapped in fragment anchors the same way vdom renders them.
+pub fn gen_block<'a>(
+  block: BlockIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let is_fragment = block.returns.len() != 1;
+  if is_fragment {
+    buffer.push_string("<!--[-->");
+  }
+  gen_block_content(block, context, buffer);
+  if is_fragment {
+    buffer.push_string("<!--]-->");
+  }
+}
+
+// v-if branches and keyed blocks are swapped as a whole on the client, so
+// they are anchored even with a single root.
+fn gen_fragment<'a>(
+  block: BlockIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  buffer.push_string("<!--[-->");
+  gen_block_content(block, context, buffer);
+  buffer.push_string("<!--]-->");
+}
+
+pub fn gen_block_content<'a>(
+  block: BlockIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let BlockIRNode {
+    dynamic,
+    effect,
+    operation,
+    ..
+  } = block;
+  let mut operations = collect_operations(operation, effect);
+  for child in dynamic.children {
+    gen_dynamic(child, &mut operations, context, buffer);
+  }
+}
+
+fn collect_operations<'a>(
+  operation: Vec<OperationNode<'a>>,
+  effect: Vec<IREffect<'a>>,
+) -> ElementOperations<'a> {
+  let mut operations = ElementOperations::new();
+  for oper in operation
+    .into_iter()
+    .chain(effect.into_iter().flat_map(|effect| effect.operations))
+  {
+    let element = match &oper {
+      OperationNode::SetProp(oper) => oper.element,
+      OperationNode::SetDynamicProps(oper) => oper.element,
+      OperationNode::SetText(oper) => oper.element,
+      OperationNode::SetNodes(oper) => oper.element,
+      OperationNode::SetHtml(oper) => oper.element,
+      OperationNode::CreateNodes(oper) => oper.id,
+      OperationNode::Directive(oper) => oper.element,
+      // events, template refs and keys don't affect the rendered markup
+      _ => continue,
+    };
+    operations.entry(element).or_default().push(oper);
+  }
+  operations
+}
+
+pub fn gen_dynamic<'a>(
+  dynamic: IRDynamicInfo<'a>,
+  operations: &mut ElementOperations<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  if let Some(template) = dynamic.template {
+    let content = context.options.templates.borrow()[template as usize]
+      .content
+      .clone();
+    gen_template(&content, dynamic, operations, context, buffer);
+  } else if let Some(operation) = dynamic.operation {
+    gen_operation(*operation, context, buffer);
+  } else if let Some(id) = dynamic.id
+    && let Some(opers) = operations.remove(&id)
+  {
+    for oper in opers {
+      if let OperationNode::CreateNodes(oper) = oper {
+        gen_nodes(oper.values, context, buffer);
+      }
+    }
+  }
+}
+
+fn gen_operation<'a>(
+  oper: OperationNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  match oper {
+    OperationNode::If(oper) => buffer.push_statement(gen_if(oper, context)),
+    OperationNode::For(oper) => gen_for(oper, context, buffer),
+    OperationNode::Key(oper) => gen_fragment(oper.block, context, buffer),
+    OperationNode::CreateComponent(oper) => gen_create_component(oper, context, buffer),
+    OperationNode::SlotOutlet(oper) => gen_slot_outlet(oper, context, buffer),
+    // the inserted template is rendered in place by `gen_dynamic`
+    OperationNode::InsertNode(_) => {}
+    // rendered with the element they target, see `collect_operations`
+    OperationNode::SetText(_)
+    | OperationNode::SetProp(_)
+    | OperationNode::SetDynamicProps(_)
+    | OperationNode::SetDynamicEvents(_)
+    | OperationNode::SetNodes(_)
+    | OperationNode::SetHtml(_)
+    | OperationNode::SetEvent(_)
+    | OperationNode::SetTemplateRef(_)
+    | OperationNode::CreateNodes(_)
+    | OperationNode::Directive(_)
+    | OperationNode::GetTextChild(_)
+    | OperationNode::SetBlockKey(_) => {
+      unreachable!("element operations are not block children")
+    }
+  }
+}
+
+fn gen_if<'a>(oper: IfIRNode<'a>, context: &'a CodegenContext<'a>) -> Statement<'a> {
+  let ast = context.ast;
+  let IfIRNode {
+    condition,
+    positive,
+    negative,
+    ..
+  } = oper;
+
+  let mut consequent = SSRBuffer::new(ast);
+  gen_fragment(positive, context, &mut consequent);
+
+  let alternate = match negative.map(|negative| *negative) {
+    Some(Either::B(if_node)) => gen_if(if_node, context),
+    // a missing branch still renders anchors to hydrate against
+    negative => {
+      let mut alternate = SSRBuffer::new(ast);
+      gen_fragment(
+        match negative {
+          Some(Either::A(block)) => block,
+          _ => BlockIRNode::default(),
+        },
+        context,
+        &mut alternate,
+      );
+      ast.statement_block(SPAN, alternate.finish())
+    }
+  };
+
+  ast.statement_if(
+    SPAN,
+    gen_expression(condition, context, None, false),
+    ast.statement_block(SPAN, consequent.finish()),
+    Some(alternate),
+  )
+}
+
+fn gen_for<'a>(oper: ForIRNode<'a>, context: &'a CodegenContext<'a>, buffer: &mut SSRBuffer<'a>) {
+  let ast = context.ast;
+  let ForIRNode {
+    source,
+    mut value,
+    key,
+    index,
+    render,
+    ..
+  } = oper;
+
+  let source = gen_expression(source, context, None, false);
+
+  let (depth, exit_scope) = context.enter_scope();
+  let item_var = format!("_for_item{depth}");
+  let mut id_map = context.parse_value_destructure(
+    value.as_mut(),
+    ast.expression_identifier(SPAN, ast.str(&item_var)),
+  );
+
+  let mut params = vec![item_var];
+  let raw_key = if let Some(Expression::Identifier(key)) = &key {
+    Some(key.span.source_text(context.source_text))
+  } else {
+    None
+  };
+  let raw_index = index
+    .as_ref()
+    .map(|index| index.span().source_text(context.source_text));
+  if raw_key.is_some() || raw_index.is_some() {
+    let key_var = format!("_for_key{depth}");
+    if let Some(raw_key) = raw_key {
+      id_map.insert(raw_key, ast.expression_identifier(SPAN, ast.str(&key_var)));
+    }
+    params.push(key_var);
+  }
+  if let Some(raw_index) = raw_index {
+    let index_var = format!("_for_index{depth}");
+    id_map.insert(
+      raw_index,
+      ast.expression_identifier(SPAN, ast.str(&index_var)),
+    );
+    params.push(index_var);
+  }
+
+  let render_fn = context.with_id(
+    || {
+      let mut body = SSRBuffer::new(ast);
+      gen_block(render, context, &mut body);
+      ast.expression_arrow_function(
+        SPAN,
+        false,
+        false,
+        NONE,
+        ast.formal_parameters(
+          SPAN,
+          FormalParameterKind::ArrowFormalParameters,
+          ast.vec_from_iter(params.iter().map(|param| {
+            ast.plain_formal_parameter(
+              SPAN,
+              ast.binding_pattern_binding_identifier(SPAN, ast.str(param)),
+            )
+          })),
+          NONE,
+        ),
+        NONE,
+        ast.function_body(SPAN, ast.vec(), body.finish()),
+      )
+    },
+    id_map,
+  );
+  exit_scope();
+
+  buffer.push_string("<!--[-->");
+  buffer.push_statement(ast.statement_expression(
+    SPAN,
+    gen_call("_ssrRenderList", vec![source, render_fn], context),
+  ));
+  buffer.push_string("<!--]-->");
+}
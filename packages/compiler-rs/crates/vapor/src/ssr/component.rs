@@ -0,0 +1,456 @@
+use std::borrow::Cow;
+
+use common::{
+  check::is_simple_identifier,
+  either::{Either, Either3, Either4},
+  text::to_valid_asset_id,
+};
+use oxc_ast::{
+  NONE,
+  ast::{
+    ArrayExpressionElement, Expression, FormalParameterKind, NumberBase, ObjectPropertyKind,
+    PropertyKey, PropertyKind, Str,
+  },
+};
+use oxc_span::{GetSpan, SPAN};
+
+use crate::{
+  generate::{
+    CodegenContext,
+    expression::gen_expression,
+    prop::{gen_prop_key, gen_prop_value},
+  },
+  ir::{
+    component::{IRProp, IRProps, IRSlotDynamicBasic, IRSlotDynamicConditional, IRSlots},
+    index::{BlockIRNode, CreateComponentIRNode, IRFor, SlotOutletIRNode},
+  },
+  ssr::{SSRBuffer, block::gen_block_content, gen_call, gen_render_params},
+};
+
+pub fn gen_create_component<'a>(
+  oper: CreateComponentIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let ast = context.ast;
+  let CreateComponentIRNode {
+    tag,
+    tag_span,
+    props,
+    slots,
+    asset,
+    is_custom_element,
+    ..
+  } = oper;
+
+  if is_custom_element {
+    return gen_custom_element(tag, props, slots, context, buffer);
+  }
+
+  let tag = if asset {
+    ast.expression_identifier(SPAN, ast.str(&to_valid_asset_id(tag, "component")))
+  } else {
+    gen_expression(
+      ast.expression_identifier(tag_span, ast.str(tag)),
+      context,
+      None,
+      false,
+    )
+  };
+  let props = gen_props(props, context).unwrap_or_else(|| ast.expression_null_literal(SPAN));
+  let slots = gen_slots(slots, context).unwrap_or_else(|| ast.expression_null_literal(SPAN));
+  buffer.push_item(gen_call(
+    "_ssrRenderComponent",
+    vec![
+      tag,
+      props,
+      slots,
+      ast.expression_identifier(SPAN, "_parent"),
+    ],
+    context,
+  ));
+}
+
+// Custom elements are plain markup on the server, with the default slot
+// rendered as their children.
+fn gen_custom_element<'a>(
+  tag: &'a str,
+  props: Vec<IRProps<'a>>,
+  slots: Vec<IRSlots<'a>>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  buffer.push_string(&format!("<{tag}"));
+  if let Some(props) = gen_props(props, context) {
+    buffer.push_interpolation(gen_call("_ssrRenderAttrs", vec![props], context));
+  }
+  buffer.push_string(">");
+  for slot in slots {
+    if let Either4::A(mut slot) = slot
+      && let Some(block) = slot.slots.shift_remove("default")
+    {
+      gen_block_content(block, context, buffer);
+    }
+  }
+  buffer.push_string(&format!("</{tag}>"));
+}
+
+pub fn gen_props<'a>(
+  props: Vec<IRProps<'a>>,
+  context: &'a CodegenContext<'a>,
+) -> Option<Expression<'a>> {
+  let mut args = vec![];
+  for props in props {
+    match props {
+      Either3::A(props) => {
+        if !props.is_empty() {
+          args.push(gen_object_props(props, context));
+        }
+      }
+      Either3::B(prop) => args.push(gen_object_props(vec![prop], context)),
+      // handler spreads (`v-on={obj}`) are client-only
+      Either3::C(props) => {
+        if !props.handler {
+          args.push(gen_expression(props.value, context, None, false));
+        }
+      }
+    }
+  }
+  if args.len() > 1 {
+    Some(gen_call("_mergeProps", args, context))
+  } else {
+    args.pop()
+  }
+}
+
+fn gen_object_props<'a>(props: Vec<IRProp<'a>>, context: &'a CodegenContext<'a>) -> Expression<'a> {
+  let ast = context.ast;
+  ast.expression_object(
+    SPAN,
+    ast.vec_from_iter(props.into_iter().map(|prop| {
+      let key = gen_prop_key(
+        prop.key,
+        prop.runtime_camelize,
+        prop.modifier,
+        prop.handler,
+        prop
+          .handler_modifiers
+          .map(|modifiers| modifiers.options)
+          .unwrap_or_default(),
+        context,
+      );
+      let computed = !matches!(key, PropertyKey::StaticIdentifier(_));
+      ast.object_property_kind_object_property(
+        SPAN,
+        PropertyKind::Init,
+        key,
+        gen_prop_value(prop.values, context),
+        false,
+        false,
+        computed,
+      )
+    })),
+  )
+}
+
+fn gen_slots<'a>(
+  mut slots: Vec<IRSlots<'a>>,
+  context: &'a CodegenContext<'a>,
+) -> Option<Expression<'a>> {
+  let ast = context.ast;
+  if slots.is_empty() {
+    return None;
+  }
+  if slots.len() == 1
+    && let Either4::D(_) = &slots[0]
+    && let Either4::D(slot) = slots.remove(0)
+  {
+    return Some(gen_call(
+      "_ssrNormalizeSlots",
+      vec![gen_expression(slot.slots, context, None, false)],
+      context,
+    ));
+  }
+
+  let mut properties = ast.vec();
+  let mut dynamic_slots = ast.vec();
+  for slot in slots {
+    match slot {
+      Either4::A(slot) => {
+        for (name, block) in slot.slots {
+          properties.push(ast.object_property_kind_object_property(
+            SPAN,
+            PropertyKind::Init,
+            gen_slot_key(name, context),
+            gen_slot_fn(block, context),
+            false,
+            false,
+            false,
+          ));
+        }
+      }
+      Either4::B(slot) => dynamic_slots.push(ArrayExpressionElement::from(gen_dynamic_slot(
+        slot, context,
+      ))),
+      Either4::C(slot) => dynamic_slots.push(ArrayExpressionElement::from(gen_conditional_slot(
+        slot, context,
+      ))),
+      Either4::D(slot) => properties.push(ObjectPropertyKind::SpreadProperty(
+        ast.alloc_spread_element(
+          SPAN,
+          gen_call(
+            "_ssrNormalizeSlots",
+            vec![gen_expression(slot.slots, context, None, false)],
+            context,
+          ),
+        ),
+      )),
+    }
+  }
+
+  // `_: 1` marks compiled slots as stable, `_: 2` as dynamic
+  properties.insert(
+    0,
+    ast.object_property_kind_object_property(
+      SPAN,
+      PropertyKind::Init,
+      ast.property_key_static_identifier(SPAN, "_"),
+      ast.expression_numeric_literal(
+        SPAN,
+        if dynamic_slots.is_empty() { 1.0 } else { 2.0 },
+        None,
+        NumberBase::Decimal,
+      ),
+      false,
+      false,
+      false,
+    ),
+  );
+  let slots = ast.expression_object(SPAN, properties);
+  Some(if dynamic_slots.is_empty() {
+    slots
+  } else {
+    gen_call(
+      "_createSlots",
+      vec![slots, ast.expression_array(SPAN, dynamic_slots)],
+      context,
+    )
+  })
+}
+
+fn gen_slot_key<'a>(name: Str<'a>, context: &'a CodegenContext<'a>) -> PropertyKey<'a> {
+  let ast = context.ast;
+  if is_simple_identifier(&name) {
+    ast.property_key_static_identifier(SPAN, name)
+  } else {
+    ast.property_key_static_identifier(SPAN, ast.str(&format!("\"{name}\"")))
+  }
+}
+
+// `(props, _push, _parent) => {...}`, following the compiled slot signature
+// of vdom SSR so `_ssrRenderSlot` can push the content directly.
+fn gen_slot_fn<'a>(mut block: BlockIRNode<'a>, context: &'a CodegenContext<'a>) -> Expression<'a> {
+  let ast = context.ast;
+  let mut props_name = Cow::Borrowed("_");
+  let mut props_ast = None;
+  let mut exit_scope = None;
+
+  if let Some(props) = block.props.as_mut() {
+    match props.without_parentheses().get_inner_expression() {
+      Expression::ObjectExpression(_) => {
+        props_ast = Some(props);
+        let scope = context.enter_scope();
+        props_name = Cow::Owned(format!("_slotProps{}", scope.0));
+        exit_scope = Some(scope.1);
+      }
+      Expression::Identifier(props) => {
+        props_name = Cow::Borrowed(props.name.as_str());
+      }
+      _ => {}
+    }
+  }
+
+  let id_map = context.parse_value_destructure(
+    props_ast,
+    ast.expression_identifier(SPAN, ast.str(&props_name)),
+  );
+  let slot_fn = context.with_id(
+    || {
+      let mut body = SSRBuffer::new(ast);
+      gen_block_content(block, context, &mut body);
+      ast.expression_arrow_function(
+        SPAN,
+        false,
+        false,
+        NONE,
+        gen_render_params(&[&props_name], ast),
+        NONE,
+        ast.function_body(SPAN, ast.vec(), body.finish()),
+      )
+    },
+    id_map,
+  );
+  if let Some(exit_scope) = exit_scope {
+    exit_scope();
+  }
+  slot_fn
+}
+
+fn gen_dynamic_slot<'a>(
+  slot: IRSlotDynamicBasic<'a>,
+  context: &'a CodegenContext<'a>,
+) -> Expression<'a> {
+  let ast = context.ast;
+  let IRSlotDynamicBasic {
+    name, _fn, _loop, ..
+  } = slot;
+  let slot_expr = ast.expression_object(
+    SPAN,
+    ast.vec_from_array([
+      ast.object_property_kind_object_property(
+        SPAN,
+        PropertyKind::Init,
+        ast.property_key_static_identifier(SPAN, "name"),
+        gen_expression(name, context, None, false),
+        false,
+        false,
+        false,
+      ),
+      ast.object_property_kind_object_property(
+        SPAN,
+        PropertyKind::Init,
+        ast.property_key_static_identifier(SPAN, "fn"),
+        gen_slot_fn(_fn, context),
+        false,
+        false,
+        false,
+      ),
+    ]),
+  );
+  let Some(IRFor {
+    source,
+    value,
+    key,
+    index,
+  }) = _loop
+  else {
+    return slot_expr;
+  };
+
+  let params = [value, key, index].into_iter().flatten().map(|param| {
+    ast.plain_formal_parameter(
+      SPAN,
+      ast.binding_pattern_binding_identifier(
+        SPAN,
+        ast.str(param.span().source_text(context.source_text)),
+      ),
+    )
+  });
+  gen_call(
+    "_renderList",
+    vec![
+      gen_expression(source.unwrap(), context, None, false),
+      ast.expression_arrow_function(
+        SPAN,
+        true,
+        false,
+        NONE,
+        ast.formal_parameters(
+          SPAN,
+          FormalParameterKind::ArrowFormalParameters,
+          ast.vec_from_iter(params),
+          NONE,
+        ),
+        NONE,
+        ast.function_body(
+          SPAN,
+          ast.vec(),
+          ast.vec1(ast.statement_expression(SPAN, slot_expr)),
+        ),
+      ),
+    ],
+    context,
+  )
+}
+
+fn gen_conditional_slot<'a>(
+  slot: IRSlotDynamicConditional<'a>,
+  context: &'a CodegenContext<'a>,
+) -> Expression<'a> {
+  let ast = context.ast;
+  let IRSlotDynamicConditional {
+    condition,
+    positive,
+    negative,
+    ..
+  } = slot;
+  ast.expression_conditional(
+    SPAN,
+    gen_expression(condition, context, None, false),
+    gen_dynamic_slot(positive, context),
+    match negative.map(|negative| *negative) {
+      Some(Either::A(negative)) => gen_dynamic_slot(negative, context),
+      Some(Either::B(negative)) => gen_conditional_slot(negative, context),
+      None => ast.expression_identifier(SPAN, "undefined"),
+    },
+  )
+}
+
+pub fn gen_slot_outlet<'a>(
+  oper: SlotOutletIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let ast = context.ast;
+  let SlotOutletIRNode {
+    name,
+    props,
+    fallback,
+    ..
+  } = oper;
+
+  let fallback = match fallback {
+    Some(fallback) => {
+      let mut body = SSRBuffer::new(ast);
+      gen_block_content(fallback, context, &mut body);
+      ast.expression_arrow_function(
+        SPAN,
+        false,
+        false,
+        NONE,
+        ast.formal_parameters(
+          SPAN,
+          FormalParameterKind::ArrowFormalParameters,
+          ast.vec(),
+          NONE,
+        ),
+        NONE,
+        ast.function_body(SPAN, ast.vec(), body.finish()),
+      )
+    }
+    None => ast.expression_null_literal(SPAN),
+  };
+
+  buffer.push_statement(ast.statement_expression(
+    SPAN,
+    gen_call(
+      "_ssrRenderSlot",
+      vec![
+        ast
+          .member_expression_static(
+            SPAN,
+            ast.expression_identifier(SPAN, "_parent"),
+            ast.identifier_name(SPAN, "slots"),
+            false,
+          )
+          .into(),
+        gen_expression(name, context, None, false),
+        gen_props(props, context).unwrap_or_else(|| ast.expression_object(SPAN, ast.vec())),
+        fallback,
+        ast.expression_identifier(SPAN, "_push"),
+        ast.expression_identifier(SPAN, "_parent"),
+      ],
+      context,
+    ),
+  ));
+}
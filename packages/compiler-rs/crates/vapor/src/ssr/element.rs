@@ -0,0 +1,589 @@
+use std::borrow::Cow;
+
+use common::{
+  check::is_void_tag,
+  text::{escape_html, get_text_like_value},
+};
+use oxc_allocator::CloneIn;
+use oxc_ast::{
+  NONE,
+  ast::{Expression, LogicalOperator, PropertyKind},
+};
+use oxc_span::SPAN;
+
+use crate::{
+  generate::{CodegenContext, expression::gen_expression, prop::gen_prop_value},
+  ir::{
+    component::IRProp,
+    index::{DynamicFlag, IRDynamicInfo, OperationNode, SetPropIRNode},
+  },
+  ssr::{
+    SSRBuffer,
+    block::{ElementOperations, gen_dynamic},
+    component::gen_props,
+    gen_call,
+  },
+};
+
+// Templates are re-parsed into a tree so dynamic children can be matched
+// against the DOM children they are resolved from on the client.
+pub enum TemplateNode<'t> {
+  Element {
+    tag: &'t str,
+    // `<tag attrs` without the closing `>`
+    open: &'t str,
+    children: Vec<TemplateNode<'t>>,
+  },
+  Text(&'t str),
+  Placeholder,
+}
+
+pub fn parse_template(content: &str) -> Vec<TemplateNode<'_>> {
+  let bytes = content.as_bytes();
+  let mut roots = vec![];
+  let mut stack: Vec<(&str, &str, Vec<TemplateNode>)> = vec![];
+  let mut i = 0;
+  while i < content.len() {
+    let rest = &content[i..];
+    let node = if rest.starts_with("<!>") {
+      i += 3;
+      Some(TemplateNode::Placeholder)
+    } else if rest.starts_with("<!--") {
+      let end = rest.find("-->").map_or(content.len(), |end| i + end + 3);
+      let comment = &content[i..end];
+      i = end;
+      Some(TemplateNode::Text(comment))
+    } else if let Some(rest) = rest.strip_prefix("</") {
+      let end = rest.find('>').unwrap_or(rest.len());
+      let tag = &rest[..end];
+      i += end + 3;
+      // also closes descendants whose end tags were omitted
+      if let Some(position) = stack.iter().rposition(|(name, ..)| *name == tag) {
+        while stack.len() > position {
+          close_element(&mut stack, &mut roots);
+        }
+      }
+      None
+    } else if bytes[i] == b'<' && bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+      let end = find_tag_end(content, i);
+      let open = &content[i..end];
+      let tag = &open[1..open
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(open.len())];
+      i = end + 1;
+      if is_void_tag(tag) {
+        Some(TemplateNode::Element {
+          tag,
+          open,
+          children: vec![],
+        })
+      } else {
+        stack.push((tag, open, vec![]));
+        None
+      }
+    } else {
+      let end = content[i + 1..]
+        .find('<')
+        .map_or(content.len(), |end| i + 1 + end);
+      let text = &content[i..end];
+      i = end;
+      Some(TemplateNode::Text(text))
+    };
+    if let Some(node) = node {
+      match stack.last_mut() {
+        Some((_, _, children)) => children.push(node),
+        None => roots.push(node),
+      }
+    }
+  }
+  while !stack.is_empty() {
+    close_element(&mut stack, &mut roots);
+  }
+  roots
+}
+
+fn close_element<'t>(
+  stack: &mut Vec<(&'t str, &'t str, Vec<TemplateNode<'t>>)>,
+  roots: &mut Vec<TemplateNode<'t>>,
+) {
+  let (tag, open, children) = stack.pop().unwrap();
+  let node = TemplateNode::Element {
+    tag,
+    open,
+    children,
+  };
+  match stack.last_mut() {
+    Some((_, _, children)) => children.push(node),
+    None => roots.push(node),
+  }
+}
+
+// Index of the `>` ending the open tag at `start`, skipping quoted values.
+fn find_tag_end(content: &str, start: usize) -> usize {
+  let mut in_quote = false;
+  for (i, c) in content[start..].char_indices() {
+    match c {
+      '"' => in_quote = !in_quote,
+      '>' if !in_quote => return start + i,
+      _ => {}
+    }
+  }
+  content.len()
+}
+
+fn find_static_attr(open: &str, name: &str) -> Option<String> {
+  take_static_attr(&mut open.to_string(), name)
+}
+
+// Removes a static attribute from the open tag, returning its value.
+fn take_static_attr(open: &mut String, name: &str) -> Option<String> {
+  let start = open.find(&format!(" {name}="))?;
+  let value_start = start + name.len() + 2;
+  let rest = &open[value_start..];
+  let (value, len) = if let Some(value) = rest.strip_prefix('"') {
+    let end = value.find('"').unwrap_or(value.len());
+    (
+      value[..end].replace("&quot;", "\""),
+      (end + 2).min(rest.len()),
+    )
+  } else {
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    (rest[..end].to_string(), end)
+  };
+  open.replace_range(start..value_start + len, "");
+  Some(value)
+}
+
+pub fn gen_template<'a>(
+  content: &str,
+  dynamic: IRDynamicInfo<'a>,
+  operations: &mut ElementOperations<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  if !content.starts_with('<') {
+    // text templates are created with `createNodes` and kept as written
+    let text = escape_html(Cow::Borrowed(content));
+    gen_node(
+      TemplateNode::Text(&text),
+      Some(dynamic),
+      operations,
+      context,
+      buffer,
+    );
+    return;
+  }
+  let mut nodes = parse_template(content).into_iter();
+  if let Some(node) = nodes.next() {
+    gen_node(node, Some(dynamic), operations, context, buffer);
+  }
+  for node in nodes {
+    gen_node(node, None, operations, context, buffer);
+  }
+}
+
+fn gen_node<'a>(
+  node: TemplateNode,
+  dynamic: Option<IRDynamicInfo<'a>>,
+  operations: &mut ElementOperations<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  match node {
+    TemplateNode::Element {
+      tag,
+      open,
+      children,
+    } => gen_element(tag, open, children, dynamic, operations, context, buffer),
+    TemplateNode::Text(text) => {
+      let mut rendered = false;
+      if let Some(id) = dynamic.and_then(|dynamic| dynamic.id)
+        && let Some(opers) = operations.remove(&id)
+      {
+        for oper in opers {
+          if let OperationNode::SetNodes(oper) = oper {
+            gen_nodes(oper.values, context, buffer);
+            rendered = true;
+          }
+        }
+      }
+      if !rendered {
+        buffer.push_string(text);
+      }
+    }
+    TemplateNode::Placeholder => match dynamic {
+      Some(dynamic) => gen_dynamic(dynamic, operations, context, buffer),
+      None => buffer.push_string("<!---->"),
+    },
+  }
+}
+
+enum ElementContent<'a> {
+  Html(Expression<'a>),
+  Text(Vec<Expression<'a>>),
+  Nodes(Vec<Expression<'a>>),
+}
+
+fn gen_element<'a>(
+  tag: &str,
+  open: &str,
+  children: Vec<TemplateNode>,
+  dynamic: Option<IRDynamicInfo<'a>>,
+  operations: &mut ElementOperations<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let (id, dynamic_children) = match dynamic {
+    Some(dynamic) => (dynamic.id, dynamic.children),
+    None => (None, vec![]),
+  };
+  let opers = id.and_then(|id| operations.remove(&id)).unwrap_or_default();
+
+  let content = gen_element_props(tag, open, opers, context, buffer);
+  buffer.push_string(">");
+  match content {
+    Some(content) => gen_element_content(content, context, buffer),
+    None => gen_children(children, dynamic_children, operations, context, buffer),
+  }
+  if !is_void_tag(tag) {
+    buffer.push_string(&format!("</{tag}>"));
+  }
+}
+
+// Mirrors `gen_children` in generate/template.rs: non-template children shift
+// the element index, and trailing inserts are appended after the template.
+fn gen_children<'a>(
+  nodes: Vec<TemplateNode>,
+  dynamic_children: Vec<IRDynamicInfo<'a>>,
+  operations: &mut ElementOperations<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let mut nodes = nodes.into_iter();
+  let mut cursor = 0;
+  let mut offset = 0;
+  for (index, child) in dynamic_children.into_iter().enumerate() {
+    if child.flags & DynamicFlag::NonTemplate as i32 != 0 {
+      offset -= 1;
+      if child.flags & DynamicFlag::Insert as i32 != 0 {
+        for node in nodes.by_ref() {
+          gen_node(node, None, operations, context, buffer);
+        }
+        gen_dynamic(child, operations, context, buffer);
+      }
+      continue;
+    }
+    let element_index = index as i32 + offset;
+    while cursor < element_index {
+      if let Some(node) = nodes.next() {
+        gen_node(node, None, operations, context, buffer);
+      }
+      cursor += 1;
+    }
+    if let Some(node) = nodes.next() {
+      gen_node(node, Some(child), operations, context, buffer);
+      cursor += 1;
+    }
+  }
+  for node in nodes {
+    gen_node(node, None, operations, context, buffer);
+  }
+}
+
+fn gen_element_props<'a>(
+  tag: &str,
+  open: &str,
+  opers: Vec<OperationNode<'a>>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) -> Option<ElementContent<'a>> {
+  let ast = context.ast;
+  let mut content = None;
+  let mut class = None;
+  let mut style = None;
+  let mut show = None;
+
+  // static values are merged into their dynamic counterparts, an attribute
+  // can only be rendered once
+  let mut open = open.to_string();
+  let has_prop = |name: &str| {
+    opers.iter().any(|oper| {
+      matches!(oper, OperationNode::SetProp(SetPropIRNode {
+        prop: IRProp {
+          key: Expression::StringLiteral(key),
+          ..
+        },
+        ..
+      }) if key.value == name)
+    })
+  };
+  let has_show = opers.iter().any(
+    |oper| matches!(oper, OperationNode::Directive(oper) if oper.builtin && oper.name == "show"),
+  );
+  let static_class = if has_prop("class") {
+    take_static_attr(&mut open, "class")
+  } else {
+    None
+  };
+  let static_style = if has_prop("style") || has_show {
+    take_static_attr(&mut open, "style")
+  } else {
+    None
+  };
+  let static_value = |value: Option<String>| {
+    value.map(|value| ast.expression_string_literal(SPAN, ast.str(&value), None))
+  };
+  let static_class = static_value(static_class);
+  let static_style = static_value(static_style);
+  buffer.push_string(&open);
+
+  // `value` is needed up front to render checkbox and radio models
+  let mut value = opers
+    .iter()
+    .find_map(|oper| match oper {
+      OperationNode::SetProp(SetPropIRNode {
+        prop:
+          IRProp {
+            key: Expression::StringLiteral(key),
+            values,
+            ..
+          },
+        ..
+      }) if key.value == "value" => Some(gen_prop_value(
+        values
+          .iter()
+          .map(|value| value.clone_in(ast.allocator))
+          .collect(),
+        context,
+      )),
+      _ => None,
+    })
+    .or_else(|| {
+      find_static_attr(&open, "value")
+        .map(|value| ast.expression_string_literal(SPAN, ast.str(&value), None))
+    });
+
+  for oper in opers {
+    match oper {
+      OperationNode::SetProp(SetPropIRNode {
+        prop: IRProp { key, values, .. },
+        ..
+      }) => {
+        let name = if let Expression::StringLiteral(key) = &key {
+          key.value.to_string()
+        } else {
+          String::new()
+        };
+        match name.as_str() {
+          "class" => class = Some(gen_prop_value(values, context)),
+          "style" => style = Some(gen_prop_value(values, context)),
+          "innerHTML" => content = Some(ElementContent::Html(gen_prop_value(values, context))),
+          "textContent" => content = Some(ElementContent::Text(values)),
+          "value" if tag == "textarea" => content = Some(ElementContent::Text(values)),
+          _ => buffer.push_interpolation(gen_call(
+            "_ssrRenderDynamicAttr",
+            vec![
+              gen_expression(key, context, None, false),
+              gen_prop_value(values, context),
+            ],
+            context,
+          )),
+        }
+      }
+      OperationNode::SetDynamicProps(oper) => {
+        if let Some(props) = gen_props(oper.props, context) {
+          buffer.push_interpolation(gen_call("_ssrRenderAttrs", vec![props], context));
+        }
+      }
+      OperationNode::SetHtml(oper) => {
+        content = Some(ElementContent::Html(gen_expression(
+          oper.value, context, None, false,
+        )))
+      }
+      OperationNode::SetText(oper) => content = Some(ElementContent::Text(oper.values)),
+      OperationNode::SetNodes(oper) => content = Some(ElementContent::Nodes(oper.values)),
+      OperationNode::Directive(oper) if oper.builtin => match oper.name.as_ref() {
+        "show" => show = oper.dir.exp,
+        "model" => {
+          if let Some(exp) = oper.dir.exp {
+            gen_model(
+              tag,
+              oper.model_type,
+              exp,
+              value.take(),
+              &mut content,
+              context,
+              buffer,
+            );
+          }
+        }
+        _ => {}
+      },
+      _ => {}
+    }
+  }
+
+  let hidden = show.map(|show| {
+    ast.expression_conditional(
+      SPAN,
+      gen_expression(show, context, None, false),
+      ast.expression_null_literal(SPAN),
+      ast.expression_object(
+        SPAN,
+        ast.vec1(ast.object_property_kind_object_property(
+          SPAN,
+          PropertyKind::Init,
+          ast.property_key_static_identifier(SPAN, "display"),
+          ast.expression_string_literal(SPAN, "none", None),
+          false,
+          false,
+          false,
+        )),
+      ),
+    )
+  });
+  if let Some(class) = merge_values([static_class, class], context) {
+    buffer.push_string(" class=\"");
+    buffer.push_interpolation(gen_call("_ssrRenderClass", vec![class], context));
+    buffer.push_string("\"");
+  }
+  if let Some(style) = merge_values([static_style, style, hidden], context) {
+    buffer.push_string(" style=\"");
+    buffer.push_interpolation(gen_call("_ssrRenderStyle", vec![style], context));
+    buffer.push_string("\"");
+  }
+
+  content
+}
+
+// A single value as is, several ones as an array for the normalizers.
+fn merge_values<'a, const N: usize>(
+  values: [Option<Expression<'a>>; N],
+  context: &'a CodegenContext<'a>,
+) -> Option<Expression<'a>> {
+  let ast = context.ast;
+  let mut values = values.into_iter().flatten().collect::<Vec<_>>();
+  match values.len() {
+    0 => None,
+    1 => values.pop(),
+    _ => Some(ast.expression_array(SPAN, ast.vec_from_iter(values.into_iter().map(Into::into)))),
+  }
+}
+
+fn gen_model<'a>(
+  tag: &str,
+  model_type: Option<&str>,
+  exp: Expression<'a>,
+  value: Option<Expression<'a>>,
+  content: &mut Option<ElementContent<'a>>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let ast = context.ast;
+  if tag == "textarea" {
+    *content = Some(ElementContent::Text(vec![exp]));
+    return;
+  }
+  let model = gen_expression(exp, context, None, false);
+  let value = value.unwrap_or_else(|| ast.expression_null_literal(SPAN));
+  let checked = match model_type.unwrap_or_default() {
+    "text" => {
+      buffer.push_interpolation(gen_call(
+        "_ssrRenderAttr",
+        vec![ast.expression_string_literal(SPAN, "value", None), model],
+        context,
+      ));
+      return;
+    }
+    "checkbox" => gen_call(
+      "_ssrIncludeBooleanAttr",
+      vec![
+        ast.expression_conditional(
+          SPAN,
+          ast.expression_call(
+            SPAN,
+            ast
+              .member_expression_static(
+                SPAN,
+                ast.expression_identifier(SPAN, "Array"),
+                ast.identifier_name(SPAN, "isArray"),
+                false,
+              )
+              .into(),
+            NONE,
+            ast.vec1(model.clone_in(ast.allocator).into()),
+            false,
+          ),
+          gen_call(
+            "_ssrLooseContain",
+            vec![model.clone_in(ast.allocator), value],
+            context,
+          ),
+          model,
+        ),
+      ],
+      context,
+    ),
+    "radio" => gen_call("_ssrLooseEqual", vec![model, value], context),
+    // select options and dynamic input types are resolved on the client
+    _ => return,
+  };
+  buffer.push_interpolation(ast.expression_conditional(
+    SPAN,
+    checked,
+    ast.expression_string_literal(SPAN, " checked", None),
+    ast.expression_string_literal(SPAN, "", None),
+  ));
+}
+
+fn gen_element_content<'a>(
+  content: ElementContent<'a>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let ast = context.ast;
+  match content {
+    ElementContent::Html(value) => buffer.push_interpolation(ast.expression_logical(
+      SPAN,
+      value,
+      LogicalOperator::Coalesce,
+      ast.expression_string_literal(SPAN, "", None),
+    )),
+    ElementContent::Text(values) => {
+      for value in values {
+        if let Some(text) = get_text_like_value(&value) {
+          buffer.push_string(&escape_html(text));
+        } else {
+          buffer.push_interpolation(gen_call(
+            "_ssrInterpolate",
+            vec![gen_expression(value, context, None, false)],
+            context,
+          ));
+        }
+      }
+    }
+    ElementContent::Nodes(values) => gen_nodes(values, context, buffer),
+  }
+}
+
+pub fn gen_nodes<'a>(
+  values: Vec<Expression<'a>>,
+  context: &'a CodegenContext<'a>,
+  buffer: &mut SSRBuffer<'a>,
+) {
+  let ast = context.ast;
+  for value in values {
+    if let Some(text) = get_text_like_value(&value) {
+      buffer.push_string(&escape_html(text));
+    } else {
+      buffer.push_item(gen_call(
+        "_ssrRenderNodes",
+        vec![
+          gen_expression(value, context, None, false),
+          ast.expression_identifier(SPAN, "_parent"),
+        ],
+        context,
+      ));
+    }
+  }
+}
@@ -1,14 +1,15 @@
-use common::ast::RootNode;
+use common::ast::{RootNode, source_span};
 use common::directive::{Directives, Modifiers};
 use common::expression::get_constant_expression_text;
 use common::options::Template;
 pub use common::options::TransformOptions;
-use oxc_allocator::{Allocator, TakeIn};
-use oxc_ast::ast::{Expression, JSXAttributeItem, JSXChild, JSXElement};
+use oxc_allocator::{Allocator, CloneIn, TakeIn};
+use oxc_ast::ast::{Expression, JSXAttributeItem, JSXAttributeValue, JSXChild, JSXElement};
 use oxc_ast::{AstBuilder, NONE};
-use oxc_span::{GetSpan, SPAN};
+use oxc_span::{GetSpan, SPAN, Span};
 use std::borrow::Cow;
 use std::{cell::RefCell, collections::HashSet, mem, rc::Rc};
+pub mod transform_built_in;
 pub mod transform_children;
 pub mod transform_element;
 pub mod transform_key;
@@ -30,7 +31,10 @@ pub mod v_text;
 use crate::generate::CodegenContext;
 use crate::transform::transform_key::transform_key;
 use crate::{
-  ir::index::{BlockIRNode, DynamicFlag, IRDynamicInfo, IREffect, OperationNode, RootIRNode},
+  ir::{
+    index::{BlockIRNode, DynamicFlag, IRDynamicInfo, IREffect, IRMemo, OperationNode, RootIRNode},
+    json::IRSerializer,
+  },
   transform::{
     transform_children::transform_children, transform_element::transform_element,
     transform_template_ref::transform_template_ref, transform_text::transform_text,
@@ -39,7 +43,7 @@ use crate::{
   },
 };
 
-use common::check::{is_constant_node, is_math_ml_tag, is_native_tag, is_svg_tag, is_template};
+use common::check::{is_constant_node, is_math_ml_tag, is_svg_tag, is_template};
 
 pub struct DirectiveTransformResult<'a> {
   pub key: Expression<'a>,
@@ -86,6 +90,9 @@ pub struct TransformContext<'a> {
 
   pub in_v_once: RefCell<bool>,
   pub in_v_for: RefCell<i32>,
+  // v-memo of the enclosing elements, their effects only re-run when the
+  // dependencies change
+  pub in_v_memo: RefCell<Vec<IRMemo<'a>>>,
 
   pub seen: Rc<RefCell<HashSet<u32>>>,
 
@@ -111,6 +118,7 @@ pub struct TransformContext<'a> {
 
   pub ir: Rc<RefCell<RootIRNode<'a>>>,
   pub node: RefCell<JSXChild<'a>>,
+  pub root_span: Span,
 
   pub parent_dynamic: RefCell<IRDynamicInfo<'a>>,
 }
@@ -131,6 +139,7 @@ impl<'a> TransformContext<'a> {
       children_template: RefCell::new(Vec::new()),
       in_v_once: RefCell::new(*options.in_v_once.borrow()),
       in_v_for: RefCell::new(*options.in_v_for.borrow()),
+      in_v_memo: RefCell::new(vec![]),
       seen: Rc::new(RefCell::new(HashSet::new())),
       effect_index: RefCell::new(0),
       operation_index: RefCell::new(0),
@@ -140,6 +149,7 @@ impl<'a> TransformContext<'a> {
       template_close_tags: RefCell::new(HashSet::new()),
       global_id: RefCell::new(0),
       if_index: RefCell::new(0),
+      root_span: node.span(),
       node: RefCell::new(RootNode::from(ast, options, node, true, None)),
       parent_dynamic: RefCell::new(IRDynamicInfo::new()),
       ir: Rc::new(RefCell::new(RootIRNode::default())),
@@ -156,7 +166,19 @@ impl<'a> TransformContext<'a> {
     *self.block.borrow_mut() = block;
     *self.ir.borrow_mut() = ir;
     self.transform_node(None, None);
-    CodegenContext::new(self).generate()
+    if self.options.dump_ir {
+      let root = IRSerializer::new(self.source_text).root(
+        &self.ir.borrow(),
+        &self.block.borrow(),
+        self.root_span,
+      );
+      self.options.ir.borrow_mut().push(root);
+    }
+    if self.options.ssr {
+      CodegenContext::new(self).generate_ssr()
+    } else {
+      CodegenContext::new(self).generate()
+    }
   }
 
   pub fn increase_id(&self) -> i32 {
@@ -195,6 +217,20 @@ impl<'a> TransformContext<'a> {
     expressions.iter().all(|exp| is_constant_node(exp))
   }
 
+  // the v-memo the effects and dynamic children of the current node are
+  // guarded by
+  pub fn current_memo(&self) -> Vec<IRMemo<'a>> {
+    self
+      .in_v_memo
+      .borrow()
+      .iter()
+      .map(|memo| IRMemo {
+        id: memo.id,
+        deps: memo.deps.clone_in(self.allocator),
+      })
+      .collect()
+  }
+
   pub fn register_effect(
     &self,
     context_block: &mut BlockIRNode<'a>,
@@ -218,6 +254,7 @@ impl<'a> TransformContext<'a> {
       index,
       IREffect {
         operations: vec![operation],
+        memo: self.current_memo(),
       },
     );
     if has_get_effect_index {
@@ -279,10 +316,16 @@ impl<'a> TransformContext<'a> {
       return false;
     }
 
-    is_native_tag(tag)
+    self.options.is_native_tag(tag)
   }
 
-  pub fn push_template(&self, content: String, tag: Option<&str>, _static: bool) -> i32 {
+  pub fn push_template(
+    &self,
+    content: String,
+    tag: Option<&str>,
+    _static: bool,
+    span: Span,
+  ) -> i32 {
     let len = self.options.templates.borrow().len();
     let root = *self.template_root.borrow();
     // root_template_index.map(|i| i.eq(&len)).unwrap_or(false);
@@ -308,6 +351,7 @@ impl<'a> TransformContext<'a> {
       root,
       ns,
       _static,
+      span,
     });
     len as i32
   }
@@ -317,12 +361,13 @@ impl<'a> TransformContext<'a> {
     block: &mut BlockIRNode<'a>,
     tag: Option<&str>,
     _static: bool,
+    span: Span,
   ) -> i32 {
     let template = self.template.borrow();
     if template.is_empty() {
       return -1;
     }
-    let id = self.push_template(template.clone(), tag, _static);
+    let id = self.push_template(template.clone(), tag, _static, span);
     block.dynamic.template = Some(id);
     id
   }
@@ -332,6 +377,8 @@ impl<'a> TransformContext<'a> {
     context_block: &'a mut BlockIRNode<'a>,
     ir: BlockIRNode<'a>,
     is_v_for: bool,
+    // where the template of the block is mapped to
+    span: Span,
   ) -> Box<dyn FnOnce() -> BlockIRNode<'a> + 'a> {
     let block = mem::take(&mut *context_block);
     let template = mem::take(&mut *self.template.borrow_mut());
@@ -349,7 +396,7 @@ impl<'a> TransformContext<'a> {
 
     (Box::new(move || {
       // exit
-      self.register_template(context_block, None, false);
+      self.register_template(context_block, None, false, span);
       let return_block = mem::take(context_block);
       *context_block = block;
       *self.template.borrow_mut() = template;
@@ -427,6 +474,17 @@ impl<'a> TransformContext<'a> {
     }
   }
 
+  // The span generated code for the current node is mapped to. The root and
+  // wrapping fragments have none of their own, so their children's is used.
+  pub fn node_span(&self) -> Span {
+    match &*self.node.borrow() {
+      JSXChild::Fragment(node) if source_span([node.span]) == SPAN => {
+        source_span(node.children.iter().map(GetSpan::span))
+      }
+      node => source_span([node.span()]),
+    }
+  }
+
   pub fn create_block(
     &'a self,
     context_node: &mut JSXChild<'a>,
@@ -435,9 +493,10 @@ impl<'a> TransformContext<'a> {
     is_v_for: bool,
   ) -> Box<dyn FnOnce() -> BlockIRNode<'a> + 'a> {
     let block = BlockIRNode::new();
+    let span = node.span();
     *context_node = self.wrap_fragment(node);
     let _context_block = context_block as *mut BlockIRNode;
-    let exit_block = self.enter_block(unsafe { &mut *_context_block }, block, is_v_for);
+    let exit_block = self.enter_block(unsafe { &mut *_context_block }, block, is_v_for, span);
     self.reference(&mut context_block.dynamic);
     exit_block
   }
@@ -454,6 +513,7 @@ impl<'a> TransformContext<'a> {
     self.node.replace(node);
     let index = self.index.replace(index);
     let in_v_once = *self.in_v_once.borrow();
+    let in_v_memo = self.in_v_memo.borrow().len();
     let template = self.template.replace(String::new());
     let is_last_effective_child = self
       .is_last_effective_child
@@ -469,6 +529,7 @@ impl<'a> TransformContext<'a> {
     move || {
       self.index.replace(index);
       self.in_v_once.replace(in_v_once);
+      self.in_v_memo.borrow_mut().truncate(in_v_memo);
       self.template.replace(template);
       self
         .is_last_effective_child
@@ -499,6 +560,7 @@ impl<'a> TransformContext<'a> {
 
       let mut directives = Directives::default();
       let is_root = RootNode::is_root(&self.node.borrow());
+      let span = self.node_span();
       if !is_root {
         let context = self as *const TransformContext;
         let node = &mut *self.node.borrow_mut() as *mut _;
@@ -512,6 +574,21 @@ impl<'a> TransformContext<'a> {
           if directives.v_once.is_some() {
             *(&*context).in_v_once.borrow_mut() = true;
           };
+          if let Some(dir) = &directives.v_memo
+            && !self.options.ssr
+            && let Some(JSXAttributeValue::ExpressionContainer(value)) = &dir.value
+            && let Some(deps) = value.expression.as_expression()
+          {
+            // elements with v-if or v-for are transformed again inside their
+            // own block
+            let in_v_memo = &mut (&*context).in_v_memo.borrow_mut();
+            if !in_v_memo.iter().any(|memo| memo.id == dir.span.start) {
+              in_v_memo.push(IRMemo {
+                id: dir.span.start,
+                deps: deps.clone_in(self.allocator),
+              });
+            }
+          };
 
           if (directives.v_if.is_some()
             || directives.v_else_if.is_some()
@@ -603,7 +680,7 @@ impl<'a> TransformContext<'a> {
       }
 
       if is_root {
-        self.register_template(context_block, None, false);
+        self.register_template(context_block, None, false, span);
       }
     }
   }
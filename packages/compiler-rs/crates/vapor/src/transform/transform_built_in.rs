@@ -0,0 +1,192 @@
+use common::{
+  check::is_template,
+  directive::find_prop,
+  error::{ErrorCodes, WarningCodes},
+  text::is_empty_text,
+};
+use oxc_ast::{
+  AstKind,
+  ast::{
+    JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement, JSXElementName,
+    JSXExpression, ObjectPropertyKind,
+  },
+};
+use oxc_span::{GetSpan, Span};
+
+use crate::transform::TransformContext;
+
+pub fn is_teleport_tag(tag: &str) -> bool {
+  matches!(tag, "Teleport" | "VaporTeleport")
+}
+
+pub fn is_keep_alive_tag(tag: &str) -> bool {
+  matches!(tag, "KeepAlive" | "VaporKeepAlive")
+}
+
+pub fn is_suspense_tag(tag: &str) -> bool {
+  tag == "Suspense"
+}
+
+// A tag is a built-in when it isn't declared or is imported from vue, so a
+// user component with the same name is left alone.
+pub fn is_built_in_component(node: &JSXElement, context: &TransformContext) -> bool {
+  let JSXElementName::IdentifierReference(name) = &node.opening_element.name else {
+    return false;
+  };
+  let Some(reference_id) = name.reference_id.get() else {
+    return true;
+  };
+  let semantic = context.options.semantic.borrow();
+  let scoping = semantic.scoping();
+  let Some(symbol_id) = scoping.get_reference(reference_id).symbol_id() else {
+    return true;
+  };
+  let declaration = scoping.symbol_declaration(symbol_id);
+  matches!(
+    semantic.nodes().kind(declaration),
+    AstKind::ImportSpecifier(_)
+  ) && semantic
+    .nodes()
+    .ancestor_kinds(declaration)
+    .any(|kind| matches!(kind, AstKind::ImportDeclaration(node) if node.source.value == "vue"))
+}
+
+// `Teleport` and `KeepAlive` from vue only work with the Virtual DOM, so they
+// are created with their Vapor counterparts.
+pub fn resolve_built_in_component<'a>(tag: &'a str, context: &TransformContext<'a>) -> &'a str {
+  if context.options.ssr {
+    return tag;
+  }
+  match tag {
+    "Teleport" => context.options.helper("_VaporTeleport"),
+    "KeepAlive" => context.options.helper("_VaporKeepAlive"),
+    _ => tag,
+  }
+}
+
+pub fn transform_teleport<'a>(node: &JSXElement<'a>, context: &TransformContext<'a>) {
+  let Some(to) = find_prop(node, vec!["to"]) else {
+    // the target may be passed with a spread
+    if !node
+      .opening_element
+      .attributes
+      .iter()
+      .any(|attr| matches!(attr, JSXAttributeItem::SpreadAttribute(_)))
+    {
+      context
+        .options
+        .error(ErrorCodes::TeleportMissingTarget, node.opening_element.span);
+    }
+    return;
+  };
+  let is_invalid = match &to.value {
+    None => true,
+    Some(JSXAttributeValue::StringLiteral(value)) => value.value.trim().is_empty(),
+    Some(JSXAttributeValue::ExpressionContainer(value)) => match &value.expression {
+      JSXExpression::StringLiteral(value) => value.value.trim().is_empty(),
+      JSXExpression::NumericLiteral(_)
+      | JSXExpression::BooleanLiteral(_)
+      | JSXExpression::NullLiteral(_) => true,
+      JSXExpression::Identifier(value) => value.name == "undefined",
+      _ => false,
+    },
+    Some(_) => false,
+  };
+  if is_invalid {
+    context
+      .options
+      .error(ErrorCodes::TeleportInvalidTarget, to.span);
+  }
+
+  // any string disables the teleport, including "false"
+  if let Some(disabled) = find_prop(node, vec!["disabled"])
+    && let Some(JSXAttributeValue::StringLiteral(value)) = &disabled.value
+    && value.value == "false"
+  {
+    context
+      .options
+      .warn(WarningCodes::TeleportDisabledString, disabled.span);
+  }
+}
+
+pub fn transform_keep_alive<'a>(node: &JSXElement<'a>, context: &TransformContext<'a>) {
+  let roots = get_root_children(&node.children);
+  if roots.is_empty() {
+    context
+      .options
+      .error(ErrorCodes::KeepAliveInvalidChildren, node.span);
+  } else if roots.len() > 1 {
+    context.options.error(
+      ErrorCodes::KeepAliveInvalidChildren,
+      Span::new(roots[0].span().start, roots[roots.len() - 1].span().end),
+    );
+  }
+}
+
+pub fn transform_suspense<'a>(node: &JSXElement<'a>, context: &TransformContext<'a>) {
+  if let Some(slot) = find_prop(node, vec!["v-slot"]) {
+    check_suspense_slot_name(&slot.name, slot.span, context);
+  }
+  if let Some(slots) = find_prop(node, vec!["v-slots"])
+    && let Some(JSXAttributeValue::ExpressionContainer(value)) = &slots.value
+    && let JSXExpression::ObjectExpression(value) = &value.expression
+  {
+    for prop in value.properties.iter() {
+      if let ObjectPropertyKind::ObjectProperty(prop) = prop
+        && let Some(name) = prop.key.static_name()
+        && !matches!(name.as_ref(), "default" | "fallback")
+      {
+        context
+          .options
+          .error(ErrorCodes::SuspenseInvalidSlot, prop.key.span());
+      }
+    }
+  }
+
+  let mut default_roots = vec![];
+  for child in get_root_children(&node.children) {
+    if let JSXChild::Element(child) = child
+      && is_template(child)
+      && let Some(slot) = find_prop(child, vec!["v-slot"])
+    {
+      check_suspense_slot_name(&slot.name, slot.span, context);
+      check_suspense_slot_roots(&get_root_children(&child.children), context);
+    } else {
+      default_roots.push(child);
+    }
+  }
+  check_suspense_slot_roots(&default_roots, context);
+}
+
+fn check_suspense_slot_name(name: &JSXAttributeName, span: Span, context: &TransformContext) {
+  if let JSXAttributeName::NamespacedName(name) = name
+    && !matches!(name.name.name.as_str(), "default" | "fallback")
+  {
+    context.options.error(ErrorCodes::SuspenseInvalidSlot, span);
+  }
+}
+
+fn check_suspense_slot_roots(roots: &[&JSXChild], context: &TransformContext) {
+  if roots.len() > 1 {
+    context.options.error(
+      ErrorCodes::SuspenseInvalidChildren,
+      Span::new(roots[0].span().start, roots[roots.len() - 1].span().end),
+    );
+  }
+}
+
+// v-else-if/v-else branches replace their v-if, so a whole chain counts as a
+// single root.
+fn get_root_children<'b, 'a>(children: &'b [JSXChild<'a>]) -> Vec<&'b JSXChild<'a>> {
+  children
+    .iter()
+    .filter(|child| match child {
+      JSXChild::Element(child) => find_prop(child, vec!["v-else-if", "v-else"]).is_none(),
+      JSXChild::ExpressionContainer(child) => !matches!(
+        child.expression,
+        JSXExpression::EmptyExpression(_) | JSXExpression::NullLiteral(_)
+      ),
+      child => !is_empty_text(child),
+    })
+    .collect()
+}
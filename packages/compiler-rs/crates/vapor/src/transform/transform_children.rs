@@ -2,6 +2,7 @@ use std::{borrow::Cow, mem};
 
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::ast::{JSXChild, JSXExpression};
+use oxc_span::GetSpan;
 
 use crate::{
   ir::index::{
@@ -15,8 +16,8 @@ use crate::{
 };
 
 use common::{
-  ast::RootNode,
-  check::is_fragment_node,
+  ast::{RootNode, source_span},
+  check::{is_fragment_node, is_jsx_component},
   directive::Directives,
   text::{get_tag_name, is_empty_text},
 };
@@ -74,6 +75,7 @@ pub unsafe fn transform_children<'a>(
     let mut tag = "";
     let mut next_is_interpolation = false;
     let is_text_child = matches!(&child, JSXChild::Text(_));
+    let child_span = child.span();
     let exit_context = context.create(
       if is_text_child
         && let Some(next) = unsafe { &mut *children_ptr }.get_mut(i + 1)
@@ -88,6 +90,11 @@ pub unsafe fn transform_children<'a>(
       } else {
         if let JSXChild::Element(child) = child {
           tag = get_tag_name(child, context.options);
+          if !is_fragment_or_component && !is_jsx_component(child) {
+            context
+              .options
+              .check_html_nesting(parent_tag_name, tag, child.opening_element.span);
+          }
         }
         child.take_in(context.allocator)
       },
@@ -122,6 +129,7 @@ pub unsafe fn transform_children<'a>(
           context_block,
           Some(tag),
           is_text_child || context.can_use_static_template(context_block, tag),
+          source_span([child_span]),
         );
         context.reference(&mut context_block.dynamic);
         if flags & DynamicFlag::NonTemplate as i32 == 0 || flags & DynamicFlag::Insert as i32 != 0 {
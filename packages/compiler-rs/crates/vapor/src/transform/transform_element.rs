@@ -1,6 +1,5 @@
 use std::{borrow::Cow, cell::RefCell, collections::HashSet, mem, rc::Rc};
 
-use napi::{Either, bindgen_prelude::Either3};
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{
   Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild,
@@ -17,10 +16,20 @@ use crate::{
     },
   },
   transform::{
-    DirectiveTransformResult, TransformContext, transform_key::resolve_static_key,
-    transform_slot_outlet::transform_slot_outlet, transform_transition::transform_transition,
-    v_bind::transform_v_bind, v_html::transform_v_html, v_model::transform_v_model,
-    v_on::transform_v_on, v_show::transform_v_show, v_text::transform_v_text,
+    DirectiveTransformResult, TransformContext,
+    transform_built_in::{
+      is_built_in_component, is_keep_alive_tag, is_suspense_tag, is_teleport_tag,
+      resolve_built_in_component, transform_keep_alive, transform_suspense, transform_teleport,
+    },
+    transform_key::resolve_static_key,
+    transform_slot_outlet::transform_slot_outlet,
+    transform_transition::transform_transition,
+    v_bind::transform_v_bind,
+    v_html::transform_v_html,
+    v_model::transform_v_model,
+    v_on::transform_v_on,
+    v_show::transform_v_show,
+    v_text::transform_v_text,
   },
 };
 
@@ -30,11 +39,12 @@ use common::{
     get_directive_name, is_always_close_tag, is_block_tag, is_built_in_directive,
     is_formatting_tag, is_inline_tag, is_template, is_void_tag,
   },
-  directive::{Directives, resolve_directive, resolve_prop_name},
+  directive::{Directives, apply_directive_transform, resolve_directive, resolve_prop_name},
   dom::is_valid_html_nesting,
+  either::{Either, Either3},
   error::ErrorCodes,
   expression::jsx_attribute_value_to_expression,
-  text::{camelize, get_tag_name},
+  text::{camelize, get_tag_name, stringify_attribute},
 };
 
 /// # SAFETY
@@ -72,6 +82,8 @@ pub unsafe fn transform_element<'a>(
 
   let tag = directives.tag_name;
   let tag_span = node.opening_element.name.span();
+  let is_built_in = (is_teleport_tag(tag) || is_keep_alive_tag(tag) || is_suspense_tag(tag))
+    && is_built_in_component(node, context);
   if tag == "slot" {
     return unsafe {
       transform_slot_outlet(
@@ -85,7 +97,21 @@ pub unsafe fn transform_element<'a>(
     };
   } else if matches!(tag, "VaporTransition" | "VaporTransitionGroup") {
     transform_transition(node, context);
+  } else if is_built_in {
+    if is_teleport_tag(tag) {
+      transform_teleport(node, context);
+    } else if is_keep_alive_tag(tag) {
+      transform_keep_alive(node, context);
+    } else {
+      transform_suspense(node, context);
+    }
   }
+  let is_teleport = is_built_in && is_teleport_tag(tag);
+  let tag = if is_built_in {
+    resolve_built_in_component(tag, context)
+  } else {
+    tag
+  };
   // treat custom elements as components because the template helper cannot
   // resolve them properly; they require creation via createElement
   let is_custom_element = directives.is_custom_element;
@@ -101,6 +127,7 @@ pub unsafe fn transform_element<'a>(
     parent_slots = Some(context_block.slots.drain(..).collect::<Vec<_>>());
   }
 
+  let span = node.span;
   let context_block_ptr = context_block as *mut BlockIRNode;
   let props_result = build_props(
     directives,
@@ -113,7 +140,9 @@ pub unsafe fn transform_element<'a>(
     Rc::clone(&get_operation_index),
   );
 
-  let single_root = RootNode::is_single_root(parent_node);
+  // teleported content isn't rendered in place, so it can't receive the
+  // fallthrough attrs of the parent component
+  let single_root = RootNode::is_single_root(parent_node) && !is_teleport;
 
   Some(Box::new(move || {
     if is_component {
@@ -124,6 +153,7 @@ pub unsafe fn transform_element<'a>(
         static_key,
         single_root,
         is_custom_element,
+        is_built_in,
         context,
         context_block,
         Rc::clone(&get_operation_index),
@@ -134,6 +164,7 @@ pub unsafe fn transform_element<'a>(
         props_result,
         static_key,
         single_root,
+        span,
         context,
         context_block,
         parent_node,
@@ -157,6 +188,7 @@ pub fn transform_native_element<'a>(
   props_result: PropsResult<'a>,
   static_key: Option<Expression<'a>>,
   single_root: bool,
+  span: Span,
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
   parent_node: &'a mut JSXChild<'a>,
@@ -190,23 +222,7 @@ pub fn transform_native_element<'a>(
           && let Some(Expression::StringLiteral(first_value)) = values.first()
           && !DYNAMIC_KEYS.contains(&key.value.as_str())
         {
-          template += " ";
-          let value = first_value.value;
-          template += &key.value;
-
-          if !value.is_empty() {
-            // The attribute value can remain unquoted if it doesn't contain ASCII whitespace
-            // or any of " ' ` = < or >.
-            // https://html.spec.whatwg.org/multipage/introduction.html#intro-early-example
-            let needs_quotes = value.contains(|c: char| {
-              c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '=' | '<' | '>')
-            });
-            template += &if needs_quotes {
-              format!(r#"="{}""#, value.replace("\"", "&quot;"))
-            } else {
-              format!("={}", value)
-            };
-          }
+          template += &stringify_attribute(&key.value, &first_value.value);
         } else {
           let element = context.reference(&mut context_block.dynamic);
           context.register_effect(
@@ -217,6 +233,7 @@ pub fn transform_native_element<'a>(
               prop,
               element,
               tag,
+              root: single_root,
             }),
             Some(Rc::clone(&get_effect_index)),
             Some(Rc::clone(&get_operation_index)),
@@ -239,7 +256,7 @@ pub fn transform_native_element<'a>(
   {
     let dynamic = &mut context_block.dynamic;
     context.reference(dynamic);
-    dynamic.template = Some(context.push_template(template, Some(tag), false));
+    dynamic.template = Some(context.push_template(template, Some(tag), false, span));
     dynamic.flags = dynamic.flags | DynamicFlag::NonTemplate as i32 | DynamicFlag::Insert as i32;
   } else {
     *context.template.borrow_mut() = format!("{}{}", context.template.borrow(), template);
@@ -336,6 +353,7 @@ pub fn transform_component_element<'a>(
   static_key: Option<Expression<'a>>,
   single_root: bool,
   is_custom_element: bool,
+  built_in: bool,
   context: &'a TransformContext<'a>,
   context_block: &mut BlockIRNode<'a>,
   get_operation_index: Rc<RefCell<Box<dyn FnMut() -> i32 + 'a>>>,
@@ -358,6 +376,7 @@ pub fn transform_component_element<'a>(
       slots: mem::take(&mut context_block.slots),
       once: *context.in_v_once.borrow(),
       is_custom_element,
+      built_in,
       parent: None,
       anchor: None,
       append_index: None,
@@ -460,7 +479,7 @@ pub fn build_props<'a>(
               );
             }
           } else {
-            context.options.on_error.as_ref()(ErrorCodes::VOnNoExpression, span);
+            context.options.error(ErrorCodes::VOnNoExpression, span);
           }
           continue;
         } else if collect_name && prop_name == "name" {
@@ -468,15 +487,39 @@ pub fn build_props<'a>(
           continue;
         }
 
+        // custom directives with a registered transform expand into attributes,
+        // built-in ones can't be overridden
+        let dir_name = get_directive_name(
+          resolve_prop_name(match &prop.name {
+            JSXAttributeName::Identifier(name) => name.name.as_str(),
+            JSXAttributeName::NamespacedName(name) => name.namespace.name.as_str(),
+          })[0],
+        );
+        let attributes = match (!is_built_in_directive(dir_name))
+          .then(|| apply_directive_transform(prop, dir_name, directives, context.options))
+          .flatten()
+        {
+          Some((mut attributes, need_runtime)) => {
+            if need_runtime {
+              attributes.push(prop.as_mut());
+            }
+            attributes
+          }
+          None => vec![prop.as_mut()],
+        };
+
         let context_block = context_block as *mut BlockIRNode;
-        if let Some(prop) = transform_prop(
-          directives,
-          prop,
-          unsafe { &mut *node },
-          context,
-          unsafe { &mut *context_block },
-          Rc::clone(&get_operation_index),
-        ) {
+        for prop in attributes {
+          let Some(prop) = transform_prop(
+            directives,
+            prop,
+            unsafe { &mut *node },
+            context,
+            unsafe { &mut *context_block },
+            Rc::clone(&get_operation_index),
+          ) else {
+            continue;
+          };
           if is_component && !matches!(&prop.key, Expression::StringLiteral(_)) {
             // v-model:$name$="value"
             if !results.is_empty() {
@@ -1,6 +1,5 @@
 use std::{cell::RefCell, rc::Rc};
 
-use napi::{Either, bindgen_prelude::Either3};
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{JSXChild, JSXElement};
 use oxc_span::SPAN;
@@ -10,8 +9,12 @@ use crate::{
   transform::{TransformContext, transform_element::build_props},
 };
 use common::{
-  directive::Directives, error::ErrorCodes, expression::jsx_attribute_value_to_expression,
-  patch_flag::VaporSlotFlags, text::is_empty_text,
+  directive::Directives,
+  either::{Either, Either3},
+  error::ErrorCodes,
+  expression::jsx_attribute_value_to_expression,
+  patch_flag::VaporSlotFlags,
+  text::is_empty_text,
 };
 
 /// # SAFETY
@@ -74,7 +77,7 @@ pub unsafe fn transform_slot_outlet<'a>(
           }
         })
     {
-      context.options.on_error.as_ref()(
+      context.options.error(
         ErrorCodes::VSlotUnexpectedDirectiveOnSlotOutlet,
         runtime_directive.dir.span,
       );
@@ -123,6 +126,7 @@ fn create_fallback<'a>(
   }
 
   let ast = context.ast;
+  let span = node.span;
   *unsafe { &mut *context_node } = ast.jsx_child_fragment(
     SPAN,
     ast.jsx_opening_fragment(SPAN),
@@ -131,5 +135,5 @@ fn create_fallback<'a>(
   );
 
   let fallback = BlockIRNode::new();
-  Some(context.enter_block(context_block, fallback, false))
+  Some(context.enter_block(context_block, fallback, false, span))
 }
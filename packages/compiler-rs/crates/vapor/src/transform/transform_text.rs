@@ -1,6 +1,5 @@
 use std::{borrow::Cow, collections::HashSet};
 
-use napi::Either;
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{ConditionalExpression, Expression, JSXChild};
 use oxc_span::GetSpan;
@@ -8,7 +7,7 @@ use oxc_span::GetSpan;
 use crate::{
   ir::index::{
     BlockIRNode, CreateNodesIRNode, DynamicFlag, GetTextChildIRNode, IfIRNode, OperationNode,
-    SetNodesIRNode,
+    SetNodesIRNode, SetTextIRNode,
   },
   transform::{
     TransformContext,
@@ -20,10 +19,12 @@ use common::{
   ast::RootNode,
   check::{is_constant_node, is_custom_element, is_fragment_node, is_jsx_component, is_template},
   directive::Directives,
+  either::Either,
   patch_flag::VaporBlockShape,
   text::{
     escape_html, get_tag_name, get_text_like_value, is_empty_text, is_text_like, resolve_jsx_text,
   },
+  text_type::is_text_type_expression,
 };
 
 /// # SAFETY
@@ -99,7 +100,7 @@ pub unsafe fn transform_text<'a>(
       let is_root_text = RootNode::is_root(parent_node)
         || if let JSXChild::Element(parent_node) = parent_node {
           is_jsx_component(parent_node)
-            || is_custom_element(parent_node)
+            || is_custom_element(parent_node, context.options)
             || get_tag_name(parent_node, context.options) == "template"
         } else {
           false
@@ -189,7 +190,7 @@ fn process_interpolation<'a>(
   } else {
     is_fragment_node(parent_node)
       || if let JSXChild::Element(parent_node) = parent_node {
-        is_jsx_component(parent_node) || is_custom_element(parent_node)
+        is_jsx_component(parent_node) || is_custom_element(parent_node, context.options)
       } else {
         false
       }
@@ -201,12 +202,27 @@ fn process_interpolation<'a>(
         id,
         once,
         values,
+        memo: context.current_memo(),
       }),
       None,
     );
   } else {
     let mut template = context.template.borrow_mut();
     *template = format!("{} ", template);
+    if is_text_only(&values, context) {
+      context.register_effect(
+        context_block,
+        context.is_operation(values.iter().collect()),
+        OperationNode::SetText(SetTextIRNode {
+          values,
+          element: id,
+          generated: false,
+        }),
+        None,
+        None,
+      );
+      return;
+    }
     context.register_operation(
       context_block,
       OperationNode::SetNodes(SetNodesIRNode {
@@ -215,6 +231,7 @@ fn process_interpolation<'a>(
         once,
         values,
         generated: false,
+        memo: context.current_memo(),
       }),
       None,
     );
@@ -294,6 +311,20 @@ fn process_text_container<'a>(
       None,
     );
     let element = context.reference(&mut context_block.dynamic);
+    if is_text_only(&values, context) {
+      context.register_effect(
+        context_block,
+        context.is_operation(values.iter().collect()),
+        OperationNode::SetText(SetTextIRNode {
+          values,
+          element,
+          generated: true,
+        }),
+        None,
+        None,
+      );
+      return;
+    }
     context.register_operation(
       context_block,
       OperationNode::SetNodes(SetNodesIRNode {
@@ -303,12 +334,21 @@ fn process_text_container<'a>(
         values,
         // indicates this node is generated, so prefix should be "x" instead of "n"
         generated: true,
+        memo: context.current_memo(),
       }),
       None,
     );
   }
 }
 
+// Values that can only be text are set with the cheaper `setText`.
+fn is_text_only<'a>(values: &[Expression<'a>], context: &TransformContext<'a>) -> bool {
+  context.options.type_aware_text
+    && values
+      .iter()
+      .all(|value| is_text_type_expression(value, context.options))
+}
+
 fn process_text_like_expressions<'a>(
   nodes: &mut Vec<&mut JSXChild<'a>>,
   context: &'a TransformContext<'a>,
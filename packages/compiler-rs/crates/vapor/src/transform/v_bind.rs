@@ -1,5 +1,6 @@
 use common::{
   check::{is_boolean_attr, is_reserved_prop},
+  constant::fold_constant,
   directive::{Directives, resolve_prop_name},
   expression::jsx_attribute_value_to_expression,
   text::{camelize, get_text_like_value},
@@ -35,6 +36,8 @@ pub fn transform_v_bind<'a>(
     if let Some(value) = match value {
       JSXAttributeValue::ExpressionContainer(value) => {
         let expression = value.expression.as_expression_mut()?;
+        // `const cls = 'card'` makes `class={cls}` a static attribute
+        fold_constant(expression, context.options, ast);
         if directives.is_component && expression.is_number_literal() {
           if let Expression::NumericLiteral(_) = expression {
             Some(expression.take_in(ast.allocator))
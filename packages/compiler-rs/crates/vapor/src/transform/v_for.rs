@@ -49,7 +49,9 @@ pub unsafe fn transform_v_for<'a>(
   } = get_for_parse_result(dir, context)?;
 
   let Some(source) = source else {
-    context.options.on_error.as_ref()(ErrorCodes::VForMalformedExpression, span);
+    context
+      .options
+      .error(ErrorCodes::VForMalformedExpression, span);
     return None;
   };
 
@@ -62,7 +64,7 @@ pub unsafe fn transform_v_for<'a>(
     None
   };
 
-  let is_component = directives.is_component || is_template_with_single_component(node);
+  let is_component = directives.is_component || is_template_with_single_component(node, context);
   let dynamic = &mut context_block.dynamic;
   let id = context.reference(dynamic);
   dynamic.flags = dynamic.flags | DynamicFlag::NonTemplate as i32 | DynamicFlag::Insert as i32;
@@ -81,7 +83,7 @@ pub unsafe fn transform_v_for<'a>(
   // when the entire list is emptied
   let mut only_child = false;
   if let JSXChild::Element(parent_node) = parent_node
-    && !(is_jsx_component(parent_node) || is_custom_element(parent_node))
+    && !(is_jsx_component(parent_node) || is_custom_element(parent_node, context.options))
     && !is_template(parent_node)
   {
     let index = *context.index.borrow() as usize;
@@ -174,7 +176,9 @@ pub fn get_for_parse_result<'a>(
       source = Some((unsafe { &mut *expression }.right).take_in(context.allocator));
     }
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VForNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VForNoExpression, dir.span);
     return None;
   }
   Some(IRFor {
@@ -185,7 +189,10 @@ pub fn get_for_parse_result<'a>(
   })
 }
 
-fn is_template_with_single_component<'a>(node: &'a JSXElement<'a>) -> bool {
+fn is_template_with_single_component<'a>(
+  node: &'a JSXElement<'a>,
+  context: &TransformContext<'a>,
+) -> bool {
   if !is_template(node) {
     return false;
   }
@@ -197,6 +204,6 @@ fn is_template_with_single_component<'a>(node: &'a JSXElement<'a>) -> bool {
 
   non_comment_children.len() == 1
     && matches!(non_comment_children[0], JSXChild::Element(child)
-      if is_jsx_component(child) || is_custom_element(child)
+      if is_jsx_component(child) || is_custom_element(child, context.options)
     )
 }
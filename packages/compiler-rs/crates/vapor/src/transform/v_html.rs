@@ -21,12 +21,16 @@ pub fn transform_v_html<'a>(
   {
     value
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VHtmlNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VHtmlNoExpression, dir.span);
     return None;
   };
 
   if node.children.iter().any(|c| !is_empty_text(c)) {
-    context.options.on_error.as_ref()(ErrorCodes::VHtmlWithChildren, node.span);
+    context
+      .options
+      .error(ErrorCodes::VHtmlWithChildren, node.span);
     node.children.clear();
   }
 
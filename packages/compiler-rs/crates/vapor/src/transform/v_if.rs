@@ -1,4 +1,3 @@
-use napi::Either;
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{Expression, JSXChild, JSXElement};
 use oxc_span::GetSpan;
@@ -12,6 +11,7 @@ use common::{
   ast::RootNode,
   check::{is_constant_node, is_template},
   directive::{Directives, find_prop},
+  either::Either,
   error::ErrorCodes,
   expression::jsx_attribute_value_to_expression,
   patch_flag::{VaporBlockShape, VaporIfFlags},
@@ -51,7 +51,7 @@ pub unsafe fn transform_v_if<'a>(
     .as_mut()
     .and_then(|value| jsx_attribute_value_to_expression(value, context.ast));
   if dir_name != "v-else" && dir_exp.is_none() {
-    context.options.on_error.as_ref()(ErrorCodes::VIfNoExpression, dir.span);
+    context.options.error(ErrorCodes::VIfNoExpression, dir.span);
     return None;
   }
 
@@ -118,7 +118,9 @@ pub unsafe fn transform_v_if<'a>(
 
   // check if IfNode is the last operation and get the root IfNode
   let Some(mut last_if_node) = last_if_node else {
-    context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, unsafe { &*node }.span);
+    context
+      .options
+      .error(ErrorCodes::VElseNoAdjacentIf, unsafe { &*node }.span);
     return None;
   };
 
@@ -132,7 +134,9 @@ pub unsafe fn transform_v_if<'a>(
 
   // Check if v-else was followed by v-else-if
   if dir_name == "v-else-if" && last_if_node.negative.is_some() {
-    context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VElseNoAdjacentIf, dir.span);
   };
 
   let exit_block = context.create_block(
@@ -10,6 +10,7 @@ use crate::{
   transform::{DirectiveTransformResult, TransformContext},
 };
 use common::{
+  diagnostic::Diagnostic,
   directive::{Directives, resolve_directive},
   error::ErrorCodes,
 };
@@ -25,12 +26,16 @@ pub fn transform_v_model<'a>(
   let dir = resolve_directive(_dir, context.ast);
 
   let Some(exp) = &dir.exp else {
-    context.options.on_error.as_ref()(ErrorCodes::VModelNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VModelNoExpression, dir.span);
     return None;
   };
 
   if !(exp.is_identifier_reference() || exp.is_member_expression()) {
-    context.options.on_error.as_ref()(ErrorCodes::VModelMalformedExpression, exp.span());
+    context
+      .options
+      .error(ErrorCodes::VModelMalformedExpression, exp.span());
     return None;
   }
 
@@ -54,7 +59,9 @@ pub fn transform_v_model<'a>(
   }
 
   if dir.arg.is_some() {
-    context.options.on_error.as_ref()(ErrorCodes::VModelArgOnElement, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VModelArgOnElement, dir.span);
   }
 
   let tag = directives.tag_name;
@@ -73,7 +80,9 @@ pub fn transform_v_model<'a>(
             "checkbox" => model_type = "checkbox",
             "file" => {
               model_type = "";
-              context.options.on_error.as_ref()(ErrorCodes::VModelOnFileInputElement, node.span);
+              context
+                .options
+                .error(ErrorCodes::VModelOnFileInputElement, node.span);
             }
             // text type
             _ => check_duplicated_value(directives, context),
@@ -93,7 +102,9 @@ pub fn transform_v_model<'a>(
       check_duplicated_value(directives, context)
     }
   } else if !is_custom_element {
-    context.options.on_error.as_ref()(ErrorCodes::VModelOnInvalidElement, node.span)
+    context
+      .options
+      .error(ErrorCodes::VModelOnInvalidElement, node.span)
   }
 
   if !model_type.is_empty() {
@@ -120,7 +131,13 @@ fn check_duplicated_value(directives: &Directives, context: &TransformContext) {
   if let Some(value) = directives.value.as_ref()
     && !matches!(value.value, Some(JSXAttributeValue::StringLiteral(_)))
   {
-    context.options.on_error.as_ref()(ErrorCodes::VModelUnnecessaryValue, value.span);
+    context.options.report(
+      Diagnostic::error(ErrorCodes::VModelUnnecessaryValue, value.span).with_fix(
+        "remove the value binding",
+        value.span,
+        "",
+      ),
+    );
   }
 }
 
@@ -2,8 +2,9 @@ use std::borrow::Cow;
 
 use common::{
   check::{is_delegated_event, is_keyboard_event},
+  diagnostic::Diagnostic,
   directive::{Directives, Modifiers, resolve_modifiers},
-  error::ErrorCodes,
+  error::{ErrorCodes, WarningCodes},
   expression::jsx_attribute_value_to_expression,
 };
 use oxc_ast::{
@@ -54,7 +55,7 @@ pub fn transform_v_on<'a>(
 
   let value = &mut dir.value;
   if value.is_none() && !has_modifier {
-    context.options.on_error.as_ref()(ErrorCodes::VOnNoExpression, dir.span);
+    context.options.error(ErrorCodes::VOnNoExpression, dir.span);
   }
 
   let mut arg = ast.alloc_string_literal(
@@ -99,9 +100,12 @@ pub fn transform_v_on<'a>(
   } = resolve_modifiers(&arg.value, modifiers);
 
   if delegate_modifier && is_component {
-    context.options.on_warn.as_ref()(
-      ".delegate modifier is only supported on native DOM elements. The modifier will be ignored.",
-      name_loc,
+    context.options.report(
+      Diagnostic::warning(WarningCodes::VOnDelegateOnComponent, name_loc).with_fix(
+        "remove the .delegate modifier",
+        name_loc,
+        name.replacen("_delegate", "", 1),
+      ),
     );
   }
 
@@ -131,12 +135,13 @@ pub fn transform_v_on<'a>(
   let delegate = if !delegate_modifier || is_component {
     false
   } else if !is_delegated_event(&arg.value) {
-    context.options.on_warn.as_ref()(
-      &format!(
-        ".delegate modifier is not supported on the \"{}\" event. The listener will be attached directly.",
-        arg.value
-      ),
-      name_loc,
+    context.options.report(
+      Diagnostic::warning(WarningCodes::VOnDelegateUnsupportedEvent, name_loc)
+        .with_message(format!(
+          ".delegate modifier is not supported on the \"{}\" event. The listener will be attached directly.",
+          arg.value
+        ))
+        .with_label(format!("\"{}\" is not a delegated event", arg.value)),
     );
     false
   } else {
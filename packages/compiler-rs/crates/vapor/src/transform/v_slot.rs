@@ -1,9 +1,8 @@
 use std::mem;
 
 use indexmap::IndexMap;
-use napi::{Either, bindgen_prelude::Either4};
 use oxc_ast::ast::{Expression, JSXChild, JSXElement};
-use oxc_span::SPAN;
+use oxc_span::{SPAN, Span};
 
 use crate::{
   ir::{
@@ -15,6 +14,7 @@ use crate::{
 use common::{
   check::{is_custom_element, is_jsx_component, is_template},
   directive::{DirectiveNode, Directives, find_prop, resolve_directive},
+  either::{Either, Either4},
   error::ErrorCodes,
   text::is_empty_text,
 };
@@ -39,7 +39,7 @@ pub unsafe fn transform_v_slot<'a>(
   let is_component = directives.is_component;
   let is_slot_template = is_template(unsafe { &*node })
     && if let JSXChild::Element(parent_node) = parent_node
-      && (is_jsx_component(parent_node) || is_custom_element(parent_node))
+      && (is_jsx_component(parent_node) || is_custom_element(parent_node, context.options))
     {
       true
     } else {
@@ -57,11 +57,14 @@ pub unsafe fn transform_v_slot<'a>(
     return Some(transform_template_slot(
       directives,
       dir,
+      unsafe { &*node }.span,
       context,
       context_block,
     ));
   } else if !is_component && dir.is_some() {
-    context.options.on_error.as_ref()(ErrorCodes::VSlotMisplaced, unsafe { &*node }.span);
+    context
+      .options
+      .error(ErrorCodes::VSlotMisplaced, unsafe { &*node }.span);
   }
   None
 }
@@ -95,7 +98,7 @@ fn transform_component_slot<'a>(
     .count();
 
   let context_block = context_block as *mut BlockIRNode;
-  let exit_block = create_slot_block(exp, context, unsafe { &mut *context_block });
+  let exit_block = create_slot_block(exp, node.span, context, unsafe { &mut *context_block });
 
   Box::new(move || {
     let mut slots = mem::take(&mut unsafe { &mut *context_block }.slots);
@@ -104,16 +107,17 @@ fn transform_component_slot<'a>(
     let has_other_slots = !slots.is_empty();
     if has_dir && has_other_slots {
       // already has on-component slot - this is incorrect usage.
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMixedSlotUsage, node.span);
+      context
+        .options
+        .error(ErrorCodes::VSlotMixedSlotUsage, node.span);
       return;
     }
 
     if non_slot_template_children_len > 0 {
       if has_static_slot(&slots, "default") {
-        context.options.on_error.as_ref()(
-          ErrorCodes::VSlotExtraneousDefaultSlotChildren,
-          node.span,
-        );
+        context
+          .options
+          .error(ErrorCodes::VSlotExtraneousDefaultSlotChildren, node.span);
       } else {
         register_slot(&mut slots, arg, block, context);
         unsafe { &mut *context_block }.slots = slots;
@@ -128,6 +132,7 @@ fn transform_component_slot<'a>(
 fn transform_template_slot<'a>(
   directives: &'a mut Directives<'a>,
   dir: DirectiveNode<'a>,
+  span: Span,
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) -> Box<dyn FnOnce() + 'a> {
@@ -137,7 +142,7 @@ fn transform_template_slot<'a>(
   dynamic.flags |= DynamicFlag::NonTemplate as i32;
 
   let DirectiveNode { arg, exp, .. } = dir;
-  let exit_block = create_slot_block(exp, context, context_block);
+  let exit_block = create_slot_block(exp, span, context, context_block);
 
   let for_parse_result = if let Some(v_for) = directives.v_for.as_mut() {
     get_for_parse_result(v_for, context)
@@ -165,7 +170,9 @@ fn transform_template_slot<'a>(
         "default"
       };
       if !slot_name.is_empty() && has_static_slot(slots, slot_name) {
-        context.options.on_error.as_ref()(ErrorCodes::VSlotDuplicateSlotNames, dir.span)
+        context
+          .options
+          .error(ErrorCodes::VSlotDuplicateSlotNames, dir.span)
       } else {
         register_slot(slots, Some(name), block, context);
       }
@@ -203,7 +210,9 @@ fn transform_template_slot<'a>(
         };
         set_slot(v_if_slot, negative);
       } else {
-        context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, v_else_dir.span)
+        context
+          .options
+          .error(ErrorCodes::VElseNoAdjacentIf, v_else_dir.span)
       }
     } else if let Some(for_parse_result) = for_parse_result
       && for_parse_result.source.is_some()
@@ -282,11 +291,12 @@ fn has_static_slot(slots: &Vec<IRSlots>, name: &str) -> bool {
 
 fn create_slot_block<'a>(
   props: Option<Expression<'a>>,
+  span: Span,
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) -> Box<dyn FnOnce() -> BlockIRNode<'a> + 'a> {
   let mut block = BlockIRNode::new();
   block.props = props;
 
-  context.enter_block(context_block, block, false)
+  context.enter_block(context_block, block, false, span)
 }
@@ -1,4 +1,3 @@
-use napi::bindgen_prelude::Either4;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -18,7 +17,8 @@ use crate::{
   transform::TransformContext,
 };
 use common::{
-  check::is_slots_expression, directive::Directives, error::ErrorCodes, text::is_empty_text,
+  check::is_slots_expression, directive::Directives, either::Either4, error::ErrorCodes,
+  text::is_empty_text,
 };
 
 /// # SAFETY
@@ -75,7 +75,9 @@ pub unsafe fn transform_v_slots<'a>(
 
   if let Some(dir) = directives.v_slots.as_mut() {
     if !is_component {
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMisplaced, unsafe { &*node_ptr }.span);
+      context
+        .options
+        .error(ErrorCodes::VSlotMisplaced, unsafe { &*node_ptr }.span);
       return None;
     }
 
@@ -126,7 +128,9 @@ pub unsafe fn transform_v_slots<'a>(
         })];
       }))
     } else {
-      context.options.on_error.as_ref()(ErrorCodes::VSlotsNoExpression, dir.span);
+      context
+        .options
+        .error(ErrorCodes::VSlotsNoExpression, dir.span);
       None
     }
   } else {
@@ -26,12 +26,16 @@ pub fn transform_v_text<'a>(
   {
     value
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VTextNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VTextNoExpression, dir.span);
     return None;
   };
 
   if node.children.iter().any(|c| !is_empty_text(c)) {
-    context.options.on_error.as_ref()(ErrorCodes::VTextWithChildren, node.span);
+    context
+      .options
+      .error(ErrorCodes::VTextWithChildren, node.span);
     node.children.clear();
   };
 
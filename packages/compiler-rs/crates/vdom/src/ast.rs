@@ -1,4 +1,4 @@
-use napi::bindgen_prelude::Either3;
+use common::either::Either3;
 use oxc_ast::ast::{ArrayExpression, Expression, JSXAttribute, JSXChild};
 use oxc_span::Span;
 
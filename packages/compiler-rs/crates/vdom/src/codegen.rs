@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
-use common::text::{hash_string, resolve_jsx_text, to_valid_asset_id};
-use napi::bindgen_prelude::Either3;
+use common::{
+  ast::source_span,
+  either::Either3,
+  text::{hash_string, resolve_jsx_text, to_valid_asset_id},
+};
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -272,6 +275,7 @@ impl<'a> TransformContext<'a> {
       disable_tracking,
       is_component,
       v_for,
+      loc,
       ..
     } = node;
 
@@ -305,9 +309,13 @@ impl<'a> TransformContext<'a> {
         },
       )
     };
+    // the codegen maps a call through its callee
     let mut result = ast.expression_call(
-      SPAN,
-      ast.expression_identifier(SPAN, ast.str(self.options.helper(call_helper))),
+      source_span([loc]),
+      ast.expression_identifier(
+        source_span([loc]),
+        ast.str(self.options.helper(call_helper)),
+      ),
       NONE,
       ast.vec_from_iter(
         [
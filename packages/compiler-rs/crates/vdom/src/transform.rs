@@ -15,6 +15,7 @@ use oxc_span::{GetSpan, SPAN, Span};
 use std::collections::HashMap;
 use std::{cell::RefCell, collections::HashSet, rc::Rc};
 pub mod cache_static;
+pub mod stringify_static;
 pub mod transform_children;
 pub mod transform_element;
 pub mod transform_slot_outlet;
@@ -2,11 +2,11 @@ use std::collections::HashMap;
 
 use common::{
   check::{get_directive_name, is_jsx_component},
+  either::{Either, Either3},
   patch_flag::PatchFlags,
   text::{get_tag_name, is_empty_text},
   walk::WalkIdentifiers,
 };
-use napi::{Either, bindgen_prelude::Either3};
 use oxc_ast::ast::{
   Expression, JSXAttributeItem, JSXAttributeValue, JSXChild, JSXElement, NumberBase,
   ObjectPropertyKind,
@@ -15,7 +15,7 @@ use oxc_span::{GetSpan, SPAN, Span};
 
 use crate::{
   ast::{ConstantTypes, NodeTypes, VNodeCallChildren, get_vnode_block_helper},
-  transform::TransformContext,
+  transform::{TransformContext, stringify_static::stringify_static},
 };
 
 pub fn cache_static<'a>(
@@ -42,9 +42,8 @@ pub fn cache_static_children<'a>(
     return;
   }
   let codegen_map_ptr = codegen_map as *mut HashMap<Span, NodeTypes>;
-  let child_len = children.len();
   let mut to_cache = vec![];
-  for child in children {
+  for child in children.iter_mut() {
     let child_ptr = child as *mut JSXChild;
     let child = unsafe { &mut *child_ptr };
     let child_span = child.span();
@@ -58,7 +57,7 @@ pub fn cache_static_children<'a>(
           if let Some(NodeTypes::VNodeCall(codegen)) = codegen_map.get_mut(&child.span) {
             codegen.patch_flag = Some(PatchFlags::Cached as i32);
           };
-          to_cache.push(unsafe { &mut *child_ptr });
+          to_cache.push(child.span);
           continue;
         }
       } else if let Some(codegen) = unsafe { &mut *codegen_map_ptr }.get_mut(&child_span)
@@ -102,7 +101,7 @@ pub fn cache_static_children<'a>(
               .into(),
           );
         }
-        to_cache.push(unsafe { &mut *child_ptr });
+        to_cache.push(child_span);
         continue;
       }
     }
@@ -128,6 +127,15 @@ pub fn cache_static_children<'a>(
     }
   }
 
+  // slot content is left alone since it may be rendered more than once
+  if !to_cache.is_empty()
+    && let Some(Either::A(node)) = &node
+    && !matches!(node, JSXChild::Element(node) if is_jsx_component(node))
+  {
+    stringify_static(children, &mut to_cache, context, codegen_map);
+  }
+
+  let child_len = children.len();
   if child_len > 1
     && to_cache.len() == child_len
     && let Some((children, codegen_children)) = if let Some(Either::A(node)) = node
@@ -156,8 +164,7 @@ pub fn cache_static_children<'a>(
     return;
   }
 
-  for child in to_cache {
-    let span = child.span();
+  for span in to_cache {
     match codegen_map.remove(&span) {
       Some(NodeTypes::VNodeCall(codegen)) => {
         unsafe { &mut *codegen_map_ptr }.insert(
@@ -0,0 +1,271 @@
+use std::{borrow::Cow, collections::HashMap, ops::Range};
+
+use common::{
+  check::{is_boolean_attr, is_event, is_reserved_prop, is_void_tag},
+  either::Either3,
+  text::{escape_html, resolve_jsx_text, stringify_attribute},
+};
+use oxc_ast::{
+  NONE,
+  ast::{Expression, JSXChild, NumberBase, ObjectPropertyKind},
+};
+use oxc_span::{GetSpan, SPAN, Span};
+
+use crate::{
+  ast::{ConstantTypes, NodeTypes},
+  transform::TransformContext,
+};
+
+// Runs of cached siblings are only turned into a static vnode once they are
+// large enough to outweigh the cost of parsing the html at runtime.
+const NODE_COUNT: usize = 20;
+const ELEMENT_WITH_BINDING_COUNT: usize = 5;
+
+// Tags that can't be created by `innerHTML` outside of their table context.
+const NON_STRINGIFIABLE_TAGS: &[&str] = &[
+  "caption", "thead", "tr", "th", "tbody", "td", "tfoot", "colgroup", "col",
+];
+
+// Properties that are set on the element instead of as attributes.
+const DOM_PROPS: &[&str] = &["value", "checked", "selected", "muted", "indeterminate"];
+
+#[derive(Default)]
+struct Chunk {
+  html: String,
+  // number of nodes
+  nc: usize,
+  // number of elements with non-string props
+  ec: usize,
+}
+
+/// Collapses runs of cached children into `_createStaticVNode(html, count)`,
+/// removing the merged children from both `children` and `to_cache`.
+pub fn stringify_static<'a>(
+  children: &mut oxc_allocator::Vec<'_, JSXChild<'a>>,
+  to_cache: &mut Vec<Span>,
+  context: &TransformContext<'a>,
+  codegen_map: &mut HashMap<Span, NodeTypes<'a>>,
+) {
+  let mut chunks: Vec<(Range<usize>, String)> = vec![];
+  let mut flush = |range: Range<usize>, chunk: &mut Chunk| {
+    let chunk = std::mem::take(chunk);
+    if chunk.nc >= NODE_COUNT || chunk.ec >= ELEMENT_WITH_BINDING_COUNT {
+      chunks.push((range, chunk.html));
+    }
+  };
+
+  let mut start = 0;
+  let mut chunk = Chunk::default();
+  for (index, child) in children.iter().enumerate() {
+    // adjacent text would be parsed into a single DOM node, breaking the
+    // node count of the static vnode
+    if index > start && is_text(child) && is_text(&children[index - 1]) {
+      flush(start..index, &mut chunk);
+      start = index;
+    }
+    let mut child_chunk = Chunk::default();
+    if to_cache.contains(&child.span())
+      && stringify_node(child, true, context, codegen_map, &mut child_chunk)
+    {
+      chunk.html += &child_chunk.html;
+      chunk.nc += child_chunk.nc;
+      chunk.ec += child_chunk.ec;
+    } else {
+      flush(start..index, &mut chunk);
+      start = index + 1;
+    }
+  }
+  flush(start..children.len(), &mut chunk);
+
+  let ast = &context.ast;
+  for (range, html) in chunks.into_iter().rev() {
+    let static_call = ast.expression_call(
+      SPAN,
+      ast.expression_identifier(SPAN, ast.str(context.options.helper("_createStaticVNode"))),
+      NONE,
+      ast.vec_from_array([
+        ast
+          .expression_string_literal(SPAN, ast.str(&html), None)
+          .into(),
+        ast
+          .expression_numeric_literal(SPAN, range.len() as f64, None, NumberBase::Decimal)
+          .into(),
+      ]),
+      false,
+    );
+    codegen_map.insert(
+      children[range.start].span(),
+      NodeTypes::TextCallNode(static_call),
+    );
+    for child in children.drain(range.start + 1..range.end) {
+      let span = child.span();
+      to_cache.retain(|cached| *cached != span);
+    }
+  }
+}
+
+fn is_text(node: &JSXChild) -> bool {
+  matches!(node, JSXChild::Text(_) | JSXChild::ExpressionContainer(_))
+}
+
+fn stringify_node<'a>(
+  node: &JSXChild<'a>,
+  is_root: bool,
+  context: &TransformContext<'a>,
+  codegen_map: &HashMap<Span, NodeTypes<'a>>,
+  chunk: &mut Chunk,
+) -> bool {
+  match node {
+    JSXChild::Element(node) => {
+      if !matches!(
+        context.constant_cache.borrow().get(&node.span),
+        Some(ConstantTypes::CanStringify)
+      ) {
+        return false;
+      }
+      let Some(NodeTypes::VNodeCall(codegen)) = codegen_map.get(&node.span) else {
+        return false;
+      };
+      let tag = codegen.tag;
+      if tag.is_empty()
+        || codegen.is_component
+        || context.options.is_custom_element(tag)
+        || codegen.directives.is_some()
+        || NON_STRINGIFIABLE_TAGS.contains(&tag)
+      {
+        return false;
+      }
+
+      chunk.html += &format!("<{tag}");
+      let mut has_binding = false;
+      match &codegen.props {
+        Some(Expression::ObjectExpression(props)) => {
+          for prop in props.properties.iter() {
+            let ObjectPropertyKind::ObjectProperty(prop) = prop else {
+              return false;
+            };
+            let Some(name) = prop.key.static_name() else {
+              return false;
+            };
+            if !is_stringifiable_attr(&name) {
+              return false;
+            }
+            let value = match &prop.value {
+              Expression::StringLiteral(value) => Some(Cow::Borrowed(value.value.as_str())),
+              value => {
+                has_binding = true;
+                match value {
+                  Expression::NumericLiteral(value) => Some(Cow::Owned(value.value.to_string())),
+                  Expression::BooleanLiteral(value) if is_boolean_attr(&name) => {
+                    value.value.then_some(Cow::Borrowed(""))
+                  }
+                  Expression::BooleanLiteral(value) => Some(Cow::Owned(value.value.to_string())),
+                  Expression::NullLiteral(_) => None,
+                  Expression::TemplateLiteral(value) if value.expressions.is_empty() => {
+                    let Some(value) = value.quasis.first().and_then(|quasi| quasi.value.cooked)
+                    else {
+                      return false;
+                    };
+                    Some(Cow::Borrowed(value.as_str()))
+                  }
+                  _ => return false,
+                }
+              }
+            };
+            if let Some(value) = value {
+              chunk.html += &stringify_attribute(&name, &value);
+            }
+          }
+        }
+        Some(_) => return false,
+        None => (),
+      }
+      chunk.html += ">";
+
+      let stringified = match &codegen.children {
+        Some(Either3::A(child)) => {
+          stringify_node(unsafe { &**child }, false, context, codegen_map, chunk)
+        }
+        Some(Either3::B(children)) => unsafe { &**children }
+          .iter()
+          .all(|child| stringify_node(child, false, context, codegen_map, chunk)),
+        Some(Either3::C(_)) => false,
+        None => true,
+      };
+      if !stringified {
+        return false;
+      }
+
+      if !is_void_tag(tag) {
+        chunk.html += &format!("</{tag}>");
+      }
+      chunk.nc += 1;
+      if has_binding {
+        chunk.ec += 1;
+      }
+      true
+    }
+    JSXChild::Text(node) => {
+      let text = match codegen_map.get(&node.span) {
+        Some(NodeTypes::TextCallNode(codegen)) => {
+          let Some(text) = get_text_call_value(codegen) else {
+            return false;
+          };
+          text
+        }
+        Some(_) => return false,
+        None => resolve_jsx_text(node),
+      };
+      push_text(text, is_root, chunk)
+    }
+    JSXChild::ExpressionContainer(node) => {
+      let text = match codegen_map.get(&node.span) {
+        Some(NodeTypes::TextCallNode(codegen)) => get_text_call_value(codegen),
+        Some(_) => return false,
+        None => node.expression.as_expression().and_then(get_literal_text),
+      };
+      let Some(text) = text else {
+        return false;
+      };
+      push_text(text, is_root, chunk)
+    }
+    _ => false,
+  }
+}
+
+fn push_text(text: Cow<str>, is_root: bool, chunk: &mut Chunk) -> bool {
+  // an empty text node has no counterpart in the parsed html
+  if is_root {
+    if text.is_empty() {
+      return false;
+    }
+    chunk.nc += 1;
+  }
+  chunk.html += &escape_html(text);
+  true
+}
+
+// `_normalizeVNode("text")`
+fn get_text_call_value<'b>(codegen: &'b Expression) -> Option<Cow<'b, str>> {
+  let Expression::CallExpression(call) = codegen else {
+    return None;
+  };
+  get_literal_text(call.arguments.first()?.as_expression()?)
+}
+
+fn get_literal_text<'b>(exp: &'b Expression) -> Option<Cow<'b, str>> {
+  match exp {
+    Expression::StringLiteral(exp) => Some(Cow::Borrowed(exp.value.as_str())),
+    Expression::NumericLiteral(exp) => Some(Cow::Owned(exp.value.to_string())),
+    // booleans and nullish values are rendered as comments
+    _ => None,
+  }
+}
+
+fn is_stringifiable_attr(name: &str) -> bool {
+  !is_reserved_prop(name)
+    && !is_event(name)
+    && !DOM_PROPS.contains(&name)
+    // camelCase props such as `innerHTML` are DOM properties
+    && !name.contains(|c: char| c.is_ascii_uppercase())
+}
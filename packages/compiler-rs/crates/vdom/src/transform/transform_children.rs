@@ -2,7 +2,11 @@ use oxc_ast::ast::JSXChild;
 
 use crate::transform::TransformContext;
 
-use common::{check::is_fragment_node, directive::Directives, text::is_empty_text};
+use common::{
+  check::{is_fragment_node, is_jsx_component},
+  directive::Directives,
+  text::{get_tag_name, is_empty_text},
+};
 
 /// # SAFETY
 pub unsafe fn transform_children<'a>(
@@ -26,6 +30,16 @@ pub unsafe fn transform_children<'a>(
       if is_empty_text(child) {
         false
       } else {
+        if !is_fragment_or_component
+          && let JSXChild::Element(child) = child
+          && !is_jsx_component(child)
+        {
+          context.options.check_html_nesting(
+            directives.tag_name,
+            get_tag_name(child, context.options),
+            child.opening_element.span,
+          );
+        }
         context.transform_node(child, Some(node));
         true
       }
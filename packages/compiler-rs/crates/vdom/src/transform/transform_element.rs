@@ -1,7 +1,6 @@
 use std::borrow::Cow;
 
 use indexmap::IndexSet;
-use napi::{Either, bindgen_prelude::Either3};
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
   AstBuilder, NONE,
@@ -29,7 +28,8 @@ use common::{
     get_directive_name, is_built_in_directive, is_event, is_reserved_prop, is_slots_component,
     is_template,
   },
-  directive::{DirectiveNode, resolve_directive, resolve_prop_name},
+  directive::{DirectiveNode, apply_directive_transform, resolve_directive, resolve_prop_name},
+  either::{Either, Either3},
   error::ErrorCodes,
   patch_flag::PatchFlags,
   text::{camelize, get_tag_name, to_valid_asset_id},
@@ -147,7 +147,7 @@ pub unsafe fn transform_element<'a>(
         // 2. Force keep-alive to always be updated, since it uses raw children.
         patch_flag |= PatchFlags::DynamicSlots as i32;
         if children.len() > 1 {
-          context.options.on_error.as_ref()(
+          context.options.error(
             ErrorCodes::KeepAliveInvalidChildren,
             Span::new(
               children.first().unwrap().span().start,
@@ -235,7 +235,7 @@ pub unsafe fn transform_element<'a>(
       is_component,
       v_for: None,
       v_if: None,
-      loc: SPAN,
+      loc: node_span,
     };
     context
       .codegen_map
@@ -426,7 +426,7 @@ pub fn build_props<'a>(
         // skip v-slot - it is handled by its dedicated transform.
         if dir_name == "slot" {
           if !is_component {
-            context.options.on_error.as_ref()(ErrorCodes::VSlotMisplaced, prop.span);
+            context.options.error(ErrorCodes::VSlotMisplaced, prop.span);
           }
           continue;
         }
@@ -461,7 +461,9 @@ pub fn build_props<'a>(
                 false,
               ));
             } else {
-              context.options.on_error.as_ref()(ErrorCodes::VOnNoExpression, prop.span);
+              context
+                .options
+                .error(ErrorCodes::VOnNoExpression, prop.span);
             }
             continue;
           }
@@ -502,8 +504,12 @@ pub fn build_props<'a>(
           "show" => transform_v_show(prop, context),
           "html" => transform_v_html(prop, node, context),
           "text" => transform_v_text(prop, node, context),
-          _ => {
-            if !is_built_in_directive(dir_name) {
+          _ if !is_built_in_directive(dir_name) => {
+            let custom = apply_directive_transform(prop, dir_name, directives, context.options);
+            if custom
+              .as_ref()
+              .is_none_or(|(_, need_runtime)| *need_runtime)
+            {
               let runtime = if dir_name
                 .chars()
                 .nth(1)
@@ -539,8 +545,27 @@ pub fn build_props<'a>(
                 is_block_required = true;
               }
             }
-            None
+            custom.map(|(attributes, _)| {
+              let mut result = DirectiveTransformResult {
+                props: vec![],
+                runtime: None,
+                has_jsx: false,
+              };
+              for attribute in attributes {
+                let generated = if is_event(&attribute.name.get_identifier().name) {
+                  transform_v_on(attribute, context)
+                } else {
+                  transform_v_bind(attribute, node, context)
+                };
+                if let Some(generated) = generated {
+                  result.props.extend(generated.props);
+                  result.has_jsx |= generated.has_jsx;
+                }
+              }
+              result
+            })
           }
+          _ => None,
         } {
           if has_jsx {
             should_use_block = true;
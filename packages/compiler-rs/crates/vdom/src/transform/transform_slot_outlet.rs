@@ -170,7 +170,7 @@ fn process_slot_outlet<'a>(
     if let Some(directives) = directives
       && !directives.elements.is_empty()
     {
-      context.options.on_error.as_ref()(
+      context.options.error(
         ErrorCodes::VSlotUnexpectedDirectiveOnSlotOutlet,
         directives.elements.first().unwrap().span(),
       );
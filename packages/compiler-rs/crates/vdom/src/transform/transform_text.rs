@@ -1,4 +1,3 @@
-use napi::bindgen_prelude::Either3;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -17,6 +16,7 @@ use crate::{
 use common::{
   check::{get_directive_name, is_built_in_directive, is_template},
   directive::Directives,
+  either::Either3,
   patch_flag::PatchFlags,
   text::resolve_jsx_text,
 };
@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 
-use common::{check::is_simple_identifier, directive::resolve_prop_name, text::camelize};
+use common::{
+  check::is_simple_identifier, constant::fold_constant, directive::resolve_prop_name,
+  text::camelize,
+};
 use oxc_ast::ast::{JSXAttribute, JSXAttributeName, JSXAttributeValue, JSXElement, PropertyKind};
 use oxc_span::{GetSpan, SPAN};
 
@@ -45,6 +48,9 @@ pub fn transform_v_bind<'a>(
     if let JSXAttributeValue::ExpressionContainer(value) = value
       && let Some(value) = value.expression.as_expression_mut()
     {
+      // props bound to constants can be cached, like in Vapor they are folded
+      // whether `optimize` is on or not
+      fold_constant(value, context.options, ast);
       let result = context.process_expression(value);
       has_jsx = result.3;
       result.0
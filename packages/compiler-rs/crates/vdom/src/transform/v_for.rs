@@ -1,4 +1,3 @@
-use napi::{Either, bindgen_prelude::Either3};
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
   NONE,
@@ -18,7 +17,12 @@ use crate::{
     utils::inject_prop,
   },
 };
-use common::{check::is_template, error::ErrorCodes, patch_flag::PatchFlags};
+use common::{
+  check::is_template,
+  either::{Either, Either3},
+  error::ErrorCodes,
+  patch_flag::PatchFlags,
+};
 
 /// # SAFETY
 pub unsafe fn transform_v_for<'a>(
@@ -52,7 +56,9 @@ pub unsafe fn transform_v_for<'a>(
   } = get_for_parse_result(dir, context)?;
 
   let Some(source) = source else {
-    context.options.on_error.as_ref()(ErrorCodes::VForMalformedExpression, span);
+    context
+      .options
+      .error(ErrorCodes::VForMalformedExpression, span);
     return None;
   };
 
@@ -392,7 +398,9 @@ pub fn get_for_parse_result<'a>(
       source = Some(unsafe { &mut *expression }.right.take_in(context.allocator));
     }
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VForNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VForNoExpression, dir.span);
     return None;
   }
   // scope management
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
-use napi::bindgen_prelude::Either3;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
-  ast::{ConditionalExpression, Expression, JSXChild, JSXElement, NumberBase, PropertyKind},
+  ast::{
+    ConditionalExpression, Expression, JSXAttributeValue, JSXChild, JSXElement, JSXExpression,
+    NumberBase, PropertyKind,
+  },
 };
 use oxc_span::{GetSpan, SPAN, Span};
 
@@ -15,7 +17,7 @@ use crate::{
   },
 };
 
-use common::{check::is_template, error::ErrorCodes};
+use common::{check::is_template, either::Either3, error::ErrorCodes};
 
 /// # SAFETY
 pub unsafe fn transform_v_if<'a>(
@@ -48,8 +50,14 @@ pub unsafe fn transform_v_if<'a>(
   let ast = &context.ast;
 
   let dir_name = dir.name.get_identifier().name;
-  if dir_name != "v-else" && dir.value.is_none() {
-    context.options.on_error.as_ref()(ErrorCodes::VIfNoExpression, dir.span);
+  // `v-if={}` has no expression either
+  if dir_name != "v-else"
+    && dir.value.as_ref().is_none_or(|value| {
+      matches!(value, JSXAttributeValue::ExpressionContainer(value)
+        if matches!(value.expression, JSXExpression::EmptyExpression(_)))
+    })
+  {
+    context.options.error(ErrorCodes::VIfNoExpression, dir.span);
     dir.value = Some(ast.jsx_attribute_value_expression_container(
       SPAN,
       ast.expression_boolean_literal(SPAN, true).into(),
@@ -132,7 +140,9 @@ pub unsafe fn transform_v_if<'a>(
       .unwrap();
       branchs.push(branch);
     } else {
-      context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, unsafe { &*node }.span);
+      context
+        .options
+        .error(ErrorCodes::VElseNoAdjacentIf, unsafe { &*node }.span);
       return None;
     }
   }
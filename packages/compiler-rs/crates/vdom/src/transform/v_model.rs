@@ -14,6 +14,7 @@ use crate::transform::{
 };
 use common::{
   check::is_simple_identifier,
+  diagnostic::Diagnostic,
   directive::{Directives, get_modifier_prop_name, resolve_directive},
   error::ErrorCodes,
 };
@@ -27,7 +28,9 @@ pub fn transform_v_model<'a>(
   let ast = &context.ast;
   let dir_ref = _dir as *mut JSXAttribute;
   let Some(exp) = &mut unsafe { &mut *dir_ref }.value else {
-    context.options.on_error.as_ref()(ErrorCodes::VModelNoExpression, _dir.span);
+    context
+      .options
+      .error(ErrorCodes::VModelNoExpression, _dir.span);
     return None;
   };
   let mut dir = resolve_directive(_dir, context.ast);
@@ -40,7 +43,9 @@ pub fn transform_v_model<'a>(
   {
     (exp, has_scope_ref)
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VModelMalformedExpression, exp.span());
+    context
+      .options
+      .error(ErrorCodes::VModelMalformedExpression, exp.span());
     return None;
   };
 
@@ -230,7 +235,9 @@ pub fn transform_v_model<'a>(
   }
 
   if arg_is_some {
-    context.options.on_error.as_ref()(ErrorCodes::VModelArgOnElement, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VModelArgOnElement, dir.span);
   }
 
   let mut runtime_name = None;
@@ -249,7 +256,9 @@ pub fn transform_v_model<'a>(
             "checkbox" => directive_to_use = "_vModelCheckbox",
             "file" => {
               is_invalid_type = true;
-              context.options.on_error.as_ref()(ErrorCodes::VModelOnFileInputElement, node.span);
+              context
+                .options
+                .error(ErrorCodes::VModelOnFileInputElement, node.span);
             }
             // text type
             _ => check_duplicated_value(directives, context),
@@ -275,7 +284,9 @@ pub fn transform_v_model<'a>(
       runtime_name = Some(context.options.helper(directive_to_use));
     }
   } else if !is_custom_element {
-    context.options.on_error.as_ref()(ErrorCodes::VModelOnInvalidElement, node.span)
+    context
+      .options
+      .error(ErrorCodes::VModelOnInvalidElement, node.span)
   }
 
   // native vmodel doesn't need the `modelValue` props since they are also
@@ -307,7 +318,13 @@ fn check_duplicated_value(directives: &Directives, context: &TransformContext) {
   if let Some(value) = directives.value.as_ref()
     && !matches!(value.value, Some(JSXAttributeValue::StringLiteral(_)))
   {
-    context.options.on_error.as_ref()(ErrorCodes::VModelUnnecessaryValue, value.span);
+    context.options.report(
+      Diagnostic::error(ErrorCodes::VModelUnnecessaryValue, value.span).with_fix(
+        "remove the value binding",
+        value.span,
+        "",
+      ),
+    );
   }
 }
 
@@ -4,6 +4,7 @@ use common::{
   check::{is_keyboard_event, is_simple_identifier},
   directive::{Modifiers, resolve_modifiers},
   error::ErrorCodes,
+  modifier::{gen_modifier_guards, is_inlinable_handler},
   text::capitalize,
 };
 use oxc_ast::{
@@ -36,7 +37,7 @@ pub fn transform_v_on<'a>(
 
   let value = &mut dir.value;
   if value.is_none() && modifiers.is_empty() {
-    context.options.on_error.as_ref()(ErrorCodes::VOnNoExpression, dir.span);
+    context.options.error(ErrorCodes::VOnNoExpression, dir.span);
   }
 
   if event_name.starts_with("vue:") {
@@ -96,48 +97,77 @@ pub fn transform_v_on<'a>(
       event_name = Cow::Borrowed("contextmenu");
     }
 
-    if !non_key_modifiers.is_empty() {
-      exp = ast.expression_call(
-        SPAN,
-        ast.expression_identifier(SPAN, ast.str(context.options.helper("_withModifiers"))),
-        NONE,
-        ast.vec_from_array([
-          exp.into(),
-          ast
-            .expression_array(
-              SPAN,
-              ast.vec_from_iter(non_key_modifiers.iter().map(|modifier| {
-                ast
-                  .expression_string_literal(SPAN, ast.str(modifier), None)
-                  .into()
-              })),
-            )
-            .into(),
-        ]),
-        false,
-      )
-    }
+    // key guards only apply to keyboard events
+    let key_modifiers = if is_keyboard_event(&event_name) {
+      key_modifiers
+    } else {
+      vec![]
+    };
 
-    if !key_modifiers.is_empty() && is_keyboard_event(&event_name) {
-      exp = ast.expression_call(
-        SPAN,
-        ast.expression_identifier(SPAN, ast.str(context.options.helper("_withKeys"))),
-        NONE,
-        ast.vec_from_array([
-          exp.into(),
-          ast
-            .expression_array(
-              SPAN,
-              ast.vec_from_iter(key_modifiers.into_iter().map(|key| {
-                ast
-                  .expression_string_literal(SPAN, ast.str(&key), None)
-                  .into()
-              })),
-            )
-            .into(),
-        ]),
-        false,
+    // the guards are a new closure on every render unless they are cached,
+    // so otherwise the helpers that cache on the handler are kept
+    if context.options.inline_modifiers
+      && should_cache
+      && is_inlinable_handler(&exp)
+      && (!key_modifiers.is_empty() || !non_key_modifiers.is_empty())
+    {
+      // the modifiers borrow the attribute name
+      let to_arena = |modifiers: &[Cow<str>]| {
+        modifiers
+          .iter()
+          .map(|modifier| Cow::Borrowed(ast.str(modifier).as_str()))
+          .collect::<Vec<_>>()
+      };
+      exp = gen_modifier_guards(
+        exp,
+        &to_arena(&key_modifiers),
+        &to_arena(&non_key_modifiers),
+        ast,
       );
+    } else {
+      if !non_key_modifiers.is_empty() {
+        exp = ast.expression_call(
+          SPAN,
+          ast.expression_identifier(SPAN, ast.str(context.options.helper("_withModifiers"))),
+          NONE,
+          ast.vec_from_array([
+            exp.into(),
+            ast
+              .expression_array(
+                SPAN,
+                ast.vec_from_iter(non_key_modifiers.iter().map(|modifier| {
+                  ast
+                    .expression_string_literal(SPAN, ast.str(modifier), None)
+                    .into()
+                })),
+              )
+              .into(),
+          ]),
+          false,
+        )
+      }
+
+      if !key_modifiers.is_empty() {
+        exp = ast.expression_call(
+          SPAN,
+          ast.expression_identifier(SPAN, ast.str(context.options.helper("_withKeys"))),
+          NONE,
+          ast.vec_from_array([
+            exp.into(),
+            ast
+              .expression_array(
+                SPAN,
+                ast.vec_from_iter(key_modifiers.into_iter().map(|key| {
+                  ast
+                    .expression_string_literal(SPAN, ast.str(&key), None)
+                    .into()
+                })),
+              )
+              .into(),
+          ]),
+          false,
+        );
+      }
     }
 
     if !event_option_modifiers.is_empty() {
@@ -10,7 +10,9 @@ pub fn transform_v_show<'a>(
   context: &'a TransformContext<'a>,
 ) -> Option<DirectiveTransformResult<'a>> {
   if dir.value.is_none() {
-    context.options.on_error.as_ref()(ErrorCodes::VShowNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VShowNoExpression, dir.span);
     return None;
   };
 
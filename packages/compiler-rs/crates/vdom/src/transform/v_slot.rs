@@ -2,12 +2,13 @@ use std::collections::HashSet;
 
 use common::{
   check::{is_simple_identifier, is_template},
+  diagnostic::Diagnostic,
   directive::{Directives, find_prop, resolve_directive},
+  either::Either,
   error::ErrorCodes,
   options::SlotScope,
   patch_flag::SlotFlags,
 };
-use napi::Either;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -56,7 +57,7 @@ pub unsafe fn track_slot_scopes<'a>(
       if let Some(v_slot) = directives.v_slot.as_ref() {
         let mut identifiers = vec![];
         if let Some(JSXAttributeValue::ExpressionContainer(slot_props)) = v_slot.value.as_ref() {
-          identifiers = context.add_identifiers(&Some(slot_props.expression.to_expression()));
+          identifiers = context.add_identifiers(&slot_props.expression.as_expression());
         }
         if is_component {
           context.options.slot_scopes.borrow_mut().insert(
@@ -192,7 +193,12 @@ pub fn build_slots<'a>(
 
     if let Some(on_component_slot_span) = on_component_slot_span {
       // already has on-component slot - this is incorrect usage.
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMixedSlotUsage, on_component_slot_span);
+      context.options.report(
+        Diagnostic::error(ErrorCodes::VSlotMixedSlotUsage, on_component_slot_span).with_related(
+          slot_dir.as_ref().unwrap().span,
+          "nested <template> slot found here",
+        ),
+      );
       break;
     }
 
@@ -319,7 +325,9 @@ pub fn build_slots<'a>(
           )
         };
       } else {
-        context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, v_else.span);
+        context
+          .options
+          .error(ErrorCodes::VElseNoAdjacentIf, v_else.span);
       }
     } else if let Some(v_for) = slot_directives.v_for.as_mut() {
       has_dynamic_slots = true;
@@ -370,7 +378,9 @@ pub fn build_slots<'a>(
       // check duplicate static names
       if let Some(static_slot_name) = static_slot_name {
         if seen_slot_names.contains(static_slot_name) {
-          context.options.on_error.as_ref()(ErrorCodes::VSlotDuplicateSlotNames, dir_loc);
+          context
+            .options
+            .error(ErrorCodes::VSlotDuplicateSlotNames, dir_loc);
           continue;
         }
         seen_slot_names.insert(static_slot_name);
@@ -510,7 +520,7 @@ pub fn build_slots<'a>(
   } else if !implicit_default_children.is_empty() {
     // implicit default slot (mixed with named slots)
     if has_named_default_slot {
-      context.options.on_error.as_ref()(
+      context.options.error(
         ErrorCodes::VSlotExtraneousDefaultSlotChildren,
         implicit_default_children[0].span(),
       )
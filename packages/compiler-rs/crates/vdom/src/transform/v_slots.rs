@@ -1,4 +1,3 @@
-use napi::bindgen_prelude::Either3;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -15,8 +14,8 @@ use crate::{
   transform::{TransformContext, utils::is_dynamic_slots_component},
 };
 use common::{
-  check::is_slots_expression, directive::Directives, error::ErrorCodes, options::SlotScope,
-  patch_flag::PatchFlags, text::is_empty_text,
+  check::is_slots_expression, directive::Directives, either::Either3, error::ErrorCodes,
+  options::SlotScope, patch_flag::PatchFlags, text::is_empty_text,
 };
 
 /// # SAFETY
@@ -89,7 +88,7 @@ pub unsafe fn transform_v_slots<'a>(
 
   if let Some(dir) = directives.v_slots.as_mut() {
     if !is_component {
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMisplaced, node_span);
+      context.options.error(ErrorCodes::VSlotMisplaced, node_span);
       return None;
     }
 
@@ -238,7 +237,9 @@ pub unsafe fn transform_v_slots<'a>(
         *context.options.in_v_slot.borrow_mut() -= 1;
       }))
     } else {
-      context.options.on_error.as_ref()(ErrorCodes::VSlotsNoExpression, dir.span);
+      context
+        .options
+        .error(ErrorCodes::VSlotsNoExpression, dir.span);
       None
     }
   } else {
@@ -1,5 +1,4 @@
-use common::{check::is_void_tag, error::ErrorCodes, text::is_empty_text};
-use napi::Either;
+use common::{check::is_void_tag, either::Either, error::ErrorCodes, text::is_empty_text};
 use oxc_ast::{
   NONE,
   ast::{JSXAttribute, JSXAttributeValue, JSXElement, PropertyKind},
@@ -27,12 +26,16 @@ pub fn transform_v_text<'a>(
       context.jsx_attribute_value_to_expression(value)
     }
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VTextNoExpression, dir.span);
+    context
+      .options
+      .error(ErrorCodes::VTextNoExpression, dir.span);
     return None;
   };
 
   if node.children.iter().any(|c| !is_empty_text(c)) {
-    context.options.on_error.as_ref()(ErrorCodes::VTextWithChildren, node.span);
+    context
+      .options
+      .error(ErrorCodes::VTextWithChildren, node.span);
     return None;
   };
 
@@ -0,0 +1,13 @@
+#![no_main]
+
+use compiler_rs_fuzz::check_transform;
+use libfuzzer_sys::fuzz_target;
+
+// The first two bytes pick the options, the rest is the source.
+fuzz_target!(|data: &[u8]| {
+  if let Some((&[low, high], source)) = data.split_first_chunk()
+    && let Ok(source) = std::str::from_utf8(source)
+  {
+    check_transform(source, u16::from_le_bytes([low, high]));
+  }
+});
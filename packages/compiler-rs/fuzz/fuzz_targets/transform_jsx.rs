@@ -0,0 +1,9 @@
+#![no_main]
+
+use compiler_rs_fuzz::{Node, check_transform};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u16, Node)| {
+  let (flags, node) = input;
+  check_transform(&node.to_source(), flags);
+});
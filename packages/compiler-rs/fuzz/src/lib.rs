@@ -0,0 +1,483 @@
+use arbitrary::Arbitrary;
+use common::{
+  either::Either,
+  options::{DiagnosticLevel, Whitespace},
+};
+use compiler_rs::{TransformOptions, transform};
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+// deeper trees only make the runs slower
+const MAX_DEPTH: usize = 12;
+
+/// Every bit of `flags` toggles one option, so the fuzzer walks through all of
+/// their combinations.
+pub fn options<'a>(flags: u16) -> TransformOptions<'a> {
+  let bit = |index: u16| flags & (1 << index) != 0;
+  TransformOptions {
+    filename: "index.tsx",
+    interop: bit(0),
+    ssr: bit(1),
+    hmr: Either::A(bit(2)),
+    optimize: !bit(3),
+    merge_props: !bit(4),
+    source_map: bit(5),
+    whitespace: match (bit(6), bit(7)) {
+      (false, false) => Whitespace::Condense,
+      (true, false) => Whitespace::Preserve,
+      _ => Whitespace::JsxReact,
+    },
+    html_nesting: if bit(8) {
+      DiagnosticLevel::Error
+    } else {
+      DiagnosticLevel::Warn
+    },
+    ..Default::default()
+  }
+}
+
+/// Transforms `source` and, when it was valid and compiled without errors,
+/// checks that the output parses.
+pub fn check_transform(source: &str, flags: u16) {
+  let allocator = Allocator::default();
+  let is_valid = Parser::new(&allocator, source, SourceType::tsx())
+    .parse()
+    .errors
+    .is_empty();
+  let output = transform(source, Some(options(flags)));
+  if !is_valid
+    || output
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.is_error())
+  {
+    return;
+  }
+  let allocator = Allocator::default();
+  let errors = Parser::new(&allocator, &output.code, SourceType::tsx())
+    .parse()
+    .errors;
+  assert!(
+    errors.is_empty(),
+    "invalid output for options {flags:#011b}\n--- source\n{source}\n--- output\n{}\n--- errors\n{errors:?}",
+    output.code
+  );
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Node {
+  Element {
+    tag: Tag,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+  },
+  Fragment(Vec<Node>),
+  Text(Text),
+  Expression(Expression),
+  Comment,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Tag {
+  Div,
+  Span,
+  P,
+  Table,
+  Tr,
+  Input,
+  Textarea,
+  Select,
+  Pre,
+  Template,
+  Slot,
+  Svg,
+  Circle,
+  Component,
+  MemberComponent,
+  CustomElement,
+  DynamicComponent,
+  Teleport,
+  KeepAlive,
+  Suspense,
+  Transition,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Attribute {
+  Static(Name),
+  Dynamic(Name, Expression),
+  Boolean(Name),
+  Spread(Expression),
+  If(Expression),
+  ElseIf(Expression),
+  Else,
+  For(Expression),
+  Show(Expression),
+  Html(Expression),
+  Text(Expression),
+  Once,
+  Memo(Vec<Expression>),
+  Model(Option<Name>, Vec<Modifier>, Ident),
+  Slot(Option<Name>, bool),
+  Slots(Expression),
+  On(Expression),
+  Custom(Expression),
+  Key(Expression),
+  Ref(Ident),
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Name {
+  Id,
+  Class,
+  Style,
+  Value,
+  Checked,
+  Disabled,
+  Title,
+  OnClick,
+  OnClickStop,
+  To,
+  Is,
+  Name,
+  InnerHTML,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Modifier {
+  Trim,
+  Number,
+  Lazy,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Text {
+  Word,
+  Spaces,
+  Newline,
+  Entity,
+  Mixed,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Ident {
+  Foo,
+  Bar,
+  Item,
+  Index,
+  Props,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Expression {
+  Ident(Ident),
+  Member(Ident, Ident),
+  Call(Ident, Vec<Expression>),
+  Null,
+  Bool(bool),
+  Number(u8),
+  String(Text),
+  Template(Vec<Expression>),
+  Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+  Logical(Box<Expression>, Box<Expression>),
+  Array(Vec<Expression>),
+  Object(Vec<(Name, Expression)>),
+  Arrow(Box<Expression>),
+  Jsx(Box<Node>),
+  Map(Ident, Box<Node>),
+}
+
+impl Node {
+  pub fn to_source(&self) -> String {
+    let mut source = String::new();
+    self.print(&mut source, 0);
+    source
+  }
+
+  fn print(&self, out: &mut String, depth: usize) {
+    match self {
+      Node::Element {
+        tag,
+        attributes,
+        children,
+      } => {
+        let tag = tag.as_str();
+        out.push('<');
+        out.push_str(tag);
+        for attribute in attributes {
+          out.push(' ');
+          attribute.print(out, depth + 1);
+        }
+        if children.is_empty() || depth >= MAX_DEPTH {
+          out.push_str(" />");
+        } else {
+          out.push('>');
+          for child in children {
+            child.print_child(out, depth + 1);
+          }
+          out.push_str(&format!("</{tag}>"));
+        }
+      }
+      Node::Fragment(children) => {
+        out.push_str("<>");
+        if depth < MAX_DEPTH {
+          for child in children {
+            child.print_child(out, depth + 1);
+          }
+        }
+        out.push_str("</>");
+      }
+      // outside of children, text and expressions are wrapped into a fragment
+      node => {
+        out.push_str("<>");
+        node.print_child(out, depth + 1);
+        out.push_str("</>");
+      }
+    }
+  }
+
+  fn print_child(&self, out: &mut String, depth: usize) {
+    match self {
+      Node::Text(text) => out.push_str(text.as_str()),
+      Node::Expression(expression) => {
+        out.push('{');
+        expression.print(out, depth);
+        out.push('}');
+      }
+      Node::Comment => out.push_str("{/* comment */}"),
+      node => node.print(out, depth),
+    }
+  }
+}
+
+impl Tag {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Tag::Div => "div",
+      Tag::Span => "span",
+      Tag::P => "p",
+      Tag::Table => "table",
+      Tag::Tr => "tr",
+      Tag::Input => "input",
+      Tag::Textarea => "textarea",
+      Tag::Select => "select",
+      Tag::Pre => "pre",
+      Tag::Template => "template",
+      Tag::Slot => "slot",
+      Tag::Svg => "svg",
+      Tag::Circle => "circle",
+      Tag::Component => "Comp",
+      Tag::MemberComponent => "Foo.Bar",
+      Tag::CustomElement => "ion-button",
+      Tag::DynamicComponent => "component",
+      Tag::Teleport => "Teleport",
+      Tag::KeepAlive => "KeepAlive",
+      Tag::Suspense => "Suspense",
+      Tag::Transition => "Transition",
+    }
+  }
+}
+
+impl Attribute {
+  fn print(&self, out: &mut String, depth: usize) {
+    let directive = |out: &mut String, name: &str, expression: &Expression| {
+      out.push_str(name);
+      out.push_str("={");
+      expression.print(out, depth);
+      out.push('}');
+    };
+    match self {
+      Attribute::Static(name) => {
+        out.push_str(name.as_str());
+        out.push_str("=\"foo\"");
+      }
+      Attribute::Dynamic(name, expression) => directive(out, name.as_str(), expression),
+      Attribute::Boolean(name) => out.push_str(name.as_str()),
+      Attribute::Spread(expression) => {
+        out.push_str("{...");
+        expression.print(out, depth);
+        out.push('}');
+      }
+      Attribute::If(expression) => directive(out, "v-if", expression),
+      Attribute::ElseIf(expression) => directive(out, "v-else-if", expression),
+      Attribute::Else => out.push_str("v-else"),
+      Attribute::For(expression) => {
+        out.push_str("v-for={(item, index) in ");
+        expression.print(out, depth);
+        out.push('}');
+      }
+      Attribute::Show(expression) => directive(out, "v-show", expression),
+      Attribute::Html(expression) => directive(out, "v-html", expression),
+      Attribute::Text(expression) => directive(out, "v-text", expression),
+      Attribute::Once => out.push_str("v-once"),
+      Attribute::Memo(expressions) => {
+        out.push_str("v-memo={[");
+        for expression in expressions {
+          expression.print(out, depth);
+          out.push_str(", ");
+        }
+        out.push_str("]}");
+      }
+      Attribute::Model(name, modifiers, ident) => {
+        out.push_str("v-model");
+        if let Some(name) = name {
+          out.push(':');
+          out.push_str(name.as_str());
+        }
+        for modifier in modifiers {
+          out.push_str(match modifier {
+            Modifier::Trim => "_trim",
+            Modifier::Number => "_number",
+            Modifier::Lazy => "_lazy",
+          });
+        }
+        out.push_str(&format!("={{{}}}", ident.as_str()));
+      }
+      Attribute::Slot(name, has_props) => {
+        out.push_str("v-slot");
+        if let Some(name) = name {
+          out.push(':');
+          out.push_str(name.as_str());
+        }
+        if *has_props {
+          out.push_str("={{ foo }}");
+        }
+      }
+      Attribute::Slots(expression) => directive(out, "v-slots", expression),
+      Attribute::On(expression) => directive(out, "v-on", expression),
+      Attribute::Custom(expression) => directive(out, "v-focus", expression),
+      Attribute::Key(expression) => directive(out, "key", expression),
+      Attribute::Ref(ident) => out.push_str(&format!("ref={{{}}}", ident.as_str())),
+    }
+  }
+}
+
+impl Name {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Name::Id => "id",
+      Name::Class => "class",
+      Name::Style => "style",
+      Name::Value => "value",
+      Name::Checked => "checked",
+      Name::Disabled => "disabled",
+      Name::Title => "title",
+      Name::OnClick => "onClick",
+      Name::OnClickStop => "onClick_stop",
+      Name::To => "to",
+      Name::Is => "is",
+      Name::Name => "name",
+      Name::InnerHTML => "innerHTML",
+    }
+  }
+}
+
+impl Text {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Text::Word => "foo",
+      Text::Spaces => "  ",
+      Text::Newline => "\n  ",
+      Text::Entity => "&amp;&nbsp;&lt;",
+      Text::Mixed => " a  b\n  c ",
+    }
+  }
+}
+
+impl Ident {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Ident::Foo => "foo",
+      Ident::Bar => "bar",
+      Ident::Item => "item",
+      Ident::Index => "index",
+      Ident::Props => "props",
+    }
+  }
+}
+
+impl Expression {
+  fn print(&self, out: &mut String, depth: usize) {
+    // stop growing once the tree is deep enough
+    if depth >= MAX_DEPTH {
+      out.push_str("foo");
+      return;
+    }
+    let depth = depth + 1;
+    let list = |out: &mut String, expressions: &[Expression]| {
+      for (index, expression) in expressions.iter().enumerate() {
+        if index > 0 {
+          out.push_str(", ");
+        }
+        expression.print(out, depth);
+      }
+    };
+    match self {
+      Expression::Ident(ident) => out.push_str(ident.as_str()),
+      Expression::Member(object, property) => {
+        out.push_str(&format!("{}.{}", object.as_str(), property.as_str()))
+      }
+      Expression::Call(callee, arguments) => {
+        out.push_str(callee.as_str());
+        out.push('(');
+        list(out, arguments);
+        out.push(')');
+      }
+      Expression::Null => out.push_str("null"),
+      Expression::Bool(value) => out.push_str(&value.to_string()),
+      Expression::Number(value) => out.push_str(&value.to_string()),
+      Expression::String(text) => out.push_str(&format!("{:?}", text.as_str())),
+      Expression::Template(expressions) => {
+        out.push('`');
+        for expression in expressions {
+          out.push_str("a${");
+          expression.print(out, depth);
+          out.push('}');
+        }
+        out.push('`');
+      }
+      Expression::Conditional(test, consequent, alternate) => {
+        out.push('(');
+        test.print(out, depth);
+        out.push_str(" ? ");
+        consequent.print(out, depth);
+        out.push_str(" : ");
+        alternate.print(out, depth);
+        out.push(')');
+      }
+      Expression::Logical(left, right) => {
+        out.push('(');
+        left.print(out, depth);
+        out.push_str(" && ");
+        right.print(out, depth);
+        out.push(')');
+      }
+      Expression::Array(elements) => {
+        out.push('[');
+        list(out, elements);
+        out.push(']');
+      }
+      Expression::Object(properties) => {
+        out.push_str("({");
+        for (name, value) in properties {
+          out.push_str(&format!("{:?}: ", name.as_str()));
+          value.print(out, depth);
+          out.push_str(", ");
+        }
+        out.push_str("})");
+      }
+      Expression::Arrow(body) => {
+        out.push_str("(() => ");
+        body.print(out, depth);
+        out.push(')');
+      }
+      Expression::Jsx(node) => node.print(out, depth),
+      Expression::Map(ident, node) => {
+        out.push_str(&format!("{}.map((item, index) => ", ident.as_str()));
+        node.print(out, depth);
+        out.push(')');
+      }
+    }
+  }
+}
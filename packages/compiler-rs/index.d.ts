@@ -3,9 +3,12 @@
 export interface CompilerError extends SyntaxError {
   code: number
   loc?: [number, number]
+  severity: string
+  frame?: string
 }
 
 export declare const enum ErrorCodes {
+  SyntaxError = 0,
   VIfNoExpression = 28,
   VElseNoAdjacentIf = 30,
   VForNoExpression = 31,
@@ -28,7 +31,20 @@ export declare const enum ErrorCodes {
   VModelOnFileInputElement = 59,
   VModelUnnecessaryValue = 60,
   VShowNoExpression = 61,
-  VSlotsNoExpression = 62
+  VSlotsNoExpression = 62,
+  TeleportMissingTarget = 63,
+  TeleportInvalidTarget = 64,
+  SuspenseInvalidSlot = 65,
+  SuspenseInvalidChildren = 66,
+  InvalidHtmlNesting = 67,
+  DirectiveTransformFailed = 68
+}
+
+export declare const enum WarningCodes {
+  VOnDelegateOnComponent = 100,
+  VOnDelegateUnsupportedEvent = 101,
+  TeleportDisabledString = 102,
+  InvalidHtmlNesting = 103
 }
 
 export interface Hmr {
@@ -38,6 +54,10 @@ export interface Hmr {
    */
   defineComponentName: Array<string>
 }
+export type DiagnosticLevel = 'off'|
+'warn'|
+'error';
+
 export interface CompilerOptions {
   onError?: (arg: object) => void
   onWarn?: (arg: object) => void
@@ -95,11 +115,194 @@ export interface CompilerOptions {
    * @default true
    */
   mergeProps?: boolean
+  /**
+   * Return the Vapor IR of every root as JSON in `TransformReturn.ir`.
+   * @default false
+   */
+  dumpIr?: boolean
+  /**
+   * Compile-time transforms for custom directives, keyed by the directive
+   * name without the `v-` prefix.
+   */
+  directiveTransforms?: Record<string, (arg: DirectiveBinding) => DirectiveTransformReturn>
+  /**
+   * Return the templates of the file in `TransformReturn.templates`.
+   * @default false
+   */
+  reportTemplates?: boolean
+  /**
+   * Import templates by their hash from this module instead of declaring
+   * them in every file. The module is created with `sharedTemplateModule`.
+   */
+  sharedTemplates?: string
+  /**
+   * How to report elements the browser moves out of their parent, like a
+   * `<div>` in a `<p>`.
+   * @default 'warn'
+   */
+  htmlNesting?: DiagnosticLevel
+  /**
+   * How whitespace in JSX text is handled. `<pre>` and `<textarea>` always
+   * keep their text as written.
+   * @default 'condense'
+   */
+  whitespace?: Whitespace
+  /**
+   * Render children whose TypeScript type is `string` or `number` with
+   * `setText` instead of `setNodes` in Vapor. Reads type annotations of
+   * bindings, props and `ref<T>()`/`computed<T>()`.
+   * @default false
+   */
+  typeAwareText?: boolean
+  /**
+   * How Vapor writes DOM properties known for a native tag, like
+   * `input.checked`, `textarea.value` or `video.muted`.
+   * - `'compact'`: through runtime helpers, for smaller bundles
+   * - `'inline'`: as guarded assignments, for faster updates
+   * @default 'compact'
+   */
+  domPropWrites?: DomPropWrites
+  /**
+   * Compile event modifiers like `onClick_stop` or `onKeyup_enter` to
+   * guards at the start of the handler, e.g. `if (_e.key !== "Enter")
+   * return`, instead of wrapping it with `withModifiers` and `withKeys`.
+   * Only for listeners bound once, i.e. cached handlers in vdom and native
+   * elements in Vapor, whose handler is an identifier, a member expression
+   * or an arrow function. An arrow function's body is inlined, and a member
+   * expression is called without binding `this`.
+   * @default false
+   */
+  inlineModifiers?: boolean
+  /**
+   * Tags created as custom elements besides the ones with a dash, given as
+   * names where `*` matches any characters (e.g. `ion-*`) or as a function.
+   * Their props are set as DOM properties when possible.
+   */
+  isCustomElement?: Array<string> | ((arg: string) => boolean)
+  /**
+   * Native HTML, SVG and MathML tags. When set, other lowercase tags are
+   * created as custom elements.
+   */
+  isNativeTag?: Array<string> | ((arg: string) => boolean)
+}
+
+export interface DirectiveBinding {
+  /** Directive name without the `v-` prefix */
+  name: string
+  /** The static argument, or the code of a dynamic one */
+  arg?: string
+  isStaticArg: boolean
+  /** The code of the directive value */
+  exp?: string
+  modifiers: Array<string>
+  tag: string
+  isComponent: boolean
+  loc: [number, number]
+}
+
+export interface DirectiveProp {
+  name: string
+  /** JavaScript expression of the prop value */
+  value: string
+}
+
+export interface DirectiveTransformReturn {
+  /** Props added to the element, `onXxx` props become event listeners */
+  props?: Array<DirectiveProp>
+  /**
+   * Whether the runtime directive should still be applied
+   * @default false
+   */
+  needRuntime?: boolean
+}
+
+/** Creates the module imported by files compiled with `sharedTemplates`. */
+export declare function sharedTemplateModule(templates: Array<TemplateReturn>): string
+
+export interface TemplateReturn {
+  content: string
+  /** 0 for HTML, 1 for SVG and 2 for MathML */
+  ns: number
+  root: boolean
+  static: boolean
+  /** Identifies the template across files */
+  hash: string
 }
 
 export declare function transform(source: string, options?: CompilerOptions | undefined | null): TransformReturn
 
+export interface DiagnosticFix {
+  message: string
+  start: number
+  end: number
+  replacement: string
+}
+
+export interface DiagnosticLabel {
+  start: number
+  end: number
+  /** 1-based */
+  line: number
+  /** 1-based */
+  column: number
+  message?: string
+}
+
+export interface DiagnosticReturn {
+  /** `error` or `warning` */
+  severity: string
+  /** Stable identifier, e.g. `E28` or `W100` */
+  code: string
+  message: string
+  label: DiagnosticLabel
+  related: Array<DiagnosticLabel>
+  fix?: DiagnosticFix
+  /** The diagnostic rendered as a code frame */
+  frame: string
+}
+
 export interface TransformReturn {
   code: string
   map?: string
+  diagnostics: Array<DiagnosticReturn>
+  /** The Vapor IR as a JSON string, when `dumpIr` is enabled */
+  ir?: string
+  /** The templates of the file, when `reportTemplates` is enabled */
+  templates?: Array<TemplateReturn>
+}
+
+export interface CompileFile {
+  filename: string
+  source: string
+}
+
+/**
+ * Compiles the files on a thread pool without blocking the event loop.
+ * Callbacks can't run off the main thread, so `onError`, `onWarn`,
+ * `directiveTransforms` and function tag matchers are not supported.
+ */
+export declare function compileMany(files: Array<CompileFile>, options?: CompilerOptions | undefined | null): Promise<Array<TransformReturn>>
+
+/**
+ * Translates `v-if`, `v-for`, `v-slot` and `v-model` into plain TSX for type
+ * checking. Offsets are in UTF-16 code units, like JavaScript strings.
+ */
+export declare function virtualCode(source: string, filename?: string | undefined | null): VirtualCodeReturn
+
+export interface VirtualCodeMapping {
+  sourceOffset: number
+  generatedOffset: number
+  length: number
 }
+
+export interface VirtualCodeReturn {
+  code: string
+  mappings: Array<VirtualCodeMapping>
+}
+
+export type Whitespace = 'condense'|
+'preserve'|
+'jsx-react';
+
+export type DomPropWrites = 'compact'|
+'inline';
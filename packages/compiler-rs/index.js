@@ -700,5 +700,9 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.compileMany = nativeBinding.compileMany
 module.exports.ErrorCodes = nativeBinding.ErrorCodes
+module.exports.sharedTemplateModule = nativeBinding.sharedTemplateModule
 module.exports.transform = nativeBinding.transform
+module.exports.virtualCode = nativeBinding.virtualCode
+module.exports.WarningCodes = nativeBinding.WarningCodes
@@ -0,0 +1,601 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use common::{
+  diagnostic::{Diagnostic, Label, line_column},
+  directive::{
+    CustomDirectiveResult, DirectiveNode, DirectiveTransform, DirectiveTransformContext,
+  },
+  error::create_compiler_error,
+  expression::parse_expression,
+  options::{
+    DiagnosticLevel, DomPropWrites, Hmr, TagMatcher, Template, TransformOptions, Whitespace,
+  },
+};
+use napi::{
+  Either, Env, Error, Result, Status, Task,
+  bindgen_prelude::{AsyncTask, Function, Object},
+};
+use napi_derive::napi;
+use oxc_ast::ast::Expression;
+use oxc_codegen::Codegen;
+use oxc_span::{SPAN, SourceType};
+
+use crate::{Compiler, TransformOutput, try_transform};
+
+#[napi(object)]
+#[derive(Default)]
+pub struct CompilerOptions {
+  pub on_error: Option<Function<'static, Object<'static>, ()>>,
+  pub on_warn: Option<Function<'static, Object<'static>, ()>>,
+  /// Generate source map?
+  /// @default false
+  pub source_map: Option<bool>,
+  /// Filename for source map generation.
+  /// Also used for self-recursive reference in templates
+  /// @default 'index.jsx'
+  pub filename: Option<String>,
+  /// When enabled, JSX within `defineVaporComponent` is transformed to Vapor DOM,
+  /// while all other JSX is transformed to Virtual DOM.
+  pub interop: Option<bool>,
+  /// Enabled HMR support.
+  /// - `true`/`false`: a boolean to simply enable/disable HMR. When `true`, HMR
+  ///   is enabled with default configuration.
+  /// - `Hmr`: an object to enable HMR with custom configuration.
+  /// @default false
+  pub hmr: Option<Either<bool, Hmr>>,
+  /// Enabled SSR support.
+  /// @default false
+  pub ssr: Option<bool>,
+  /// Whether to enable compiler optimizations, including:
+  /// - **Slots**: Detect if slots are stable for more efficient updates.
+  /// - **Cache**: Cache event handler to avoid recreating closures on each render.
+  /// - **Block**: Enable block tree optimizations.
+  ///
+  /// Note: this option is only used in interop mode.
+  /// @default true
+  pub optimize: Option<bool>,
+  /// Customize where to import runtime helpers from vue-jsx-vapor.
+  /// If not specified, defaults to the virtual module path (e.g., `/vue-jsx-vapor/vapor`).
+  pub runtime_module_name: Option<String>,
+  /// Whether to merge props when using JSXSpreadAttribute.
+  /// - `true`: Use Vue's `mergeProps` behavior
+  ///   - Event listeners (`onXxx`) are merged into an array
+  ///   - `class` and `style` are normalized and merged
+  ///   - Other props are overridden by later values
+  /// - `false`: later props override earlier ones (like object spread)
+  /// @default true
+  pub merge_props: Option<bool>,
+  /// Return the Vapor IR of every root as JSON in `TransformReturn.ir`.
+  /// @default false
+  pub dump_ir: Option<bool>,
+  /// Compile-time transforms for custom directives, keyed by the directive
+  /// name without the `v-` prefix.
+  pub directive_transforms:
+    Option<HashMap<String, Function<'static, DirectiveBinding, DirectiveTransformReturn>>>,
+  /// Return the templates of the file in `TransformReturn.templates`.
+  /// @default false
+  pub report_templates: Option<bool>,
+  /// Import templates by their hash from this module instead of declaring
+  /// them in every file. The module is created with `sharedTemplateModule`.
+  pub shared_templates: Option<String>,
+  /// How to report elements the browser moves out of their parent, like a
+  /// `<div>` in a `<p>`.
+  /// @default 'warn'
+  pub html_nesting: Option<DiagnosticLevel>,
+  /// How whitespace in JSX text is handled. `<pre>` and `<textarea>` always
+  /// keep their text as written.
+  /// @default 'condense'
+  pub whitespace: Option<Whitespace>,
+  /// Render children whose TypeScript type is `string` or `number` with
+  /// `setText` instead of `setNodes` in Vapor. Reads type annotations of
+  /// bindings, props and `ref<T>()`/`computed<T>()`.
+  /// @default false
+  pub type_aware_text: Option<bool>,
+  /// How Vapor writes DOM properties known for a native tag, like
+  /// `input.checked`, `textarea.value` or `video.muted`.
+  /// - `'compact'`: through runtime helpers, for smaller bundles
+  /// - `'inline'`: as guarded assignments, for faster updates
+  /// @default 'compact'
+  pub dom_prop_writes: Option<DomPropWrites>,
+  /// Compile event modifiers like `onClick_stop` or `onKeyup_enter` to
+  /// guards at the start of the handler, e.g. `if (_e.key !== "Enter")
+  /// return`, instead of wrapping it with `withModifiers` and `withKeys`.
+  /// Only for listeners bound once, i.e. cached handlers in vdom and native
+  /// elements in Vapor, whose handler is an identifier, a member expression
+  /// or an arrow function. An arrow function's body is inlined, and a member
+  /// expression is called without binding `this`.
+  /// @default false
+  pub inline_modifiers: Option<bool>,
+  /// Tags created as custom elements besides the ones with a dash, given as
+  /// names where `*` matches any characters (e.g. `ion-*`) or as a function.
+  /// Their props are set as DOM properties when possible.
+  pub is_custom_element: Option<Either<Vec<String>, Function<'static, String, bool>>>,
+  /// Native HTML, SVG and MathML tags. When set, other lowercase tags are
+  /// created as custom elements.
+  pub is_native_tag: Option<Either<Vec<String>, Function<'static, String, bool>>>,
+}
+
+// The first exception thrown by a function matcher is kept in `error` and
+// rethrown once the transform is done, later calls are skipped.
+fn create_tag_matcher(
+  matcher: Either<Vec<String>, Function<'static, String, bool>>,
+  error: &Rc<RefCell<Option<Error>>>,
+) -> TagMatcher<'static> {
+  match matcher {
+    Either::A(patterns) => TagMatcher::Patterns(patterns),
+    Either::B(function) => {
+      let error = error.clone();
+      TagMatcher::Predicate(Box::new(move |tag| {
+        if error.borrow().is_some() {
+          return false;
+        }
+        function.call(tag.to_string()).unwrap_or_else(|reason| {
+          *error.borrow_mut() = Some(reason);
+          false
+        })
+      }))
+    }
+  }
+}
+
+#[napi(object)]
+pub struct DirectiveBinding {
+  /// Directive name without the `v-` prefix
+  pub name: String,
+  /// The static argument, or the code of a dynamic one
+  pub arg: Option<String>,
+  pub is_static_arg: bool,
+  /// The code of the directive value
+  pub exp: Option<String>,
+  pub modifiers: Vec<String>,
+  pub tag: String,
+  pub is_component: bool,
+  pub loc: (u32, u32),
+}
+
+#[napi(object)]
+pub struct DirectiveProp {
+  pub name: String,
+  /// JavaScript expression of the prop value
+  pub value: String,
+}
+
+#[napi(object)]
+pub struct DirectiveTransformReturn {
+  /// Props added to the element, `onXxx` props become event listeners
+  pub props: Option<Vec<DirectiveProp>>,
+  /// Whether the runtime directive should still be applied
+  /// @default false
+  pub need_runtime: Option<bool>,
+}
+
+struct JsDirectiveTransform {
+  name: String,
+  function: Function<'static, DirectiveBinding, DirectiveTransformReturn>,
+}
+
+impl DirectiveTransform for JsDirectiveTransform {
+  fn transform<'a>(
+    &self,
+    dir: &DirectiveNode<'a>,
+    context: &DirectiveTransformContext<'a>,
+  ) -> CustomDirectiveResult<'a> {
+    let print = |expression: &Expression| {
+      let mut codegen = Codegen::new();
+      codegen.print_expression(expression);
+      codegen.into_source_text()
+    };
+    let result = match self.function.call(DirectiveBinding {
+      name: self.name.clone(),
+      arg: dir.arg.as_ref().map(|arg| match arg {
+        Expression::StringLiteral(arg) => arg.value.to_string(),
+        arg => print(arg),
+      }),
+      is_static_arg: matches!(dir.arg, Some(Expression::StringLiteral(_))),
+      exp: dir.exp.as_ref().map(print),
+      modifiers: dir.modifiers.clone(),
+      tag: context.tag.to_string(),
+      is_component: context.is_component,
+      loc: (dir.span.start, dir.span.end),
+    }) {
+      Ok(result) => result,
+      // a throwing callback must not take the process down
+      Err(error) => {
+        return CustomDirectiveResult {
+          error: Some(format!(
+            "The transform of `v-{}` threw: {}",
+            self.name, error.reason
+          )),
+          ..Default::default()
+        };
+      }
+    };
+    let mut props = vec![];
+    for prop in result.props.unwrap_or_default() {
+      let Some(value) =
+        parse_expression(&prop.value, SPAN, context.ast.allocator, SourceType::tsx())
+      else {
+        // reported like a throwing callback, instead of leaving the prop out
+        return CustomDirectiveResult {
+          error: Some(format!(
+            "The transform of `v-{}` returned an invalid expression for `{}`: {}",
+            self.name, prop.name, prop.value
+          )),
+          ..Default::default()
+        };
+      };
+      props.push((prop.name, value));
+    }
+    CustomDirectiveResult {
+      props,
+      need_runtime: result.need_runtime.unwrap_or(false),
+      error: None,
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TransformReturn {
+  pub code: String,
+  pub map: Option<String>,
+  pub diagnostics: Vec<DiagnosticReturn>,
+  /// The Vapor IR as a JSON string, when `dumpIr` is enabled
+  pub ir: Option<String>,
+  /// The templates of the file, when `reportTemplates` is enabled
+  pub templates: Option<Vec<TemplateReturn>>,
+}
+
+impl TransformReturn {
+  fn new(output: TransformOutput, source: &str, filename: &str) -> Self {
+    TransformReturn {
+      code: output.code,
+      map: output.map,
+      diagnostics: output
+        .diagnostics
+        .iter()
+        .map(|diagnostic| DiagnosticReturn::new(diagnostic, source, filename))
+        .collect(),
+      ir: output.ir.map(|ir| ir.to_string()),
+      templates: output
+        .templates
+        .map(|templates| templates.into_iter().map(Into::into).collect()),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TemplateReturn {
+  pub content: String,
+  /// 0 for HTML, 1 for SVG and 2 for MathML
+  pub ns: i32,
+  pub root: bool,
+  #[napi(js_name = "static")]
+  pub _static: bool,
+  /// Identifies the template across files
+  pub hash: String,
+}
+
+impl From<Template> for TemplateReturn {
+  fn from(template: Template) -> Self {
+    TemplateReturn {
+      hash: template.hash(),
+      content: template.content,
+      ns: template.ns,
+      root: template.root,
+      _static: template._static,
+    }
+  }
+}
+
+/// Creates the module imported by files compiled with `sharedTemplates`.
+#[napi]
+pub fn shared_template_module(templates: Vec<TemplateReturn>) -> String {
+  let templates = templates
+    .into_iter()
+    .map(|template| Template {
+      content: template.content,
+      ns: template.ns,
+      root: template.root,
+      _static: template._static,
+      span: SPAN,
+    })
+    .collect::<Vec<_>>();
+  crate::shared_template_module(&templates)
+}
+
+#[napi(object)]
+pub struct VirtualCodeMapping {
+  pub source_offset: u32,
+  pub generated_offset: u32,
+  pub length: u32,
+}
+
+#[napi(object)]
+pub struct VirtualCodeReturn {
+  pub code: String,
+  pub mappings: Vec<VirtualCodeMapping>,
+}
+
+/// Translates `v-if`, `v-for`, `v-slot` and `v-model` into plain TSX for type
+/// checking. Offsets are in UTF-16 code units, like JavaScript strings.
+#[napi]
+pub fn virtual_code(source: String, filename: Option<String>) -> VirtualCodeReturn {
+  let filename = filename.unwrap_or("index.jsx".to_string());
+  let crate::VirtualCode { code, mappings } = crate::virtual_code(&source, &filename);
+  let source_offsets = utf16_offsets(&source);
+  let code_offsets = utf16_offsets(&code);
+  VirtualCodeReturn {
+    mappings: mappings
+      .iter()
+      .map(|mapping| {
+        let start = source_offsets[mapping.source.start as usize];
+        VirtualCodeMapping {
+          source_offset: start,
+          generated_offset: code_offsets[mapping.generated.start as usize],
+          length: source_offsets[mapping.source.end as usize] - start,
+        }
+      })
+      .collect(),
+    code,
+  }
+}
+
+// The UTF-16 offset of every byte offset of `text`.
+fn utf16_offsets(text: &str) -> Vec<u32> {
+  let mut offsets = Vec::with_capacity(text.len() + 1);
+  let mut offset = 0;
+  for c in text.chars() {
+    offsets.extend(std::iter::repeat_n(offset, c.len_utf8()));
+    offset += c.len_utf16() as u32;
+  }
+  offsets.push(offset);
+  offsets
+}
+
+#[napi(object)]
+pub struct DiagnosticLabel {
+  pub start: u32,
+  pub end: u32,
+  /// 1-based
+  pub line: u32,
+  /// 1-based
+  pub column: u32,
+  pub message: Option<String>,
+}
+
+#[napi(object)]
+pub struct DiagnosticFix {
+  pub message: String,
+  pub start: u32,
+  pub end: u32,
+  pub replacement: String,
+}
+
+#[napi(object)]
+pub struct DiagnosticReturn {
+  /// `error` or `warning`
+  pub severity: String,
+  /// Stable identifier, e.g. `E28` or `W100`
+  pub code: String,
+  pub message: String,
+  pub label: DiagnosticLabel,
+  pub related: Vec<DiagnosticLabel>,
+  pub fix: Option<DiagnosticFix>,
+  /// The diagnostic rendered as a code frame
+  pub frame: String,
+}
+
+impl DiagnosticReturn {
+  fn new(diagnostic: &Diagnostic, source: &str, filename: &str) -> Self {
+    let create_label = |label: &Label| {
+      let (line, column) = line_column(source, label.span.start);
+      DiagnosticLabel {
+        start: label.span.start,
+        end: label.span.end,
+        line: line as u32,
+        column: column as u32,
+        message: label.message.clone(),
+      }
+    };
+    DiagnosticReturn {
+      severity: diagnostic.severity.as_str().to_string(),
+      code: diagnostic.code.to_string(),
+      message: diagnostic.message.clone(),
+      label: create_label(&diagnostic.label),
+      related: diagnostic.related.iter().map(create_label).collect(),
+      fix: diagnostic.fix.as_ref().map(|fix| DiagnosticFix {
+        message: fix.message.clone(),
+        start: fix.span.start,
+        end: fix.span.end,
+        replacement: fix.replacement.clone(),
+      }),
+      frame: diagnostic.render(source, filename),
+    }
+  }
+}
+
+#[napi]
+pub fn _transform(
+  env: Env,
+  source: String,
+  options: Option<CompilerOptions>,
+) -> Result<TransformReturn> {
+  let options = options.unwrap_or_default();
+  let filename = &options.filename.unwrap_or("index.jsx".to_string());
+  let ssr = options.ssr.unwrap_or(false);
+  let matcher_error = Rc::new(RefCell::new(None));
+  let output = match try_transform(
+    &source,
+    Some(TransformOptions {
+      filename,
+      source_map: options.source_map.unwrap_or(false),
+      interop: options.interop.unwrap_or(false),
+      hmr: match options.hmr {
+        Some(Either::A(enabled)) => common::either::Either::A(enabled),
+        Some(Either::B(hmr)) => common::either::Either::B(hmr),
+        None => common::either::Either::A(false),
+      },
+      ssr,
+      optimize: options.optimize.unwrap_or(true),
+      runtime_module_name: options.runtime_module_name,
+      merge_props: options.merge_props.unwrap_or(true),
+      dump_ir: options.dump_ir.unwrap_or(false),
+      report_templates: options.report_templates.unwrap_or(false),
+      shared_templates: options.shared_templates,
+      html_nesting: options.html_nesting.unwrap_or_default(),
+      whitespace: options.whitespace.unwrap_or_default(),
+      type_aware_text: options.type_aware_text.unwrap_or(false),
+      dom_prop_writes: options.dom_prop_writes.unwrap_or_default(),
+      inline_modifiers: options.inline_modifiers.unwrap_or(false),
+      custom_element: options
+        .is_custom_element
+        .map(|matcher| create_tag_matcher(matcher, &matcher_error)),
+      native_tag: options
+        .is_native_tag
+        .map(|matcher| create_tag_matcher(matcher, &matcher_error)),
+      directive_transforms: options
+        .directive_transforms
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, function)| {
+          let transform = JsDirectiveTransform {
+            name: name.clone(),
+            function,
+          };
+          (name, Box::new(transform) as Box<dyn DirectiveTransform>)
+        })
+        .collect(),
+      ..Default::default()
+    }),
+  ) {
+    Ok(output) => output,
+    // throw the first error, with the frames of all of them
+    Err(errors) => {
+      let frame = errors
+        .iter()
+        .map(|error| error.render(&source, filename))
+        .collect::<Vec<_>>()
+        .join("\n");
+      env.throw(create_compiler_error(&env, &errors[0], Some(frame))?)?;
+      return Err(Error::new(
+        Status::PendingException,
+        errors[0].message.clone(),
+      ));
+    }
+  };
+  if let Some(error) = matcher_error.take() {
+    return Err(error);
+  }
+  for diagnostic in &output.diagnostics {
+    let callback = if diagnostic.is_error() {
+      &options.on_error
+    } else {
+      &options.on_warn
+    };
+    if let Some(callback) = callback {
+      let frame = diagnostic.render(&source, filename);
+      let compiler_error = create_compiler_error(&env, diagnostic, Some(frame))?;
+      callback.call(compiler_error)?;
+    }
+  }
+  Ok(TransformReturn::new(output, &source, filename))
+}
+
+#[napi(object)]
+pub struct CompileFile {
+  pub filename: String,
+  pub source: String,
+}
+
+pub struct CompileTask {
+  compiler: Compiler,
+  files: Vec<CompileFile>,
+}
+
+impl Task for CompileTask {
+  type Output = Vec<std::result::Result<TransformOutput, Vec<Diagnostic>>>;
+  type JsValue = Vec<TransformReturn>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let files = self
+      .files
+      .iter()
+      .map(|file| (&file.filename, &file.source))
+      .collect::<Vec<_>>();
+    Ok(self.compiler.compile_many(&files))
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(
+      self
+        .files
+        .iter()
+        .zip(output)
+        .map(|(file, result)| match result {
+          Ok(output) => TransformReturn::new(output, &file.source, &file.filename),
+          // a file that doesn't parse has no code, only its errors
+          Err(errors) => TransformReturn {
+            code: String::new(),
+            map: None,
+            diagnostics: errors
+              .iter()
+              .map(|error| DiagnosticReturn::new(error, &file.source, &file.filename))
+              .collect(),
+            ir: None,
+            templates: None,
+          },
+        })
+        .collect(),
+    )
+  }
+}
+
+/// Compiles the files on a thread pool without blocking the event loop.
+/// Callbacks can't run off the main thread, so `onError`, `onWarn`,
+/// `directiveTransforms` and function tag matchers are not supported.
+#[napi]
+pub fn compile_many(
+  files: Vec<CompileFile>,
+  options: Option<CompilerOptions>,
+) -> Result<AsyncTask<CompileTask>> {
+  let options = options.unwrap_or_default();
+  if options.on_error.is_some()
+    || options.on_warn.is_some()
+    || options.directive_transforms.is_some()
+    || matches!(options.is_custom_element, Some(Either::B(_)))
+    || matches!(options.is_native_tag, Some(Either::B(_)))
+  {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "compileMany doesn't support callback options",
+    ));
+  }
+  let patterns = |matcher: Option<Either<Vec<String>, _>>| match matcher {
+    Some(Either::A(patterns)) => Some(patterns),
+    _ => None,
+  };
+  let compiler = Compiler {
+    source_map: options.source_map.unwrap_or(false),
+    interop: options.interop.unwrap_or(false),
+    hmr: match options.hmr {
+      Some(Either::A(enabled)) => common::either::Either::A(enabled),
+      Some(Either::B(hmr)) => common::either::Either::B(hmr),
+      None => common::either::Either::A(false),
+    },
+    ssr: options.ssr.unwrap_or(false),
+    optimize: options.optimize.unwrap_or(true),
+    runtime_module_name: options.runtime_module_name,
+    merge_props: options.merge_props.unwrap_or(true),
+    dump_ir: options.dump_ir.unwrap_or(false),
+    report_templates: options.report_templates.unwrap_or(false),
+    shared_templates: options.shared_templates,
+    html_nesting: options.html_nesting.unwrap_or_default(),
+    whitespace: options.whitespace.unwrap_or_default(),
+    type_aware_text: options.type_aware_text.unwrap_or(false),
+    dom_prop_writes: options.dom_prop_writes.unwrap_or_default(),
+    inline_modifiers: options.inline_modifiers.unwrap_or(false),
+    custom_element: patterns(options.is_custom_element),
+    native_tag: patterns(options.is_native_tag),
+  };
+  Ok(AsyncTask::new(CompileTask { compiler, files }))
+}
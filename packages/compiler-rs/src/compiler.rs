@@ -0,0 +1,143 @@
+use std::cell::Cell;
+
+use common::{
+  diagnostic::Diagnostic,
+  either::Either,
+  options::{DiagnosticLevel, DomPropWrites, Hmr, TagMatcher, TransformOptions, Whitespace},
+};
+use oxc_allocator::Allocator;
+use oxc_semantic::Stats;
+use rayon::prelude::*;
+
+use crate::{Timings, TransformOutput, compile};
+
+thread_local! {
+  // reused by the files compiled on the same thread, see `Compiler::compile`
+  static ALLOCATOR: Cell<Option<Allocator>> = const { Cell::new(None) };
+}
+
+/// The options shared by a batch of files. Unlike `TransformOptions` it holds
+/// no per-file state and no callbacks, so one `Compiler` can be used from
+/// several threads.
+#[derive(Debug, Clone)]
+pub struct Compiler {
+  pub source_map: bool,
+  pub interop: bool,
+  pub hmr: Either<bool, Hmr>,
+  pub ssr: bool,
+  pub optimize: bool,
+  pub runtime_module_name: Option<String>,
+  pub merge_props: bool,
+  pub dump_ir: bool,
+  pub report_templates: bool,
+  pub shared_templates: Option<String>,
+  pub html_nesting: DiagnosticLevel,
+  pub whitespace: Whitespace,
+  pub type_aware_text: bool,
+  pub dom_prop_writes: DomPropWrites,
+  pub inline_modifiers: bool,
+  // patterns of `TagMatcher::Patterns`
+  pub custom_element: Option<Vec<String>>,
+  pub native_tag: Option<Vec<String>>,
+}
+
+impl Default for Compiler {
+  fn default() -> Self {
+    Self {
+      source_map: false,
+      interop: false,
+      hmr: Either::A(false),
+      ssr: false,
+      optimize: true,
+      runtime_module_name: None,
+      merge_props: true,
+      dump_ir: false,
+      report_templates: false,
+      shared_templates: None,
+      html_nesting: DiagnosticLevel::Warn,
+      whitespace: Whitespace::Condense,
+      type_aware_text: false,
+      dom_prop_writes: DomPropWrites::Compact,
+      inline_modifiers: false,
+      custom_element: None,
+      native_tag: None,
+    }
+  }
+}
+
+impl Compiler {
+  pub fn options<'a>(&self, filename: &'a str) -> TransformOptions<'a> {
+    TransformOptions {
+      filename,
+      source_map: self.source_map,
+      interop: self.interop,
+      hmr: self.hmr.clone(),
+      ssr: self.ssr,
+      optimize: self.optimize,
+      runtime_module_name: self.runtime_module_name.clone(),
+      merge_props: self.merge_props,
+      dump_ir: self.dump_ir,
+      report_templates: self.report_templates,
+      shared_templates: self.shared_templates.clone(),
+      html_nesting: self.html_nesting,
+      whitespace: self.whitespace,
+      type_aware_text: self.type_aware_text,
+      dom_prop_writes: self.dom_prop_writes,
+      inline_modifiers: self.inline_modifiers,
+      custom_element: self.custom_element.clone().map(TagMatcher::Patterns),
+      native_tag: self.native_tag.clone().map(TagMatcher::Patterns),
+      ..Default::default()
+    }
+  }
+
+  /// Compiles a single file like `try_transform`, reusing the allocator of
+  /// the previous file compiled on this thread.
+  pub fn compile(&self, filename: &str, source: &str) -> Result<TransformOutput, Vec<Diagnostic>> {
+    let mut allocator = ALLOCATOR.take().unwrap_or_default();
+    let result = compile_in(
+      self.options(filename),
+      source,
+      &mut allocator,
+      &mut None,
+      &mut Timings::default(),
+    );
+    ALLOCATOR.set(Some(allocator));
+    result
+  }
+
+  /// Compiles `(filename, source)` pairs on the rayon thread pool. The results
+  /// are in the order of `files`.
+  pub fn compile_many<F, S>(
+    &self,
+    files: &[(F, S)],
+  ) -> Vec<Result<TransformOutput, Vec<Diagnostic>>>
+  where
+    F: AsRef<str> + Sync,
+    S: AsRef<str> + Sync,
+  {
+    files
+      .par_iter()
+      .map(|(filename, source)| self.compile(filename.as_ref(), source.as_ref()))
+      .collect()
+  }
+}
+
+// Compiles in `allocator` after resetting it, and hands it back once the
+// AST allocated in it is dropped. `stats` holds the semantic stats of a
+// previous compilation of the file, and is updated with the ones of this one.
+pub(crate) fn compile_in<'a>(
+  mut options: TransformOptions<'a>,
+  source: &'a str,
+  allocator: &mut Allocator,
+  stats: &mut Option<Stats>,
+  timings: &mut Timings,
+) -> Result<TransformOutput, Vec<Diagnostic>> {
+  allocator.reset();
+  options.allocator = std::mem::take(allocator);
+  let result = compile(source, &options, true, *stats, timings);
+  if result.is_ok() {
+    *stats = Some(options.semantic.borrow().stats());
+  }
+  *allocator = std::mem::take(&mut options.allocator);
+  result
+}
@@ -1,5 +1,4 @@
-use common::{options::TransformOptions, text::hash_string};
-use napi::Either;
+use common::{either::Either, options::TransformOptions, text::hash_string};
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
   AstBuilder, NONE,
@@ -1,147 +1,129 @@
-pub use common::options::TransformOptions;
-use napi::{
-  Either, Env,
-  bindgen_prelude::{Function, Object},
-};
-use napi_derive::napi;
+pub use common::options::{Template, TransformOptions};
 use oxc_codegen::{Codegen, CodegenReturn};
-use oxc_parser::Parser;
-use oxc_semantic::SemanticBuilder;
-use oxc_span::{SourceType, Span};
-use std::path::{Path, PathBuf};
-
-use common::{
-  error::{ErrorCodes, create_compiler_error, create_compiler_warning},
-  options::Hmr,
+use oxc_parser::{Parser, ParserReturn};
+use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn, Stats};
+use oxc_span::SourceType;
+use serde_json::Value;
+use std::{
+  collections::HashSet,
+  path::{Path, PathBuf},
+  time::Instant,
 };
 
+use common::diagnostic::Diagnostic;
+use vapor::ir::json::ir_to_json;
+
 use crate::transform::Transform;
 
+#[cfg(feature = "napi")]
+pub mod binding;
+mod compiler;
 mod hmr_or_ssr;
+mod session;
 mod transform;
+mod virtual_code;
 
-#[cfg_attr(feature = "napi", napi(object))]
-#[derive(Default)]
-pub struct CompilerOptions {
-  pub on_error: Option<Function<'static, Object<'static>, ()>>,
-  pub on_warn: Option<Function<'static, Object<'static>, ()>>,
-  /// Generate source map?
-  /// @default false
-  pub source_map: Option<bool>,
-  /// Filename for source map generation.
-  /// Also used for self-recursive reference in templates
-  /// @default 'index.jsx'
-  pub filename: Option<String>,
-  /// When enabled, JSX within `defineVaporComponent` is transformed to Vapor DOM,
-  /// while all other JSX is transformed to Virtual DOM.
-  pub interop: Option<bool>,
-  /// Enabled HMR support.
-  /// - `true`/`false`: a boolean to simply enable/disable HMR. When `true`, HMR
-  ///   is enabled with default configuration.
-  /// - `Hmr`: an object to enable HMR with custom configuration.
-  /// @default false
-  pub hmr: Option<Either<bool, Hmr>>,
-  /// Enabled SSR support.
-  /// @default false
-  pub ssr: Option<bool>,
-  /// Whether to enable compiler optimizations, including:
-  /// - **Slots**: Detect if slots are stable for more efficient updates.
-  /// - **Cache**: Cache event handler to avoid recreating closures on each render.
-  /// - **Block**: Enable block tree optimizations.
-  ///
-  /// Note: this option is only used in interop mode.
-  /// @default true
-  pub optimize: Option<bool>,
-  /// Customize where to import runtime helpers from vue-jsx-vapor.
-  /// If not specified, defaults to the virtual module path (e.g., `/vue-jsx-vapor/vapor`).
-  pub runtime_module_name: Option<String>,
-  /// Whether to merge props when using JSXSpreadAttribute.
-  /// - `true`: Use Vue's `mergeProps` behavior
-  ///   - Event listeners (`onXxx`) are merged into an array
-  ///   - `class` and `style` are normalized and merged
-  ///   - Other props are overridden by later values
-  /// - `false`: later props override earlier ones (like object spread)
-  /// @default true
-  pub merge_props: Option<bool>,
-}
+pub use compiler::Compiler;
+pub use session::{Session, Timings};
+pub use virtual_code::{Mapping, VirtualCode, virtual_code};
 
-#[cfg(feature = "napi")]
-#[napi(object)]
-pub struct TransformReturn {
+pub struct TransformOutput {
   pub code: String,
   pub map: Option<String>,
+  pub diagnostics: Vec<Diagnostic>,
+  /// The Vapor IR of every root, when `dump_ir` is enabled
+  pub ir: Option<Value>,
+  /// The templates of the file, when `report_templates` is enabled
+  pub templates: Option<Vec<Template>>,
 }
 
-#[cfg(feature = "napi")]
-#[napi]
-pub fn _transform(env: Env, source: String, options: Option<CompilerOptions>) -> TransformReturn {
-  let options = options.unwrap_or_default();
-  let filename = &options.filename.unwrap_or("index.jsx".to_string());
-  let ssr = options.ssr.unwrap_or(false);
-  let CodegenReturn { code, map, .. } = transform(
-    &source,
-    Some(TransformOptions {
-      filename,
-      source_map: options.source_map.unwrap_or(false),
-      interop: options.interop.unwrap_or(false),
-      hmr: options.hmr.unwrap_or(Either::A(false)),
-      ssr,
-      optimize: options.optimize.unwrap_or(true),
-      runtime_module_name: options.runtime_module_name,
-      merge_props: options.merge_props.unwrap_or(true),
-      on_error: if let Some(on_error) = options.on_error {
-        Box::new(move |code: ErrorCodes, span: Span| {
-          let compiler_error = create_compiler_error(&env, code, span).unwrap();
-          on_error.call(compiler_error).unwrap();
-        }) as Box<dyn Fn(ErrorCodes, Span)>
-      } else {
-        Box::new(|_: ErrorCodes, _: Span| {}) as Box<dyn Fn(ErrorCodes, Span)>
-      },
-      on_warn: if let Some(on_warn) = options.on_warn {
-        Box::new(move |message: &str, span: Span| {
-          let warning = create_compiler_warning(&env, message, span).unwrap();
-          on_warn.call(warning).unwrap();
-        }) as Box<dyn Fn(&str, Span)>
-      } else {
-        Box::new(|_: &str, _: Span| {}) as Box<dyn Fn(&str, Span)>
-      },
-      ..Default::default()
-    }),
-  );
-  TransformReturn {
-    code,
-    map: map.map(|m| m.to_json_string()),
+// Query strings like `?vue&type=script` are ignored, and unknown extensions
+// are parsed as TSX, which accepts any JSX.
+fn get_source_type(filename: &str) -> SourceType {
+  let source_type = if let Some(ext) = Path::new(filename)
+    .extension()
+    .and_then(std::ffi::OsStr::to_str)
+    && let Some(ext) = ext.split("?").next()
+  {
+    SourceType::from_extension(ext)
+  } else {
+    SourceType::from_path(filename)
+  };
+  source_type.unwrap_or(SourceType::tsx())
+}
+
+pub fn transform<'a>(source: &'a str, options: Option<TransformOptions<'a>>) -> TransformOutput {
+  match compile(
+    source,
+    &options.unwrap_or_default(),
+    false,
+    None,
+    &mut Timings::default(),
+  ) {
+    Ok(output) => output,
+    Err(_) => unreachable!("errors are only returned by `try_transform`"),
   }
 }
 
-pub fn transform<'a>(source: &'a str, options: Option<TransformOptions<'a>>) -> CodegenReturn {
+/// Like `transform`, but fails with the parse and semantic errors of the
+/// source instead of compiling the program the parser recovered.
+pub fn try_transform<'a>(
+  source: &'a str,
+  options: Option<TransformOptions<'a>>,
+) -> Result<TransformOutput, Vec<Diagnostic>> {
+  compile(
+    source,
+    &options.unwrap_or_default(),
+    true,
+    None,
+    &mut Timings::default(),
+  )
+}
+
+fn compile<'a>(
+  source: &'a str,
+  options: &TransformOptions<'a>,
+  strict: bool,
+  // sizes the semantic tables without counting the AST first
+  stats: Option<Stats>,
+  timings: &mut Timings,
+) -> Result<TransformOutput, Vec<Diagnostic>> {
   use oxc_codegen::CodegenOptions;
-  let options = options.unwrap_or_default();
+  *timings = Timings::default();
   *options.source_text.borrow_mut() = source;
-  *options.source_type.borrow_mut() = {
-    if let Some(ext) = Path::new(options.filename)
-      .extension()
-      .and_then(std::ffi::OsStr::to_str)
-      && let Some(ext) = ext.split("?").next()
-    {
-      SourceType::from_extension(ext).unwrap()
-    } else {
-      SourceType::from_path(options.filename).unwrap()
-    }
-  };
-  let mut program = Parser::new(
+  *options.source_type.borrow_mut() = get_source_type(options.filename);
+  let start = Instant::now();
+  let ParserReturn {
+    mut program,
+    errors,
+    ..
+  } = Parser::new(
     unsafe { &*(&options.allocator as *const _) },
     source,
     *options.source_type.borrow(),
   )
-  .parse()
-  .program;
+  .parse();
+  timings.parse = start.elapsed();
+  if strict && !errors.is_empty() {
+    return Err(errors.iter().map(Diagnostic::from_oxc).collect());
+  }
   let program_ptr = &program as *const _;
-  *options.semantic.borrow_mut() = SemanticBuilder::new()
-    .build(unsafe { &*program_ptr })
-    .semantic;
-  Transform::new(unsafe { &*(&options as *const _) }).visit(&mut program);
-  Codegen::new()
+  let start = Instant::now();
+  let mut builder = SemanticBuilder::new().with_check_syntax_error(strict);
+  if let Some(stats) = stats {
+    builder = builder.with_stats(stats);
+  }
+  let SemanticBuilderReturn { semantic, errors } = builder.build(unsafe { &*program_ptr });
+  timings.semantic = start.elapsed();
+  if strict && !errors.is_empty() {
+    return Err(errors.iter().map(Diagnostic::from_oxc).collect());
+  }
+  *options.semantic.borrow_mut() = semantic;
+  let start = Instant::now();
+  Transform::new(unsafe { &*(options as *const _) }).visit(&mut program, timings);
+  timings.transform = start.elapsed() - timings.hmr_or_ssr;
+  let start = Instant::now();
+  let CodegenReturn { code, map, .. } = Codegen::new()
     .with_options(CodegenOptions {
       source_map_path: if options.source_map {
         Some(PathBuf::from(&options.filename))
@@ -150,5 +132,44 @@ pub fn transform<'a>(source: &'a str, options: Option<TransformOptions<'a>>) ->
       },
       ..CodegenOptions::default()
     })
-    .build(&program)
+    .build(&program);
+  timings.codegen = start.elapsed();
+  let templates = options.templates.take();
+  Ok(TransformOutput {
+    code,
+    map: map.map(|map| map.to_json_string()),
+    diagnostics: options.diagnostics.take(),
+    ir: options
+      .dump_ir
+      .then(|| ir_to_json(&templates, options.ir.take())),
+    templates: options.report_templates.then_some(templates),
+  })
+}
+
+/// The module imported by files compiled with `shared_templates`, declaring
+/// every template once under its hash.
+pub fn shared_template_module<'t>(templates: impl IntoIterator<Item = &'t Template>) -> String {
+  let mut seen = HashSet::new();
+  let mut code = String::from("import { template } from \"vue\";\n");
+  for template in templates {
+    let hash = template.hash();
+    if !seen.insert(hash.clone()) {
+      continue;
+    }
+    let mut args = vec![serde_json::to_string(&template.content).unwrap()];
+    let flags = template.flags();
+    if flags > 0 {
+      args.push(flags.to_string());
+    } else if template.ns > 0 {
+      args.push("false".to_string());
+    }
+    if template.ns > 0 {
+      args.push(template.ns.to_string());
+    }
+    code += &format!(
+      "export const t_{hash} = /* @__PURE__ */ template({});\n",
+      args.join(", ")
+    );
+  }
+  code
 }
@@ -0,0 +1,139 @@
+use std::{cell::RefCell, collections::HashMap, ops::AddAssign, time::Duration};
+
+use common::{check::is_tag_pattern_match, diagnostic::Diagnostic, options::TagMatcher};
+use oxc_allocator::Allocator;
+use oxc_semantic::Stats;
+
+use crate::{Compiler, TransformOutput, compiler::compile_in};
+
+/// Time spent in each phase of a compilation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+  pub parse: Duration,
+  pub semantic: Duration,
+  // JSX transform, without the HMR/SSR pass
+  pub transform: Duration,
+  pub hmr_or_ssr: Duration,
+  pub codegen: Duration,
+}
+
+impl Timings {
+  pub fn total(&self) -> Duration {
+    self.parse + self.semantic + self.transform + self.hmr_or_ssr + self.codegen
+  }
+}
+
+impl AddAssign for Timings {
+  fn add_assign(&mut self, other: Self) {
+    self.parse += other.parse;
+    self.semantic += other.semantic;
+    self.transform += other.transform;
+    self.hmr_or_ssr += other.hmr_or_ssr;
+    self.codegen += other.codegen;
+  }
+}
+
+// Tags already matched against the patterns of a `Compiler`, which a dev
+// server sees again in every file.
+struct TagCache {
+  patterns: Vec<String>,
+  matches: RefCell<HashMap<String, bool>>,
+}
+
+impl TagCache {
+  fn new(patterns: &Option<Vec<String>>) -> Option<Self> {
+    patterns.clone().map(|patterns| Self {
+      patterns,
+      matches: RefCell::default(),
+    })
+  }
+
+  fn matcher(&self) -> TagMatcher<'_> {
+    TagMatcher::Predicate(Box::new(|tag| {
+      if let Some(matches) = self.matches.borrow().get(tag) {
+        return *matches;
+      }
+      let matches = self
+        .patterns
+        .iter()
+        .any(|pattern| is_tag_pattern_match(pattern, tag));
+      self.matches.borrow_mut().insert(tag.to_string(), matches);
+      matches
+    }))
+  }
+}
+
+/// Compiles files one after the other, like a dev server does on HMR. The
+/// arena of a file is reset and reused by the next one instead of being
+/// freed, and the time spent in each phase is recorded.
+///
+/// The semantic stats of a file size the tables of its next compilation,
+/// which skips counting the AST, and tags matched against `custom_element`
+/// and `native_tag` are remembered across files.
+pub struct Session {
+  compiler: Compiler,
+  allocator: Allocator,
+  stats: HashMap<String, Stats>,
+  custom_element: Option<TagCache>,
+  native_tag: Option<TagCache>,
+  last: Timings,
+  total: Timings,
+  files: usize,
+}
+
+impl Session {
+  pub fn new(compiler: Compiler) -> Self {
+    Self {
+      custom_element: TagCache::new(&compiler.custom_element),
+      native_tag: TagCache::new(&compiler.native_tag),
+      compiler,
+      allocator: Allocator::default(),
+      stats: HashMap::new(),
+      last: Timings::default(),
+      total: Timings::default(),
+      files: 0,
+    }
+  }
+
+  pub fn compiler(&self) -> &Compiler {
+    &self.compiler
+  }
+
+  pub fn compile(
+    &mut self,
+    filename: &str,
+    source: &str,
+  ) -> Result<TransformOutput, Vec<Diagnostic>> {
+    let mut options = self.compiler.options(filename);
+    options.custom_element = self.custom_element.as_ref().map(TagCache::matcher);
+    options.native_tag = self.native_tag.as_ref().map(TagCache::matcher);
+    let mut stats = self.stats.get(filename).copied();
+    let result = compile_in(
+      options,
+      source,
+      &mut self.allocator,
+      &mut stats,
+      &mut self.last,
+    );
+    if let Some(stats) = stats {
+      self.stats.insert(filename.to_string(), stats);
+    }
+    self.total += self.last;
+    self.files += 1;
+    result
+  }
+
+  /// The timings of the last compiled file.
+  pub fn timings(&self) -> Timings {
+    self.last
+  }
+
+  /// The timings of every file compiled in this session, added up.
+  pub fn total_timings(&self) -> Timings {
+    self.total
+  }
+
+  pub fn files(&self) -> usize {
+    self.files
+  }
+}
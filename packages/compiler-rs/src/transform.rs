@@ -1,8 +1,7 @@
 use common::{
+  either::Either,
   options::{RootJsx, TransformOptions},
-  patch_flag::TemplateFlags,
 };
-use napi::Either;
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   AstBuilder, NONE,
@@ -19,8 +18,29 @@ use oxc_ast_visit::{
   },
 };
 use oxc_span::{GetSpan, SPAN};
+use std::time::Instant;
 
-use crate::hmr_or_ssr::HmrOrSsrTransform;
+use crate::{Timings, hmr_or_ssr::HmrOrSsrTransform};
+
+const VDOM_HELPERS: &[&str] = &[
+  "createVNodeCache",
+  "normalizeVNode",
+  "normalizeSlot",
+  "normalizeSlots",
+  "normalizeClass",
+];
+const VAPOR_HELPERS: &[&str] = &[
+  "setNodes",
+  "createNodes",
+  "createComponent",
+  "normalizeVaporSlots",
+  "withVaporMemo",
+];
+const SSR_HELPERS: &[&str] = &[
+  "defineVaporSSRComponent",
+  "ssrNormalizeSlots",
+  "ssrRenderNodes",
+];
 
 pub struct Transform<'a> {
   ast: AstBuilder<'a>,
@@ -32,7 +52,9 @@ pub struct Transform<'a> {
 impl<'a> Transform<'a> {
   pub fn new(options: &'a TransformOptions<'a>) -> Self {
     let ast = AstBuilder::new(&options.allocator);
-    let ast_ptr = &ast as *const _;
+    // the transform contexts borrow the builder for the whole arena lifetime,
+    // so it lives in the arena rather than in `Self`, which moves
+    let root_ast: &'a AstBuilder<'a> = options.allocator.alloc(ast);
     *options.on_enter_expression.borrow_mut() = Some(Box::new(|node| unsafe {
       if let Expression::CallExpression(node) = &mut *node
         && let Expression::Identifier(callee) = &mut node.callee
@@ -53,10 +75,15 @@ impl<'a> Transform<'a> {
         &*node,
         Expression::JSXElement(_) | Expression::JSXFragment(_)
       ) {
-        if !options.ssr && options.interop && *options.in_vapor.borrow() < 1 {
+        // Under SSR only Vapor components get the string renderer,
+        // everything else is server-rendered through vdom.
+        if options.ssr {
+          return Some((node, *options.in_vapor.borrow() < 1));
+        }
+        if options.interop && *options.in_vapor.borrow() < 1 {
           return Some((node, true));
         }
-        return Some((node, options.ssr));
+        return Some((node, false));
       }
       None
     }));
@@ -83,10 +110,10 @@ impl<'a> Transform<'a> {
       let node = (&mut *node_ptr).take_in(&options.allocator);
       let expression = if vdom {
         use vdom::transform::TransformContext;
-        TransformContext::new(node, options, &*ast_ptr).transform()
+        TransformContext::new(node, options, root_ast).transform()
       } else {
         use vapor::transform::TransformContext;
-        TransformContext::new(node, options, &*ast_ptr).transform()
+        TransformContext::new(node, options, root_ast).transform()
       };
       RootJsx {
         node_ptr,
@@ -102,14 +129,16 @@ impl<'a> Transform<'a> {
     }
   }
 
-  pub fn visit(&mut self, program: &mut Program<'a>) {
+  pub fn visit(&mut self, program: &mut Program<'a>, timings: &mut Timings) {
     self.source_text = program.source_text;
 
     self.visit_program(program);
     let ast = &self.ast;
 
     if self.options.ssr || !matches!(self.options.hmr, Either::A(false)) {
+      let start = Instant::now();
       HmrOrSsrTransform::new(self.options).visit(ast, program);
+      timings.hmr_or_ssr = start.elapsed();
     }
 
     for root in self.roots.drain(..) {
@@ -158,118 +187,69 @@ impl<'a> Transform<'a> {
         });
       };
 
-      let vdom_helpers = vec![
-        "createVNodeCache",
-        "normalizeVNode",
-        "normalizeSlot",
-        "normalizeSlots",
-        "normalizeClass",
-      ]
-      .into_iter()
-      .filter(|helper| {
-        if helpers.contains(*helper) {
-          helpers.remove(*helper);
-          true
-        } else {
-          false
+      let runtime_module_name = self.options.runtime_module_name.as_deref();
+      for (group, source) in [
+        (
+          VDOM_HELPERS,
+          runtime_module_name.unwrap_or("/vue-jsx-vapor/vdom"),
+        ),
+        (
+          VAPOR_HELPERS,
+          runtime_module_name.unwrap_or("/vue-jsx-vapor/vapor"),
+        ),
+        (
+          SSR_HELPERS,
+          runtime_module_name.unwrap_or("/vue-jsx-vapor/ssr"),
+        ),
+      ] {
+        let group_helpers = group
+          .iter()
+          .copied()
+          .filter(|helper| helpers.remove(*helper))
+          .collect::<Vec<_>>();
+        if !group_helpers.is_empty() {
+          statements.push(self.gen_helper_import(group_helpers, source));
         }
-      })
-      .collect::<Vec<_>>();
-      if !vdom_helpers.is_empty() {
-        statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
-          SPAN,
-          Some(ast.vec_from_iter(vdom_helpers.into_iter().map(|helper| {
-            ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_name(SPAN, ast.str(helper)),
-              ast.binding_identifier(SPAN, ast.str(format!("_{}", helper).as_str())),
-              ImportOrExportKind::Value,
-            )
-          }))),
-          ast.string_literal(
-            SPAN,
-            ast.str(
-              if let Some(runtime_module_name) = &self.options.runtime_module_name {
-                runtime_module_name.as_str()
-              } else {
-                "/vue-jsx-vapor/vdom"
-              },
-            ),
-            None,
-          ),
-          None,
-          NONE,
-          ImportOrExportKind::Value,
-        )))
       }
 
-      let vapor_helpers = vec![
-        "setNodes",
-        "createNodes",
-        "createComponent",
-        "normalizeVaporSlots",
-        "defineVaporSSRComponent",
-      ]
-      .into_iter()
-      .filter(|helper| {
-        if helpers.contains(*helper) {
-          helpers.remove(*helper);
-          true
-        } else {
-          false
-        }
-      })
-      .collect::<Vec<_>>();
-      if !vapor_helpers.is_empty() {
-        statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
-          SPAN,
-          Some(ast.vec_from_iter(vapor_helpers.into_iter().map(|helper| {
-            ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_name(SPAN, ast.str(helper)),
-              ast.binding_identifier(SPAN, ast.str(format!("_{}", helper).as_str())),
-              ImportOrExportKind::Value,
-            )
-          }))),
-          ast.string_literal(
-            SPAN,
-            ast.str(
-              if let Some(runtime_module_name) = &self.options.runtime_module_name {
-                runtime_module_name.as_str()
-              } else {
-                "/vue-jsx-vapor/vapor"
-              },
-            ),
-            None,
-          ),
-          None,
-          NONE,
-          ImportOrExportKind::Value,
-        )))
+      let (server_renderer_helpers, vue_helpers): (Vec<_>, Vec<_>) = helpers
+        .into_iter()
+        .partition(|helper| helper.starts_with("ssr"));
+      if !server_renderer_helpers.is_empty() {
+        statements.push(self.gen_helper_import(server_renderer_helpers, "vue/server-renderer"));
+      }
+      if !vue_helpers.is_empty() {
+        statements.push(self.gen_helper_import(vue_helpers, "vue"));
       }
+    }
 
-      if !helpers.is_empty() {
-        statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
-          SPAN,
-          Some(ast.vec_from_iter(helpers.iter().map(|helper| {
+    let templates = self.options.templates.borrow();
+    // Vapor SSR inlines templates into the rendered string.
+    if !templates.is_empty()
+      && !self.options.ssr
+      && let Some(source) = &self.options.shared_templates
+    {
+      statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
+        SPAN,
+        Some(
+          ast.vec_from_iter(templates.iter().enumerate().map(|(index, template)| {
             ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_name(SPAN, ast.str(helper)),
-              ast.binding_identifier(SPAN, ast.str(format!("_{}", helper).as_str())),
+              template.span,
+              ast.module_export_name_identifier_name(
+                SPAN,
+                ast.str(&format!("t_{}", template.hash())),
+              ),
+              ast.binding_identifier(SPAN, ast.str(&format!("_t{index}"))),
               ImportOrExportKind::Value,
             )
-          }))),
-          ast.string_literal(SPAN, ast.str("vue"), None),
-          None,
-          NONE,
-          ImportOrExportKind::Value,
-        )))
-      }
-    }
-
-    let templates = self.options.templates.take();
-    let template_len = templates.len();
-    if template_len > 0 {
+          })),
+        ),
+        ast.string_literal(SPAN, ast.str(source), None),
+        None,
+        NONE,
+        ImportOrExportKind::Value,
+      )));
+    } else if !templates.is_empty() && !self.options.ssr {
       let template_statements = templates
         .iter()
         .enumerate()
@@ -280,19 +260,11 @@ impl<'a> Transform<'a> {
             None,
           ));
 
-          let flags = if template.root {
-            TemplateFlags::ROOT as i32
-          } else {
-            0
-          } | if template._static {
-            TemplateFlags::STATIC as i32
-          } else {
-            0
-          };
+          let flags = template.flags();
 
           Statement::VariableDeclaration(
             ast.alloc_variable_declaration(
-              SPAN,
+              template.span,
               VariableDeclarationKind::Const,
               ast.vec1(
                 ast.variable_declarator(
@@ -384,6 +356,25 @@ impl<'a> Transform<'a> {
       program.body.splice(index..index, statements);
     }
   }
+
+  fn gen_helper_import(&self, helpers: Vec<&str>, source: &str) -> Statement<'a> {
+    let ast = &self.ast;
+    Statement::ImportDeclaration(ast.alloc_import_declaration(
+      SPAN,
+      Some(ast.vec_from_iter(helpers.into_iter().map(|helper| {
+        ast.import_declaration_specifier_import_specifier(
+          SPAN,
+          ast.module_export_name_identifier_name(SPAN, ast.str(helper)),
+          ast.binding_identifier(SPAN, ast.str(format!("_{}", helper).as_str())),
+          ImportOrExportKind::Value,
+        )
+      }))),
+      ast.string_literal(SPAN, ast.str(source), None),
+      None,
+      NONE,
+      ImportOrExportKind::Value,
+    ))
+  }
 }
 
 impl<'a> VisitMut<'a> for Transform<'a> {
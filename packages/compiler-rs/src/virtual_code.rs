@@ -0,0 +1,468 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{
+  check::{is_jsx_component, is_template},
+  directive::{find_prop, find_prop_mut, resolve_directive},
+};
+use oxc_allocator::Allocator;
+use oxc_ast::{
+  AstBuilder,
+  ast::{
+    BinaryOperator, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXChild,
+    JSXElement, JSXFragment,
+  },
+};
+use oxc_ast_visit::{
+  VisitMut,
+  walk_mut::{walk_jsx_element, walk_jsx_fragment},
+};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, Span};
+
+use crate::get_source_type;
+
+const RENDER_LIST: &str = "
+declare function __renderList<T>(
+  source: T,
+  render: (
+    ...args: T extends number
+      ? [value: number, index: number]
+      : T extends string
+        ? [value: string, index: number]
+        : T extends Iterable<infer V>
+          ? [value: V, index: number]
+          : [value: T[keyof T], key: keyof T, index: number]
+  ) => any,
+): any[];
+";
+
+/// A range of the virtual code copied from `source`.
+#[derive(Debug, PartialEq)]
+pub struct Mapping {
+  pub source: Span,
+  pub generated: Span,
+}
+
+pub struct VirtualCode {
+  pub code: String,
+  pub mappings: Vec<Mapping>,
+}
+
+impl VirtualCode {
+  fn push_source(&mut self, source: &str, span: Span) {
+    if span.is_empty() {
+      return;
+    }
+    let start = self.code.len() as u32;
+    self
+      .code
+      .push_str(&source[span.start as usize..span.end as usize]);
+    self.mappings.push(Mapping {
+      source: span,
+      generated: Span::new(start, self.code.len() as u32),
+    });
+  }
+}
+
+/// Translates `v-if`, `v-for`, `v-slot` and `v-model` into plain TSX for type
+/// checking. The rest of the source is copied as is, and every copied range is
+/// listed in `mappings`.
+pub fn virtual_code(source: &str, filename: &str) -> VirtualCode {
+  let allocator = Allocator::default();
+  let mut program = Parser::new(&allocator, source, get_source_type(filename))
+    .parse()
+    .program;
+  let mut generator = Generator {
+    ast: AstBuilder::new(&allocator),
+    source,
+    edits: vec![],
+    depth: 0,
+    in_children: HashSet::new(),
+    branches: HashMap::new(),
+    render_list: false,
+  };
+  generator.visit_program(&mut program);
+  generator.finish()
+}
+
+enum Segment {
+  Text(String),
+  Source(Span),
+}
+
+fn text(text: &str) -> Segment {
+  Segment::Text(text.to_string())
+}
+
+// Insertions at the same offset are ordered so that wrappers of outer elements
+// enclose the ones of inner elements, and a sibling is closed before the next
+// one is opened.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EditKind {
+  Close,
+  Open,
+  Replace,
+}
+
+struct Edit {
+  span: Span,
+  kind: EditKind,
+  depth: i32,
+  segments: Vec<Segment>,
+}
+
+#[derive(Clone, Copy)]
+struct Branch {
+  head: bool,
+  last: bool,
+}
+
+struct Generator<'a> {
+  ast: AstBuilder<'a>,
+  source: &'a str,
+  edits: Vec<Edit>,
+  depth: i32,
+  // elements that are children of an element or a fragment, keyed by start
+  in_children: HashSet<u32>,
+  // elements of a v-if chain, keyed by start
+  branches: HashMap<u32, Branch>,
+  render_list: bool,
+}
+
+impl<'a> Generator<'a> {
+  fn edit(&mut self, span: Span, kind: EditKind, segments: Vec<Segment>) {
+    if kind != EditKind::Replace && segments.is_empty() {
+      return;
+    }
+    self.edits.push(Edit {
+      span,
+      kind,
+      depth: self.depth,
+      segments,
+    });
+  }
+
+  fn finish(mut self) -> VirtualCode {
+    self.edits.sort_by_key(|edit| {
+      (
+        edit.span.start,
+        edit.kind,
+        if edit.kind == EditKind::Close {
+          -edit.depth
+        } else {
+          edit.depth
+        },
+      )
+    });
+    let mut output = VirtualCode {
+      code: String::new(),
+      mappings: vec![],
+    };
+    let mut cursor = 0;
+    for edit in self.edits {
+      // inside a range that was already replaced
+      if edit.span.start < cursor {
+        continue;
+      }
+      output.push_source(self.source, Span::new(cursor, edit.span.start));
+      for segment in edit.segments {
+        match segment {
+          Segment::Text(text) => output.code.push_str(&text),
+          Segment::Source(span) => output.push_source(self.source, span),
+        }
+      }
+      cursor = edit.span.end;
+    }
+    output.push_source(self.source, Span::new(cursor, self.source.len() as u32));
+    if self.render_list {
+      output.code.push_str(RENDER_LIST);
+    }
+    output
+  }
+
+  fn register_children(&mut self, children: &[JSXChild<'a>]) {
+    let mut previous = None;
+    for child in children {
+      match child {
+        JSXChild::Element(child) => {
+          let start = child.span.start;
+          self.in_children.insert(start);
+          if find_prop(child, vec!["v-if"]).is_some() {
+            self.branches.insert(
+              start,
+              Branch {
+                head: true,
+                last: true,
+              },
+            );
+            previous = Some(start);
+          } else if let Some(previous_start) = previous
+            && find_prop(child, vec!["v-else-if", "v-else"]).is_some()
+          {
+            if let Some(branch) = self.branches.get_mut(&previous_start) {
+              branch.last = false;
+            }
+            self.branches.insert(
+              start,
+              Branch {
+                head: false,
+                last: true,
+              },
+            );
+            previous = find_prop(child, vec!["v-else-if"])
+              .is_some()
+              .then_some(start);
+          } else {
+            previous = None;
+          }
+        }
+        JSXChild::Text(child) if child.value.trim().is_empty() => (),
+        _ => previous = None,
+      }
+    }
+  }
+
+  fn transform_element(&mut self, node: &mut JSXElement<'a>) {
+    let is_component = is_jsx_component(node);
+    let mut condition = None;
+    let mut for_parts = None;
+    let mut slot = None;
+    for attr in node.opening_element.attributes.iter_mut() {
+      let JSXAttributeItem::Attribute(attr) = attr else {
+        continue;
+      };
+      let name = match &attr.name {
+        JSXAttributeName::Identifier(name) => name.name.as_str(),
+        JSXAttributeName::NamespacedName(name) => name.namespace.name.as_str(),
+      };
+      let name = name.split('_').next().unwrap_or_default();
+      if !matches!(
+        name,
+        "v-if" | "v-else-if" | "v-else" | "v-for" | "v-slot" | "v-model"
+      ) {
+        continue;
+      }
+      let span = attr.span;
+      let dir = resolve_directive(
+        unsafe { &mut *(&mut **attr as *mut JSXAttribute<'a>) },
+        &self.ast,
+      );
+      let exp = dir.exp.as_ref().map(|exp| exp.span());
+      let mut segments = vec![];
+      match name {
+        "v-if" | "v-else-if" => {
+          condition = Some(exp.map_or(text("undefined"), Segment::Source));
+        }
+        "v-for" => for_parts = dir.exp.as_ref().and_then(get_for_parts),
+        "v-slot" if is_component => slot = Some((get_key(&dir.arg, "default"), exp)),
+        "v-model" => {
+          if let Some(exp) = exp {
+            segments.push(text("{...{ "));
+            if is_component {
+              segments.extend(get_key(&dir.arg, "modelValue"));
+            } else {
+              segments.push(text("value"));
+            }
+            segments.push(text(": "));
+            segments.push(Segment::Source(exp));
+            // native elements are checked by the runtime directive
+            segments.push(text(if is_component { " }}" } else { " } as {}}" }));
+          }
+        }
+        _ => (),
+      }
+      self.edit(span, EditKind::Replace, segments);
+    }
+
+    let start = node.span.start;
+    let in_children = self.in_children.contains(&start);
+    let has_condition = condition.is_some();
+    let branch = self
+      .branches
+      .get(&start)
+      .copied()
+      .or(has_condition.then_some(Branch {
+        head: true,
+        last: true,
+      }));
+    let mut prefix = vec![];
+    let mut suffix = vec![];
+    if branch.map_or(for_parts.is_some(), |branch| branch.head) {
+      prefix.push(text(if in_children { "{" } else { "(" }));
+    }
+    if let Some(condition) = condition {
+      prefix.push(condition);
+      prefix.push(text(" ? "));
+    }
+    if let Some((source, params)) = &for_parts {
+      self.render_list = true;
+      prefix.push(text("__renderList("));
+      prefix.push(Segment::Source(*source));
+      prefix.push(text(", ("));
+      for (index, param) in params.iter().enumerate() {
+        if index > 0 {
+          prefix.push(text(", "));
+        }
+        prefix.push(Segment::Source(*param));
+      }
+      prefix.push(text(") => "));
+      suffix.push(text(")"));
+    }
+    if let Some(branch) = branch {
+      if !branch.last {
+        suffix.push(text(" : "));
+      } else if has_condition {
+        suffix.push(text(" : null"));
+      }
+    }
+    if branch.map_or(for_parts.is_some(), |branch| branch.last) {
+      suffix.push(text(if in_children { "}" } else { ")" }));
+    }
+    self.edit(Span::empty(start), EditKind::Open, prefix);
+    self.edit(Span::empty(node.span.end), EditKind::Close, suffix);
+
+    // the children of a component with `v-slot` are its slot function
+    if let Some((key, params)) = slot
+      && let Some(closing_element) = &node.closing_element
+    {
+      let mut segments = vec![text("{{ ")];
+      segments.extend(key);
+      segments.push(text(": ("));
+      segments.extend(params.map(Segment::Source));
+      segments.push(text(") => <>"));
+      self.edit(
+        Span::empty(node.opening_element.span.end),
+        EditKind::Open,
+        segments,
+      );
+      self.edit(
+        Span::empty(closing_element.span.start),
+        EditKind::Close,
+        vec![text("</> }}")],
+      );
+    } else if is_component {
+      self.transform_slot_templates(node);
+    }
+  }
+
+  // `<template v-slot:name>` children become the entries of a slots object,
+  // the other children are wrapped into default slots.
+  fn transform_slot_templates(&mut self, node: &mut JSXElement<'a>) {
+    let Some(closing_element) = &node.closing_element else {
+      return;
+    };
+    let closing_start = closing_element.span.start;
+    if !node.children.iter().any(|child| {
+      matches!(child, JSXChild::Element(child)
+        if is_template(child) && find_prop(child, vec!["v-slot"]).is_some())
+    }) {
+      return;
+    }
+
+    self.edit(
+      Span::empty(node.opening_element.span.end),
+      EditKind::Open,
+      vec![text("{{")],
+    );
+    let mut default_slot: Option<Span> = None;
+    for child in node.children.iter_mut() {
+      if let JSXChild::Element(child) = child
+        && is_template(child)
+        && let Some(dir) = find_prop_mut(
+          unsafe { &mut *(&mut **child as *mut JSXElement<'a>) },
+          vec!["v-slot"],
+        )
+      {
+        if let Some(span) = default_slot.take() {
+          self.wrap_default_slot(span);
+        }
+        let dir = resolve_directive(dir, &self.ast);
+        let mut segments = vec![text(" ")];
+        segments.extend(get_key(&dir.arg, "default"));
+        segments.push(text(": ("));
+        segments.extend(dir.exp.as_ref().map(|exp| Segment::Source(exp.span())));
+        segments.push(text(") => "));
+        if let Some(closing_element) = &child.closing_element {
+          segments.push(text("<>"));
+          self.edit(
+            Span::new(child.span.start, child.opening_element.span.end),
+            EditKind::Replace,
+            segments,
+          );
+          self.edit(closing_element.span, EditKind::Replace, vec![text("</>,")]);
+        } else {
+          segments.push(text("null,"));
+          self.edit(child.span, EditKind::Replace, segments);
+        }
+      } else if !matches!(child, JSXChild::Text(child) if child.value.trim().is_empty()) {
+        let span = child.span();
+        default_slot =
+          Some(default_slot.map_or(span, |default_slot| Span::new(default_slot.start, span.end)));
+      }
+    }
+    if let Some(span) = default_slot {
+      self.wrap_default_slot(span);
+    }
+    self.edit(
+      Span::empty(closing_start),
+      EditKind::Close,
+      vec![text("}}")],
+    );
+  }
+
+  // spread so that default content on both sides of a template doesn't
+  // declare `default` twice
+  fn wrap_default_slot(&mut self, span: Span) {
+    self.edit(
+      Span::empty(span.start),
+      EditKind::Open,
+      vec![text(" ...{ default: () => <>")],
+    );
+    self.edit(Span::empty(span.end), EditKind::Close, vec![text("</> },")]);
+  }
+}
+
+impl<'a> VisitMut<'a> for Generator<'a> {
+  fn visit_jsx_element(&mut self, node: &mut JSXElement<'a>) {
+    self.transform_element(node);
+    self.register_children(&node.children);
+    self.depth += 1;
+    walk_jsx_element(self, node);
+    self.depth -= 1;
+  }
+
+  fn visit_jsx_fragment(&mut self, node: &mut JSXFragment<'a>) {
+    self.register_children(&node.children);
+    self.depth += 1;
+    walk_jsx_fragment(self, node);
+    self.depth -= 1;
+  }
+}
+
+// `(item, index) in list` is split into the source and the parameters
+fn get_for_parts(exp: &Expression) -> Option<(Span, Vec<Span>)> {
+  let Expression::BinaryExpression(exp) = exp.without_parentheses() else {
+    return None;
+  };
+  if exp.operator != BinaryOperator::In {
+    return None;
+  }
+  let params = match exp.left.without_parentheses() {
+    Expression::SequenceExpression(left) => left.expressions.iter().map(|e| e.span()).collect(),
+    left => vec![left.span()],
+  };
+  Some((exp.right.span(), params))
+}
+
+fn get_key(arg: &Option<Expression>, default: &str) -> Vec<Segment> {
+  match arg {
+    None => vec![text(default)],
+    Some(Expression::StringLiteral(arg)) => vec![text(&format!("\"{}\"", arg.value))],
+    // a dynamic arg is parsed in parentheses, which would take the `$`s
+    Some(arg) => vec![
+      text("["),
+      Segment::Source(arg.without_parentheses().span()),
+      text("]"),
+    ],
+  }
+}
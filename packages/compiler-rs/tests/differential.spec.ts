@@ -0,0 +1,13 @@
+// @vitest-environment jsdom
+// Renders the same JSX with both backends and checks that they produce the
+// same DOM, before and after every state change. See `differential/harness.ts`
+// for the native binding it needs.
+import { describe, test } from 'vitest'
+import { cases } from './differential/cases'
+import { expectSameRender, transform } from './differential/harness'
+
+describe.skipIf(!transform)('vdom and vapor render the same DOM', () => {
+  for (const testCase of cases) {
+    test(testCase.name, () => expectSameRender(testCase))
+  }
+})
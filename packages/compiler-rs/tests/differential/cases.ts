@@ -0,0 +1,112 @@
+// The JSX corpus rendered by both backends.
+export interface Case {
+  name: string
+  // `state` is shared by both renders
+  jsx: string
+  state?: () => Record<string, any>
+  updates?: ((state: any) => void)[]
+}
+
+export const cases: Case[] = [
+  {
+    name: 'static',
+    jsx: `<div id="foo" class="bar"><span>hello</span> <b>world</b><br /></div>`,
+  },
+  {
+    name: 'text',
+    jsx: `<div>{state.msg} - {state.count + 1}</div>`,
+    state: () => ({ msg: 'hello', count: 0 }),
+    updates: [(state) => (state.msg = 'world'), (state) => state.count++],
+  },
+  {
+    name: 'attributes',
+    jsx: `<div id={state.id} class={{ active: state.active }} style={{ color: state.color }} title={state.title} />`,
+    state: () => ({ id: 'a', active: false, color: 'red', title: undefined }),
+    updates: [
+      (state) => (state.active = true),
+      (state) => {
+        state.id = 'b'
+        state.color = 'blue'
+        state.title = 'foo'
+      },
+      (state) => (state.title = null),
+    ],
+  },
+  {
+    name: 'boolean attributes',
+    jsx: `<button disabled={state.disabled} hidden={state.hidden}>ok</button>`,
+    state: () => ({ disabled: true, hidden: false }),
+    updates: [
+      (state) => (state.disabled = false),
+      (state) => (state.hidden = true),
+    ],
+  },
+  {
+    name: 'v-if',
+    jsx: `<div>
+      <p v-if={state.type === 'a'}>a</p>
+      <span v-else-if={state.type === 'b'}>b</span>
+      <i v-else>other</i>
+    </div>`,
+    state: () => ({ type: 'a' }),
+    updates: [
+      (state) => (state.type = 'b'),
+      (state) => (state.type = 'c'),
+      (state) => (state.type = 'a'),
+    ],
+  },
+  {
+    name: 'v-for',
+    jsx: `<ul>
+      <li v-for={(item, index) in state.list} key={item.id}>{index}: {item.text}</li>
+    </ul>`,
+    state: () => ({
+      list: [
+        { id: 1, text: 'a' },
+        { id: 2, text: 'b' },
+      ],
+    }),
+    updates: [
+      (state) => state.list.push({ id: 3, text: 'c' }),
+      (state) => state.list.reverse(),
+      (state) => state.list.splice(1, 1),
+      (state) => (state.list = []),
+    ],
+  },
+  {
+    name: 'v-show',
+    jsx: `<div v-show={state.show}>shown</div>`,
+    state: () => ({ show: true }),
+    updates: [(state) => (state.show = false)],
+  },
+  {
+    name: 'v-html and v-text',
+    jsx: `<div><p v-html={state.html} /><p v-text={state.text} /></div>`,
+    state: () => ({ html: '<b>bold</b>', text: '<b>plain</b>' }),
+    updates: [(state) => (state.html = '<i>italic</i>')],
+  },
+  {
+    name: 'fragments',
+    jsx: `<><div>a</div>{state.show ? <><span>b</span><span>c</span></> : null}<div>d</div></>`,
+    state: () => ({ show: true }),
+    updates: [
+      (state) => (state.show = false),
+      (state) => (state.show = true),
+    ],
+  },
+  {
+    name: 'conditional expressions',
+    jsx: `<div>{state.ok ? <b>yes</b> : 'no'}{state.list.map((item) => <i>{item}</i>)}</div>`,
+    state: () => ({ ok: true, list: ['a'] }),
+    updates: [
+      (state) => (state.ok = false),
+      (state) => state.list.push('b'),
+    ],
+  },
+  {
+    name: 'svg',
+    jsx: `<svg viewBox="0 0 10 10"><circle cx={state.x} cy="5" r="1" /></svg>`,
+    state: () => ({ x: 5 }),
+    updates: [(state) => (state.x = 3)],
+  },
+]
@@ -0,0 +1,79 @@
+// Compiles JSX with both backends in interop mode, mounts the results into
+// jsdom and compares their DOM while driving state changes.
+import { expect } from 'vitest'
+import * as vue from 'vue'
+import { createApp, createVaporApp, nextTick, reactive } from 'vue'
+import * as vapor from '../../../runtime/src/vapor'
+import * as vdom from '../../../runtime/src/vdom'
+import type { Case } from './cases'
+
+// The native binding, run `pnpm -C packages/compiler-rs build` first.
+// `index.js` throws when it isn't built for this platform, which fails the
+// suite unless `SKIP_DIFFERENTIAL=1` explicitly skips it.
+export const transform: typeof import('../../index.js').transform | undefined =
+  process.env.SKIP_DIFFERENTIAL
+    ? undefined
+    : (await import('../../index.js')).transform
+
+const modules: Record<string, any> = {
+  vue,
+  '/vue-jsx-vapor/vapor': vapor,
+  '/vue-jsx-vapor/vdom': vdom,
+}
+
+export function compile(jsx: string, state: any) {
+  const { code, diagnostics } = transform!(
+    `__exports.Vdom = defineComponent(() => () => (${jsx}))
+__exports.Vapor = defineVaporComponent(() => (${jsx}))`,
+    { interop: true },
+  )
+  expect(diagnostics.map((diagnostic) => diagnostic.frame)).toEqual([])
+  // the code is run as a function body, where imports aren't allowed
+  const body = code.replaceAll(
+    /import \{(.+?)\} from "(.+?)";?/g,
+    (_, names: string, source: string) =>
+      `const {${names.replaceAll(' as ', ': ')}} = __modules[${JSON.stringify(source)}];`,
+  )
+  const exports: Record<string, any> = {}
+  // eslint-disable-next-line no-new-func
+  new Function(
+    '__modules',
+    '__exports',
+    'state',
+    'defineComponent',
+    'defineVaporComponent',
+    body,
+  )(modules, exports, state, vdom.defineComponent, vapor.defineVaporComponent)
+  return exports
+}
+
+// anchors differ between the backends, e.g. `<!--v-if-->` and `<!--if-->`
+export function normalize(html: string) {
+  return html.replaceAll(/<!--[\s\S]*?-->/g, '')
+}
+
+// Renders `jsx` with both backends and expects the same DOM after the
+// initial render and after every update.
+export async function expectSameRender({
+  jsx,
+  state: createState = () => ({}),
+  updates = [],
+}: Case) {
+  const state = reactive(createState())
+  const { Vdom, Vapor } = compile(jsx, state)
+  const vdomRoot = document.createElement('div')
+  const vaporRoot = document.createElement('div')
+  createApp(Vdom).mount(vdomRoot)
+  createVaporApp(Vapor).mount(vaporRoot)
+
+  const expectSameDom = (step: string) =>
+    expect(normalize(vaporRoot.innerHTML), step).toBe(
+      normalize(vdomRoot.innerHTML),
+    )
+  expectSameDom('initial render')
+  for (const [index, update] of updates.entries()) {
+    update(state)
+    await nextTick()
+    expectSameDom(`update ${index + 1}`)
+  }
+}
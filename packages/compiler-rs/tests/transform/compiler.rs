@@ -0,0 +1,59 @@
+use compiler_rs::{Compiler, TransformOptions, transform};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+  assert_send_sync::<Compiler>();
+}
+
+#[test]
+fn compile_many() {
+  let compiler = Compiler {
+    interop: true,
+    ..Default::default()
+  };
+  let files = (0..64)
+    .map(|index| {
+      (
+        format!("{index}.tsx"),
+        format!("defineVaporComponent(() => <div id=\"{index}\">{{foo}}</div>)"),
+      )
+    })
+    .collect::<Vec<_>>();
+  let results = compiler.compile_many(&files);
+  assert_eq!(results.len(), files.len());
+  for ((filename, source), result) in files.iter().zip(results) {
+    // same output as a serial call, in the order of the files
+    let expected = transform(
+      source,
+      Some(TransformOptions {
+        filename,
+        interop: true,
+        ..Default::default()
+      }),
+    );
+    assert_eq!(result.unwrap().code, expected.code);
+  }
+}
+
+#[test]
+fn compile_many_with_errors() {
+  let results = Compiler::default().compile_many(&[
+    ("a.tsx", "<div>foo</div>"),
+    ("b.tsx", "<div>foo</span>"),
+    ("c.tsx", "<div v-show />"),
+  ]);
+  assert!(results[0].as_ref().unwrap().diagnostics.is_empty());
+  assert!(results[1].is_err());
+  assert_eq!(results[2].as_ref().unwrap().diagnostics.len(), 1);
+}
+
+#[test]
+fn reuse_allocator() {
+  let compiler = Compiler::default();
+  let first = compiler.compile("index.tsx", "<div>{foo}</div>").unwrap();
+  compiler.compile("index.tsx", "<span>bar</span>").unwrap();
+  let again = compiler.compile("index.tsx", "<div>{foo}</div>").unwrap();
+  assert_eq!(first.code, again.code);
+}
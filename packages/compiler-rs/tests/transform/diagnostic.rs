@@ -0,0 +1,214 @@
+use common::{
+  diagnostic::{DiagnosticCode, Severity},
+  error::{ErrorCodes, WarningCodes},
+  options::DiagnosticLevel,
+};
+use compiler_rs::{TransformOptions, transform, try_transform};
+use insta::assert_snapshot;
+use serde_json::json;
+
+#[test]
+fn code_frame() {
+  let source = "<div v-show />";
+  let diagnostics = transform(source, None).diagnostics;
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0].severity, Severity::Error);
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Error(ErrorCodes::VShowNoExpression)
+  );
+  assert_snapshot!(diagnostics[0].render(source, "index.jsx"), @r"
+  error[E61]: v-show is missing expression.
+   --> index.jsx:1:6
+    |
+  1 | <div v-show />
+    |      ^^^^^^
+  ");
+}
+
+#[test]
+fn suggested_fix() {
+  let source = "<input\n  v-model={msg}\n  value={foo}\n/>";
+  let diagnostics = transform(source, None).diagnostics;
+  let fix = diagnostics[0].fix.as_ref().unwrap();
+  assert_eq!(
+    &source[fix.span.start as usize..fix.span.end as usize],
+    "value={foo}"
+  );
+  assert_eq!(fix.replacement, "");
+  assert_snapshot!(diagnostics[0].render(source, "index.jsx"), @r"
+  error[E60]: Unnecessary value binding used alongside v-model. It will interfere with v-model's behavior.
+   --> index.jsx:3:3
+    |
+  3 |   value={foo}
+    |   ^^^^^^^^^^^
+    = help: remove the value binding
+  ");
+}
+
+#[test]
+fn json() {
+  let source = "<input\n  v-model={msg}\n  value={foo}\n/>";
+  let diagnostics = transform(source, None).diagnostics;
+  assert_eq!(
+    diagnostics[0].to_json(source),
+    json!({
+      "severity": "error",
+      "code": "E60",
+      "message": "Unnecessary value binding used alongside v-model. It will interfere with v-model's behavior.",
+      "label": { "start": 25, "end": 36, "line": 3, "column": 3, "message": null },
+      "related": [],
+      "fix": { "message": "remove the value binding", "start": 25, "end": 36, "replacement": "" },
+    })
+  );
+}
+
+#[test]
+fn related_labels() {
+  let source = "<Comp v-slot={foo}>\n  <template v-slot:foo></template>\n</Comp>";
+  let diagnostics = transform(
+    source,
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .diagnostics;
+  assert_snapshot!(diagnostics[0].render(source, "index.jsx"), @r"
+  error[E37]: Mixed v-slot usage on both the component and nested <template>. When there are multiple named slots, all slots should use <template> syntax to avoid scope ambiguity.
+   --> index.jsx:1:7
+    |
+  1 | <Comp v-slot={foo}>
+    |       ^^^^^^^^^^^^
+  2 |   <template v-slot:foo></template>
+    |             ---------- nested <template> slot found here
+  ");
+}
+
+#[test]
+fn html_nesting() {
+  let source = "<p><div /></p>";
+  let diagnostics = transform(source, None).diagnostics;
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Warning(WarningCodes::InvalidHtmlNesting)
+  );
+  assert_snapshot!(diagnostics[0].render(source, "index.jsx"), @r"
+  warning[W103]: `<div>` cannot be a child of `<p>`; the browser will re-parent it.
+   --> index.jsx:1:4
+    |
+  1 | <p><div /></p>
+    |    ^^^^^^^
+  ");
+
+  // vdom
+  let diagnostics = transform(
+    source,
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .diagnostics;
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Warning(WarningCodes::InvalidHtmlNesting)
+  );
+}
+
+#[test]
+fn html_nesting_level() {
+  let diagnostics = transform(
+    "<table><tr /></table>",
+    Some(TransformOptions {
+      html_nesting: DiagnosticLevel::Error,
+      ..Default::default()
+    }),
+  )
+  .diagnostics;
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Error(ErrorCodes::InvalidHtmlNesting)
+  );
+  assert!(diagnostics[0].is_error());
+
+  let diagnostics = transform(
+    "<p><div /></p>",
+    Some(TransformOptions {
+      html_nesting: DiagnosticLevel::Off,
+      ..Default::default()
+    }),
+  )
+  .diagnostics;
+  assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn html_nesting_with_structural_directives() {
+  for interop in [false, true] {
+    let options = || {
+      Some(TransformOptions {
+        interop,
+        ..Default::default()
+      })
+    };
+    assert!(
+      transform("<div><p v-if={foo}><span /></p></div>", options())
+        .diagnostics
+        .is_empty()
+    );
+    assert!(
+      transform("<ul><li v-for={i in list}>{i}</li></ul>", options())
+        .diagnostics
+        .is_empty()
+    );
+    assert_eq!(
+      transform("<p><p v-if={foo} /></p>", options())
+        .diagnostics
+        .len(),
+      1
+    );
+  }
+}
+
+#[test]
+fn syntax_error() {
+  let source = "const a = <div>foo</span>";
+  let errors = try_transform(source, None).err().unwrap();
+  assert!(!errors.is_empty());
+  assert_eq!(
+    errors[0].code,
+    DiagnosticCode::Error(ErrorCodes::SyntaxError)
+  );
+  assert!(
+    errors[0]
+      .render(source, "index.jsx")
+      .starts_with("error[E0]: ")
+  );
+  // the label points into the source
+  assert!(errors[0].label.span.end as usize <= source.len());
+  assert!(errors[0].label.span.start > 0);
+}
+
+#[test]
+fn semantic_error() {
+  let errors = try_transform("let a = 1;\nlet a = <div />", None)
+    .err()
+    .unwrap();
+  assert_eq!(
+    errors[0].code,
+    DiagnosticCode::Error(ErrorCodes::SyntaxError)
+  );
+  assert!(errors[0].message.contains('a'));
+}
+
+#[test]
+fn try_transform_valid() {
+  let source = "const a = <div v-show />";
+  let output = try_transform(source, None).unwrap();
+  assert_eq!(output.code, transform(source, None).code);
+  // compiler errors are still reported as diagnostics
+  assert_eq!(output.diagnostics.len(), 1);
+}
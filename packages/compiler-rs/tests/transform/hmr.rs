@@ -1,7 +1,6 @@
-use common::options::Hmr;
+use common::{either::Either, options::Hmr};
 use compiler_rs::{TransformOptions, transform};
 use insta::assert_snapshot;
-use napi::Either;
 
 #[test]
 pub fn export() {
@@ -0,0 +1,104 @@
+use common::{diagnostic::DiagnosticCode, either::Either, error::ErrorCodes};
+use compiler_rs::{TransformOptions, transform};
+
+// Inputs the dev server may see while a file is being edited. None of them may
+// panic, whatever the options. Kept small so that they also run under Miri
+// (`pnpm run miri`, in CI too). It checks for dangling and out-of-bounds
+// access; aliasing checks are off, as the transform keeps raw pointers to
+// nodes it also borrows mutably.
+const SOURCES: &[&str] = &[
+  "<div>",
+  "<div></span>",
+  "<div v-else>foo</div>",
+  "<div><p v-else-if={foo} /></div>",
+  "<div v-for={item} />",
+  "<div v-for={(item, index) in} />",
+  "<div v-if={} />",
+  "<div v-model />",
+  "<div v-slot:foo />",
+  "<Comp v-slot={} v-slots={} />",
+  "<template v-slot:default={{ foo }} />",
+  "<div>{}</div>",
+  "<>{/* */}</>",
+  "<div {...} />",
+  "<input value={foo} v-model={foo} v-model={bar} />",
+  "<p><div /></p>",
+  "<div v-once v-memo={[]} v-html={foo} v-text={bar}>baz</div>",
+  "const App = () => <div v-if={foo}><span v-else /></div>",
+  "defineVaporComponent(() => <div v-for={(item in list} />)",
+  "export default () => <a v-on={} onClick_stop_prevent />",
+];
+
+fn each_options(f: impl Fn(TransformOptions<'static>)) {
+  for flags in 0..16u8 {
+    let bit = |index: u8| flags & (1 << index) != 0;
+    f(TransformOptions {
+      interop: bit(0),
+      ssr: bit(1),
+      hmr: Either::A(bit(2)),
+      optimize: !bit(3),
+      ..Default::default()
+    });
+  }
+}
+
+#[test]
+fn malformed_sources() {
+  for source in SOURCES {
+    each_options(|options| {
+      transform(source, Some(options));
+    });
+  }
+}
+
+#[test]
+fn unknown_extension() {
+  let output = transform(
+    "<div>{foo}</div>",
+    Some(TransformOptions {
+      filename: "index.vue?vue&type=script&lang.tsx",
+      ..Default::default()
+    }),
+  );
+  assert!(output.code.contains("_setNodes(_x0, () => foo)"));
+  // parsed as TSX
+  let output = transform(
+    "const foo: string = ''\n;<div>{foo}</div>",
+    Some(TransformOptions {
+      filename: "index.unknown",
+      ..Default::default()
+    }),
+  );
+  assert!(output.diagnostics.is_empty());
+  assert!(output.code.contains("const foo: string = \"\";"));
+  assert!(output.code.contains("_template(\"<div> \", 1)"));
+}
+
+#[test]
+fn v_if_without_expression() {
+  for interop in [false, true] {
+    let output = transform(
+      "<div v-if={} />",
+      Some(TransformOptions {
+        interop,
+        ..Default::default()
+      }),
+    );
+    assert_eq!(
+      output
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect::<Vec<_>>(),
+      vec![DiagnosticCode::Error(ErrorCodes::VIfNoExpression)]
+    );
+  }
+}
+
+#[test]
+fn deep_nesting() {
+  let depth = if cfg!(miri) { 8 } else { 64 };
+  let source = format!("{}foo{}", "<div>".repeat(depth), "</div>".repeat(depth));
+  let output = transform(&source, None);
+  assert!(output.code.contains("foo"));
+}
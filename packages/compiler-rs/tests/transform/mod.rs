@@ -1,5 +1,12 @@
+mod compiler;
+mod diagnostic;
 mod hmr;
 mod interop;
+mod malformed;
 mod optimize;
 mod options;
+mod session;
+mod source_map;
 mod ssr;
+mod virtual_code;
+mod whitespace;
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use common::{either::Either, options::TagMatcher};
+use compiler_rs::{Compiler, Session, TransformOptions, transform};
+
+#[test]
+fn reuse_allocator() {
+  let mut session = Session::new(Compiler::default());
+  let sources = ["<div>{foo}</div>", "<span v-if={bar}>bar</span>", "<p />"];
+  for _ in 0..3 {
+    for source in sources {
+      let output = session.compile("index.jsx", source).unwrap();
+      // same output as a fresh compilation
+      assert_eq!(output.code, transform(source, None).code);
+    }
+  }
+  assert_eq!(session.files(), 9);
+}
+
+#[test]
+fn timings() {
+  let mut session = Session::new(Compiler {
+    hmr: Either::A(true),
+    ..Default::default()
+  });
+  session
+    .compile("index.jsx", "export const App = () => <div>{foo}</div>")
+    .unwrap();
+  let timings = session.timings();
+  assert!(timings.parse > Duration::ZERO);
+  assert!(timings.semantic > Duration::ZERO);
+  assert!(timings.transform > Duration::ZERO);
+  assert!(timings.hmr_or_ssr > Duration::ZERO);
+  assert!(timings.codegen > Duration::ZERO);
+  session
+    .compile("index.jsx", "export const App = () => <p />")
+    .unwrap();
+  assert_eq!(
+    session.total_timings().total(),
+    timings.total() + session.timings().total()
+  );
+
+  // errors still count as a compiled file
+  assert!(session.compile("index.jsx", "<div>").is_err());
+  assert_eq!(session.files(), 3);
+}
+
+#[test]
+fn edited_file() {
+  let mut session = Session::new(Compiler::default());
+  // sized by the stats of the smaller previous version
+  for source in [
+    "<div>{foo}</div>",
+    "const { a, b } = useProps(); <div onClick={() => a(b)}>{[a, b].map((c) => <p>{c}</p>)}</div>",
+    "<div />",
+  ] {
+    let output = session.compile("index.jsx", source).unwrap();
+    assert_eq!(output.code, transform(source, None).code);
+  }
+}
+
+#[test]
+fn cached_tag_patterns() {
+  let mut session = Session::new(Compiler {
+    custom_element: Some(vec!["sl*".to_string()]),
+    ..Default::default()
+  });
+  let source = "<div><slbutton checked={ok} /><span /></div>";
+  let expected = transform(
+    source,
+    Some(TransformOptions {
+      custom_element: Some(TagMatcher::Patterns(vec!["sl*".to_string()])),
+      ..Default::default()
+    }),
+  )
+  .code;
+  for _ in 0..2 {
+    assert_eq!(session.compile("index.jsx", source).unwrap().code, expected);
+  }
+}
@@ -0,0 +1,116 @@
+use compiler_rs::{TransformOptions, transform};
+
+fn decode_vlq(segment: &str) -> Vec<i64> {
+  let mut values = vec![];
+  let (mut value, mut shift) = (0i64, 0);
+  for byte in segment.bytes() {
+    let digit = match byte {
+      b'A'..=b'Z' => byte - b'A',
+      b'a'..=b'z' => byte - b'a' + 26,
+      b'0'..=b'9' => byte - b'0' + 52,
+      b'+' => 62,
+      _ => 63,
+    } as i64;
+    value += (digit & 31) << shift;
+    if digit & 32 == 0 {
+      values.push(if value & 1 == 1 {
+        -(value >> 1)
+      } else {
+        value >> 1
+      });
+      (value, shift) = (0, 0);
+    } else {
+      shift += 5;
+    }
+  }
+  values
+}
+
+// The original line mapped from `column` of the generated line containing
+// `needle`, or from the start of that line's statement.
+fn original_line<'a>(
+  source: &'a str,
+  options: TransformOptions<'a>,
+  needle: &str,
+  at_needle: bool,
+) -> i64 {
+  let output = transform(source, Some(options));
+  let map: serde_json::Value = serde_json::from_str(&output.map.unwrap()).unwrap();
+  let (line, code) = output
+    .code
+    .lines()
+    .enumerate()
+    .find(|(_, line)| line.contains(needle))
+    .unwrap();
+  let target = if at_needle {
+    code.find(needle).unwrap()
+  } else {
+    code.len() - code.trim_start().len()
+  } as i64;
+
+  let mut original_line = 0;
+  for (index, mappings) in map["mappings"].as_str().unwrap().split(';').enumerate() {
+    let mut column = 0;
+    for segment in mappings.split(',').filter(|segment| !segment.is_empty()) {
+      let values = decode_vlq(segment);
+      column += values[0];
+      if values.len() > 2 {
+        original_line += values[2];
+      }
+      // statements may be mapped before their indentation is printed
+      if index == line && (column == target || !at_needle && column < target) {
+        return original_line;
+      }
+    }
+  }
+  panic!("no mapping for `{needle}` in `{code}`");
+}
+
+const SOURCE: &str = "const App = () => (
+  <div
+    id={id}
+    onClick={onClick}
+  >
+    <span>{msg}</span>
+  </div>
+)";
+
+fn vapor_options<'a>() -> TransformOptions<'a> {
+  TransformOptions {
+    filename: "index.jsx",
+    source_map: true,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn vapor_template() {
+  assert_eq!(
+    original_line(SOURCE, vapor_options(), "_template(", false),
+    1
+  );
+  assert_eq!(original_line(SOURCE, vapor_options(), "= _t0()", false), 1);
+}
+
+#[test]
+fn vapor_operations() {
+  assert_eq!(
+    original_line(SOURCE, vapor_options(), "_renderEffect(", false),
+    2
+  );
+  assert_eq!(original_line(SOURCE, vapor_options(), "onClick", false), 3);
+}
+
+#[test]
+fn vdom_vnode_call() {
+  let options = TransformOptions {
+    filename: "index.jsx",
+    source_map: true,
+    interop: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    original_line(SOURCE, options, r#"_createElementVNode("span""#, true),
+    5
+  );
+}
@@ -53,9 +53,10 @@ pub fn ssr_define_vapor_component() {
   )
   .code;
   assert_snapshot!(code, @r#"
-  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/vapor";
-  import { createBlock as _createBlock, openBlock as _openBlock } from "vue";
-  const Comp = _defineVaporSSRComponent(() => (_openBlock(), _createBlock("div")));
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div></div>`);
+  });
   "#);
 }
 
@@ -0,0 +1,88 @@
+use compiler_rs::virtual_code;
+
+fn code(source: &str) -> String {
+  virtual_code(source, "index.tsx").code
+}
+
+#[test]
+fn v_if() {
+  assert_eq!(
+    code(
+      "<div>
+  <a v-if={foo} />
+  <b v-else-if={bar} />
+  <c v-else />
+</div>"
+    ),
+    "<div>
+  {foo ? <a  /> : 
+  bar ? <b  /> : 
+  <c  />}
+</div>"
+  );
+  assert_eq!(
+    code("const a = <a v-if={foo} />"),
+    "const a = (foo ? <a  /> : null)"
+  );
+}
+
+#[test]
+fn v_for() {
+  let code = code("<div><a v-for={(item, index) in list} key={index}>{item}</a></div>");
+  assert!(
+    code
+      .starts_with("<div>{__renderList(list, (item, index) => <a  key={index}>{item}</a>)}</div>")
+  );
+  assert!(code.contains("declare function __renderList<T>("));
+}
+
+#[test]
+fn v_if_with_v_for() {
+  assert!(
+    code("<div><a v-if={show} v-for={item in list} /></div>")
+      .starts_with("<div>{show ? __renderList(list, (item) => <a   />) : null}</div>")
+  );
+}
+
+#[test]
+fn v_model() {
+  assert_eq!(
+    code("<Comp v-model={foo} v-model:title={bar} v-model:$name$={baz} />"),
+    r#"<Comp {...{ modelValue: foo }} {...{ "title": bar }} {...{ [name]: baz }} />"#
+  );
+  assert_eq!(
+    code("<input v-model_trim={foo} />"),
+    "<input {...{ value: foo } as {}} />"
+  );
+}
+
+#[test]
+fn v_slot() {
+  assert_eq!(
+    code("<Comp v-slot={{ foo }}>{foo}</Comp>"),
+    "<Comp >{{ default: ({ foo }) => <>{foo}</> }}</Comp>"
+  );
+  let code = code(
+    "<Comp>
+  <div />
+  <template v-slot:header={props}>{props.title}</template>
+  text
+</Comp>",
+  );
+  assert!(code.starts_with("<Comp>{{\n   ...{ default: () => <><div /></> },"));
+  assert!(code.contains(r#" "header": (props) => <>{props.title}</>,"#));
+  assert!(code.ends_with("...{ default: () => <>\n  text\n</> },}}</Comp>"));
+}
+
+#[test]
+fn mappings() {
+  let source = "<div><a v-if={foo} v-for={item in list}>{item}</a></div>";
+  let output = virtual_code(source, "index.tsx");
+  assert!(!output.mappings.is_empty());
+  for mapping in output.mappings {
+    assert_eq!(
+      &source[mapping.source.start as usize..mapping.source.end as usize],
+      &output.code[mapping.generated.start as usize..mapping.generated.end as usize]
+    );
+  }
+}
@@ -0,0 +1,108 @@
+use common::options::Whitespace;
+use compiler_rs::{TransformOptions, transform};
+
+fn transform_vapor(source: &str, whitespace: Whitespace) -> String {
+  transform(
+    source,
+    Some(TransformOptions {
+      whitespace,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+fn transform_vdom(source: &str, whitespace: Whitespace) -> String {
+  transform(
+    &format!("defineComponent(() => () => {source})"),
+    Some(TransformOptions {
+      whitespace,
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+#[test]
+fn condense() {
+  let source = "<div>
+      foo
+      bar   baz
+    </div>";
+  let code = transform_vapor(source, Whitespace::Condense);
+  assert!(code.contains(r#"_template("<div>foo\n      bar   baz", 3)"#));
+  let code = transform_vdom(source, Whitespace::Condense);
+  assert!(code.contains(r#"_createElementBlock("div", null, "foo\n      bar   baz")"#));
+}
+
+#[test]
+fn text_before_expression() {
+  let source = "<p>foo
+    bar {x}</p>";
+  for (whitespace, text) in [
+    (Whitespace::Condense, r#""foo\n    bar ""#),
+    (Whitespace::JsxReact, r#""foo bar ""#),
+  ] {
+    // the space in front of `x` is kept
+    let code = transform_vapor(source, whitespace);
+    assert!(code.contains(&format!("_setNodes(_x0, {text}, () => x)")));
+    let code = transform_vdom(source, whitespace);
+    assert!(code.contains(&format!("_normalizeVNode({text}, -1)")));
+  }
+}
+
+#[test]
+fn preserve() {
+  let source = "<div> foo  &amp; <span /> </div>";
+  let code = transform_vapor(source, Whitespace::Preserve);
+  assert!(code.contains(r#"_template("<div> foo  &amp; <span></span> ", 3)"#));
+  assert!(!code.contains("_setNodes"));
+  let code = transform_vdom(source, Whitespace::Preserve);
+  assert!(code.contains(r#"_normalizeVNode(" foo  & ", -1)"#));
+  assert!(code.contains(r#"_normalizeVNode(" ", -1)"#));
+}
+
+#[test]
+fn jsx_react() {
+  let source = "<div>
+      foo
+      bar   baz <span />
+    </div>";
+  let code = transform_vapor(source, Whitespace::JsxReact);
+  assert!(code.contains(r#"_template("<div>foo bar   baz <span>", 3)"#));
+  assert!(!code.contains("_setNodes"));
+  let code = transform_vdom(source, Whitespace::JsxReact);
+  assert!(code.contains(r#"_normalizeVNode("foo bar   baz ", -1)"#));
+}
+
+#[test]
+fn pre() {
+  let source = "<pre>
+  foo
+    bar
+</pre>";
+  for whitespace in [
+    Whitespace::Condense,
+    Whitespace::Preserve,
+    Whitespace::JsxReact,
+  ] {
+    let code = transform_vapor(source, whitespace);
+    // the leading newline is dropped by the HTML parser
+    assert!(code.contains(r#"_template("<pre>  foo\n    bar\n", 3)"#));
+    let code = transform_vdom(source, whitespace);
+    assert!(code.contains(r#"_createElementBlock("pre", null, "  foo\n    bar\n")"#));
+  }
+}
+
+#[test]
+fn textarea_in_vdom() {
+  let code = transform_vdom(
+    "<textarea>
+  foo
+    bar
+</textarea>",
+    Whitespace::Condense,
+  );
+  assert!(code.contains(r#""  foo\n    bar\n""#));
+}
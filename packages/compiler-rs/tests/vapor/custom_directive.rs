@@ -1,5 +1,15 @@
-use compiler_rs::transform;
+use std::collections::HashMap;
+
+use common::{
+  diagnostic::DiagnosticCode,
+  directive::{
+    CustomDirectiveResult, DirectiveNode, DirectiveTransform, DirectiveTransformContext,
+  },
+  error::ErrorCodes,
+};
+use compiler_rs::{TransformOptions, transform};
 use insta::assert_snapshot;
+use oxc_allocator::CloneIn;
 
 #[test]
 fn basic() {
@@ -303,3 +313,112 @@ fn array_args_with_arg() {
   })();
   "#);
 }
+
+fn tooltip<'a>(
+  dir: &DirectiveNode<'a>,
+  context: &DirectiveTransformContext<'a>,
+) -> CustomDirectiveResult<'a> {
+  CustomDirectiveResult {
+    props: dir
+      .exp
+      .as_ref()
+      .map(|exp| ("title".to_string(), exp.clone_in(context.ast.allocator)))
+      .into_iter()
+      .collect(),
+    need_runtime: dir.modifiers.iter().any(|modifier| modifier == "runtime"),
+    ..Default::default()
+  }
+}
+
+fn with_tooltip<'a>() -> TransformOptions<'a> {
+  TransformOptions {
+    directive_transforms: HashMap::from([(
+      "tooltip".to_string(),
+      Box::new(tooltip) as Box<dyn DirectiveTransform>,
+    )]),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn compile_time_transform() {
+  let code = transform("<div v-tooltip={msg} />", Some(with_tooltip())).code;
+  assert_snapshot!(code, @r#"
+  import { renderEffect as _renderEffect, setProp as _setProp, template as _template } from "vue";
+  const _t0 = _template("<div>", 1);
+  (() => {
+  	const _n0 = _t0();
+  	_renderEffect(() => _setProp(_n0, "title", msg));
+  	return _n0;
+  })();
+  "#);
+}
+
+#[test]
+fn compile_time_transform_with_runtime() {
+  let code = transform("<div v-tooltip_runtime={msg} />", Some(with_tooltip())).code;
+  assert!(code.contains(r#"_setProp(_n0, "title", msg)"#));
+  assert!(code.contains(r#"const _directive_tooltip = _resolveDirective("tooltip");"#));
+  assert!(code.contains("{ runtime: true }"));
+}
+
+#[test]
+fn compile_time_transform_of_built_in_directive() {
+  let code = transform(
+    "<div v-show={foo} onClick={bar} />",
+    Some(TransformOptions {
+      directive_transforms: HashMap::from([
+        (
+          "show".to_string(),
+          Box::new(tooltip) as Box<dyn DirectiveTransform>,
+        ),
+        (
+          "on".to_string(),
+          Box::new(tooltip) as Box<dyn DirectiveTransform>,
+        ),
+      ]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains("_applyVShow(_n0, () => foo)"));
+  assert!(code.contains(r#"_on(_n0, "click", bar)"#));
+  assert!(!code.contains("title"));
+}
+
+fn failing_tooltip<'a>(
+  _: &DirectiveNode<'a>,
+  _: &DirectiveTransformContext<'a>,
+) -> CustomDirectiveResult<'a> {
+  CustomDirectiveResult {
+    error: Some("no tooltip".to_string()),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn failed_compile_time_transform() {
+  let result = transform(
+    "<div v-tooltip={msg} />",
+    Some(TransformOptions {
+      directive_transforms: HashMap::from([(
+        "tooltip".to_string(),
+        Box::new(failing_tooltip) as Box<dyn DirectiveTransform>,
+      )]),
+      ..Default::default()
+    }),
+  );
+  let [diagnostic] = result.diagnostics.as_slice() else {
+    panic!("expected one diagnostic");
+  };
+  assert_eq!(
+    diagnostic.code,
+    DiagnosticCode::Error(ErrorCodes::DirectiveTransformFailed)
+  );
+  assert_eq!(diagnostic.message, "no tooltip");
+  assert!(
+    result
+      .code
+      .contains(r#"const _directive_tooltip = _resolveDirective("tooltip");"#)
+  );
+}
@@ -0,0 +1,80 @@
+use common::options::TransformOptions;
+use compiler_rs::transform;
+use serde_json::Value;
+
+fn dump_ir(source: &str) -> Value {
+  transform(
+    source,
+    Some(TransformOptions {
+      dump_ir: true,
+      ..Default::default()
+    }),
+  )
+  .ir
+  .unwrap()
+}
+
+// All operations of a block, including the ones registered as effects.
+fn operations(block: &Value) -> Vec<&Value> {
+  let mut operations = block["operation"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .collect::<Vec<_>>();
+  for effect in block["effect"].as_array().unwrap() {
+    operations.extend(effect["operations"].as_array().unwrap());
+  }
+  operations
+}
+
+#[test]
+fn disabled_by_default() {
+  assert!(transform("<div />", None).ir.is_none());
+}
+
+#[test]
+fn templates_and_roots() {
+  let source = "const a = <div id=\"foo\" />\nconst b = <span>{msg}</span>";
+  let ir = dump_ir(source);
+  assert_eq!(ir["version"], 1);
+
+  let templates = ir["templates"].as_array().unwrap();
+  assert_eq!(templates.len(), 2);
+  assert_eq!(templates[0]["content"], "<div id=foo>");
+  assert_eq!(templates[0]["root"], true);
+
+  let roots = ir["roots"].as_array().unwrap();
+  assert_eq!(roots.len(), 2);
+  assert_eq!(roots[0]["source"], "<div id=\"foo\" />");
+  assert_eq!(roots[1]["source"], "<span>{msg}</span>");
+  assert_eq!(roots[1]["span"], serde_json::json!([37, 55]));
+}
+
+#[test]
+fn operations_with_spans() {
+  let source = "<div onClick={handle}>{msg}</div>";
+  let ir = dump_ir(source);
+  let block = &ir["roots"][0]["block"];
+  assert_eq!(
+    block["dynamic"]["children"][0]["flagNames"][0],
+    "Referenced"
+  );
+
+  let operations = operations(block);
+  let set_event = operations
+    .iter()
+    .find(|operation| operation["type"] == "SetEvent")
+    .unwrap();
+  assert_eq!(set_event["value"]["code"], "handle");
+  assert_eq!(set_event["delegate"], false);
+
+  let set_nodes = operations
+    .iter()
+    .find(|operation| operation["type"] == "SetNodes")
+    .unwrap();
+  let value = &set_nodes["values"][0];
+  assert_eq!(value["code"], "msg");
+  let start = value["span"][0].as_u64().unwrap() as usize;
+  let end = value["span"][1].as_u64().unwrap() as usize;
+  assert_eq!(&source[start..end], "msg");
+}
@@ -1,6 +1,10 @@
 mod abbreviation;
 mod custom_directive;
+mod ir;
 mod placeholder_alignment;
+mod ssr;
+mod templates;
+mod transform_built_in;
 mod transform_children;
 mod transform_element;
 mod transform_key;
@@ -12,6 +16,7 @@ mod v_bind;
 mod v_for;
 mod v_html;
 mod v_if;
+mod v_memo;
 mod v_model;
 mod v_on;
 mod v_once;
@@ -0,0 +1,160 @@
+use common::options::TransformOptions;
+use compiler_rs::transform;
+use insta::assert_snapshot;
+
+fn transform_ssr(jsx: &str) -> String {
+  let source = format!(
+    "import {{ defineVaporComponent }} from 'vue'
+    const Comp = defineVaporComponent(() => {jsx})"
+  );
+  transform(
+    &source,
+    Some(TransformOptions {
+      ssr: true,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+#[test]
+fn element() {
+  let code = transform_ssr("<div id=\"foo\" class={cls}>{msg}</div>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent, ssrRenderNodes as _ssrRenderNodes } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderClass as _ssrRenderClass } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div id=foo class="${_ssrRenderClass(cls)}">`);
+  	_push(_ssrRenderNodes(msg, _parent));
+  	_push(`</div>`);
+  });
+  "#);
+}
+
+#[test]
+fn v_model_and_v_show() {
+  let code = transform_ssr("<input v-model={msg} v-show={ok} />");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderAttr as _ssrRenderAttr, ssrRenderStyle as _ssrRenderStyle } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<input${_ssrRenderAttr("value", msg)} style="${_ssrRenderStyle(ok ? null : { display: "none" })}">`);
+  });
+  "#);
+}
+
+#[test]
+fn v_if() {
+  let code = transform_ssr("<div>{ok ? <span>yes</span> : <b>no</b>}</div>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div>`);
+  	if (ok) {
+  		_push(`<!--[--><span>yes</span><!--]-->`);
+  	} else {
+  		_push(`<!--[--><b>no</b><!--]-->`);
+  	}
+  	_push(`</div>`);
+  });
+  "#);
+}
+
+#[test]
+fn v_for() {
+  let code = transform_ssr("<ul><li v-for={item in items} key={item.id}>{item}</li></ul>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent, ssrRenderNodes as _ssrRenderNodes } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderList as _ssrRenderList } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<ul><!--[-->`);
+  	_ssrRenderList(items, (_for_item0) => {
+  		_push(`<li>`);
+  		_push(_ssrRenderNodes(_for_item0, _parent));
+  		_push(`</li>`);
+  	});
+  	_push(`<!--]--></ul>`);
+  });
+  "#);
+}
+
+#[test]
+fn component_with_slots() {
+  let code = transform_ssr("<Foo foo={bar}><div>{msg}</div></Foo>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent, ssrRenderNodes as _ssrRenderNodes } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderComponent as _ssrRenderComponent } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(_ssrRenderComponent(Foo, { foo: bar }, {
+  		_: 1,
+  		default: (_, _push, _parent) => {
+  			_push(`<div>`);
+  			_push(_ssrRenderNodes(msg, _parent));
+  			_push(`</div>`);
+  		}
+  	}, _parent));
+  });
+  "#);
+}
+
+#[test]
+fn slot_outlet() {
+  let code = transform_ssr("<div><slot /></div>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderSlot as _ssrRenderSlot } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div>`);
+  	_ssrRenderSlot(_parent.slots, "default", {}, null, _push, _parent);
+  	_push(`</div>`);
+  });
+  "#);
+}
+
+#[test]
+fn v_if_without_else() {
+  let code = transform_ssr("<div><span v-if={ok}>yes</span></div>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div>`);
+  	if (ok) {
+  		_push(`<!--[--><span>yes</span><!--]-->`);
+  	} else {
+  		_push(`<!--[--><!--]-->`);
+  	}
+  	_push(`</div>`);
+  });
+  "#);
+}
+
+#[test]
+fn key() {
+  let code = transform_ssr("<div><span key={id}>{id}</span></div>");
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent, ssrRenderNodes as _ssrRenderNodes } from "/vue-jsx-vapor/ssr";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div><!--[--><span>`);
+  	_push(_ssrRenderNodes(id, _parent));
+  	_push(`</span><!--]--></div>`);
+  });
+  "#);
+}
+
+#[test]
+fn static_and_dynamic_style() {
+  let code = transform_ssr(r#"<div style="color: red" class="foo" class={cls} v-show={ok} />"#);
+  assert_snapshot!(code, @r#"
+  import { defineVaporSSRComponent as _defineVaporSSRComponent } from "/vue-jsx-vapor/ssr";
+  import { ssrRenderClass as _ssrRenderClass, ssrRenderStyle as _ssrRenderStyle } from "vue/server-renderer";
+  const Comp = _defineVaporSSRComponent(() => (_push, _parent) => {
+  	_push(`<div class="${_ssrRenderClass(["foo", cls])}" style="${_ssrRenderStyle(["color: red", ok ? null : { display: "none" }])}"></div>`);
+  });
+  "#);
+}
+
+#[test]
+fn escape_text() {
+  let code = transform_ssr("<>a & b<span>c &amp; d</span></>");
+  assert!(code.contains("_push(`<!--[-->a &amp; b<span>c &amp; d</span><!--]-->`);"));
+}
@@ -0,0 +1,76 @@
+use common::options::TransformOptions;
+use compiler_rs::{Template, shared_template_module, transform};
+
+fn templates(source: &str) -> Vec<Template> {
+  transform(
+    source,
+    Some(TransformOptions {
+      report_templates: true,
+      ..Default::default()
+    }),
+  )
+  .templates
+  .unwrap()
+}
+
+#[test]
+fn report_templates() {
+  let templates = templates("<><div>{foo}</div><svg><circle /></svg></>");
+  assert_eq!(templates.len(), 2);
+  assert_eq!(templates[0].content, "<div> ");
+  assert_eq!(templates[0].ns, 0);
+  assert!(templates[1].content.starts_with("<svg><circle"));
+  assert_eq!(templates[1].ns, 1);
+  assert!(transform("<div />", None).templates.is_none());
+}
+
+#[test]
+fn same_hash_across_files() {
+  let a = templates("<li class=\"row\">{item}</li>");
+  let b = templates("const Row = () => <li class=\"row\">{label}</li>");
+  assert_eq!(a[0].hash(), b[0].hash());
+  let c = templates("<li class=\"other\">{item}</li>");
+  assert_ne!(a[0].hash(), c[0].hash());
+}
+
+#[test]
+fn stable_hash() {
+  // shared modules may be built by another version of the compiler
+  assert_eq!(templates("<div />")[0].hash(), "9a535c1a479e2b55");
+}
+
+#[test]
+fn import_shared_templates() {
+  let output = transform(
+    "<li class=\"row\">{item}</li>",
+    Some(TransformOptions {
+      report_templates: true,
+      shared_templates: Some("virtual:vue-jsx-vapor/templates".to_string()),
+      ..Default::default()
+    }),
+  );
+  let hash = output.templates.unwrap()[0].hash();
+  assert!(output.code.contains(&format!(
+    "import {{ t_{hash} as _t0 }} from \"virtual:vue-jsx-vapor/templates\";"
+  )));
+  assert!(!output.code.contains("_template("));
+  assert!(!output.code.contains("template as _template"));
+}
+
+#[test]
+fn shared_module() {
+  let mut all = templates("<li class=\"row\">{item}</li>");
+  all.extend(templates("<li class=\"row\">{label}</li>"));
+  all.extend(templates("<svg />"));
+  let code = shared_template_module(&all);
+  assert_eq!(code.matches("export const").count(), 2);
+  assert!(code.starts_with("import { template } from \"vue\";\n"));
+  assert!(code.contains(&format!(
+    "export const t_{} = /* @__PURE__ */ template(\"<li class=row> \", 1);",
+    all[0].hash()
+  )));
+  assert!(code.contains(&format!(
+    "export const t_{} = /* @__PURE__ */ template(\"<svg>\", 3, 1);",
+    all[2].hash()
+  )));
+}
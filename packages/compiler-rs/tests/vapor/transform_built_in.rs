@@ -0,0 +1,171 @@
+use common::{
+  diagnostic::DiagnosticCode,
+  error::{ErrorCodes, WarningCodes},
+};
+use compiler_rs::transform;
+
+fn codes(source: &str) -> Vec<DiagnosticCode> {
+  transform(source, None)
+    .diagnostics
+    .into_iter()
+    .map(|diagnostic| diagnostic.code)
+    .collect()
+}
+
+#[test]
+fn teleport() {
+  let code = transform(r##"<Teleport to="#modal"><div /></Teleport>"##, None).code;
+  assert!(code.contains("VaporTeleport as _VaporTeleport"));
+  // never the single root, its content is rendered elsewhere
+  assert!(code.contains(r##"_createComponent(_VaporTeleport, { to: "#modal" }, () => {"##));
+  assert!(!code.contains("}, true)"));
+}
+
+#[test]
+fn vapor_teleport() {
+  let code = transform("<VaporTeleport to={target}><div /></VaporTeleport>", None).code;
+  assert!(code.contains("_createComponent(VaporTeleport, { to: () => target }"));
+  assert!(!code.contains("_VaporTeleport"));
+}
+
+#[test]
+fn teleport_missing_target() {
+  assert_eq!(
+    codes("<Teleport><div /></Teleport>"),
+    vec![DiagnosticCode::Error(ErrorCodes::TeleportMissingTarget)]
+  );
+  assert!(codes("<Teleport {...props}><div /></Teleport>").is_empty());
+}
+
+#[test]
+fn teleport_invalid_target() {
+  for source in [
+    r#"<Teleport to=""><div /></Teleport>"#,
+    "<Teleport to><div /></Teleport>",
+    "<Teleport to={null}><div /></Teleport>",
+  ] {
+    assert_eq!(
+      codes(source),
+      vec![DiagnosticCode::Error(ErrorCodes::TeleportInvalidTarget)]
+    );
+  }
+}
+
+#[test]
+fn teleport_disabled_string() {
+  assert_eq!(
+    codes(r#"<Teleport to="body" disabled="false"><div /></Teleport>"#),
+    vec![DiagnosticCode::Warning(
+      WarningCodes::TeleportDisabledString
+    )]
+  );
+  assert!(codes(r#"<Teleport to="body" disabled={false}><div /></Teleport>"#).is_empty());
+}
+
+#[test]
+fn keep_alive() {
+  let code = transform("<KeepAlive><Comp /></KeepAlive>", None).code;
+  assert!(code.contains("VaporKeepAlive as _VaporKeepAlive"));
+  assert!(code.contains("_createComponent(_VaporKeepAlive, null, () => {"));
+}
+
+#[test]
+fn keep_alive_invalid_children() {
+  assert_eq!(
+    codes("<KeepAlive><Foo /><Bar /></KeepAlive>"),
+    vec![DiagnosticCode::Error(ErrorCodes::KeepAliveInvalidChildren)]
+  );
+  assert!(
+    codes(
+      "<KeepAlive>
+        <Foo v-if={foo} />
+        <Bar v-else />
+      </KeepAlive>"
+    )
+    .is_empty()
+  );
+}
+
+#[test]
+fn keep_alive_without_children() {
+  assert_eq!(
+    codes("<KeepAlive></KeepAlive>"),
+    vec![DiagnosticCode::Error(ErrorCodes::KeepAliveInvalidChildren)]
+  );
+}
+
+#[test]
+fn built_in_slots_are_stable() {
+  let code = transform("<KeepAlive><Foo v-if={foo} /></KeepAlive>", None).code;
+  assert!(!code.contains("_extend("));
+  let code = transform("<Comp><Foo v-if={foo} /></Comp>", None).code;
+  assert!(code.contains("_extend("));
+}
+
+#[test]
+fn imported_built_in() {
+  let code = transform(
+    "import { Teleport } from 'vue'
+    ;<Teleport to=\"body\"><div /></Teleport>",
+    None,
+  )
+  .code;
+  assert!(code.contains("_createComponent(_VaporTeleport"));
+}
+
+#[test]
+fn user_component_named_like_built_in() {
+  for source in [
+    "import Teleport from './Teleport'
+    ;<Teleport><div /></Teleport>",
+    "const KeepAlive = () => null
+    ;<KeepAlive />",
+  ] {
+    let output = transform(source, None);
+    assert!(output.diagnostics.is_empty());
+    assert!(!output.code.contains("_Vapor"));
+  }
+}
+
+#[test]
+fn suspense() {
+  assert!(
+    codes(
+      "<Suspense>
+        <AsyncComp />
+        <template v-slot:fallback>Loading...</template>
+      </Suspense>"
+    )
+    .is_empty()
+  );
+}
+
+#[test]
+fn suspense_invalid_slot() {
+  assert_eq!(
+    codes(
+      "<Suspense>
+        <AsyncComp />
+        <template v-slot:loading>Loading...</template>
+      </Suspense>"
+    ),
+    vec![DiagnosticCode::Error(ErrorCodes::SuspenseInvalidSlot)]
+  );
+  assert_eq!(
+    codes("<Suspense v-slots={{ default: () => <AsyncComp />, pending: () => null }} />"),
+    vec![DiagnosticCode::Error(ErrorCodes::SuspenseInvalidSlot)]
+  );
+}
+
+#[test]
+fn suspense_invalid_children() {
+  assert_eq!(
+    codes(
+      "<Suspense>
+        <Foo />
+        <Bar />
+      </Suspense>"
+    ),
+    vec![DiagnosticCode::Error(ErrorCodes::SuspenseInvalidChildren)]
+  );
+}
@@ -1,4 +1,5 @@
-use compiler_rs::transform;
+use common::options::TagMatcher;
+use compiler_rs::{TransformOptions, transform};
 use insta::assert_snapshot;
 
 #[test]
@@ -891,3 +892,35 @@ fn v_on_obj_before_static_event_keeps_handler_getters() {
   })();
   "#)
 }
+
+#[test]
+fn custom_element_option() {
+  let code = transform(
+    "<div><ion-button value={foo} /><slbutton /></div>",
+    Some(TransformOptions {
+      custom_element: Some(TagMatcher::Patterns(vec!["sl*".to_string()])),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"_template("<div>", 1)"#));
+  assert!(code.contains(r#"_createPlainElement("ion-button", { value: () => foo })"#));
+  assert!(code.contains(r#"_createPlainElement("slbutton")"#));
+}
+
+#[test]
+fn native_tag_option() {
+  let code = transform(
+    "<div><span /><widget title={foo} /></div>",
+    Some(TransformOptions {
+      native_tag: Some(TagMatcher::Predicate(Box::new(|tag| {
+        matches!(tag, "div" | "span")
+      }))),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"_template("<div><span>"#));
+  assert!(!code.contains("<widget"));
+  assert!(code.contains(r#"_createPlainElement("widget", { title: () => foo })"#));
+}
@@ -1,3 +1,4 @@
+use common::options::TransformOptions;
 use compiler_rs::transform;
 use insta::assert_snapshot;
 
@@ -418,3 +419,66 @@ fn fragment_with_empty_interpolation() {
   })();
   "#)
 }
+
+fn transform_typed(source: &str) -> String {
+  transform(
+    source,
+    Some(TransformOptions {
+      filename: "index.tsx",
+      type_aware_text: true,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+#[test]
+fn type_aware_text() {
+  let code = transform_typed(
+    "import { computed, ref } from 'vue'
+    const count = ref<number>(0)
+    const total = computed(() => count.value * 2)
+    ;<div>{count.value} / {total.value}</div>",
+  );
+  assert!(code.contains(
+    "_setText(_x0, _toDisplayString(count.value), \" / \", _toDisplayString(total.value))"
+  ));
+  assert!(!code.contains("_setNodes"));
+}
+
+#[test]
+fn type_aware_text_props() {
+  let code = transform_typed(
+    "interface Props { label: string; count?: number }
+    const Comp = (props: Props, { unit }: { unit: 'px' | 'em' }) => (
+      <p>{props.label}: {props.count!.toFixed(2)}{unit}<br /></p>
+    )",
+  );
+  assert!(code.contains("_setText"));
+  assert!(!code.contains("_setNodes"));
+}
+
+#[test]
+fn type_aware_text_unknown() {
+  let code = transform_typed(
+    "const count = ref<number>(0)
+    let label: string | HTMLElement = ''
+    ;<div>{count.value}{label}</div>",
+  );
+  assert!(code.contains("_setNodes"));
+  assert!(!code.contains("_setText"));
+}
+
+#[test]
+fn type_aware_text_disabled() {
+  let code = transform(
+    "const count: number = 0
+    ;<div>{count}</div>",
+    Some(TransformOptions {
+      filename: "index.tsx",
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains("_setNodes"));
+}
@@ -1,3 +1,4 @@
+use common::options::{DomPropWrites, TransformOptions};
 use compiler_rs::transform;
 use insta::assert_snapshot;
 
@@ -152,12 +153,11 @@ fn with_constant_value() {
   .code;
   assert_snapshot!(code, @r#"
   import { setProp as _setProp, template as _template } from "vue";
-  const _t0 = _template("<div e=2 f=foo1 g=1.1 h=1>", 1);
+  const _t0 = _template("<div c=3 e=2 f=foo1 g=1.1 h=1>", 1);
   (() => {
   	const _n0 = _t0();
   	_setProp(_n0, "a", void 0);
   	_setProp(_n0, "b", 1 > 2);
-  	_setProp(_n0, "c", 1 + 2);
   	_setProp(_n0, "d", 1 ? 2 : 3);
   	_setProp(_n0, "i", true);
   	_setProp(_n0, "j", null);
@@ -688,3 +688,176 @@ fn class_with_v_bind_object_falls_back_to_dynamic_props() {
   );
   assert!(!code.contains("_setClassName"));
 }
+
+#[test]
+fn const_binding() {
+  let code = transform(
+    "const cls = 'card'
+    const size = 2
+    const title = `${cls}-${size}`
+    ;<div class={cls} tabindex={size} title={title} />",
+    None,
+  )
+  .code;
+  assert!(code.contains(r#"_template("<div class=card tabindex=2 title=card-2>", 3)"#));
+  assert!(!code.contains("_setProp"));
+}
+
+#[test]
+fn const_object_property() {
+  let code = transform(
+    "const SIZES = { small: 'sm', nested: { large: 'lg' } } as const
+    const COLORS = Object.freeze({ primary: 'red' })
+    ;<div id={SIZES.small} class={SIZES['nested'].large} title={COLORS.primary} />",
+    Some(TransformOptions {
+      filename: "index.tsx",
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"_template("<div id=sm class=lg title=red>", 3)"#));
+  assert!(!code.contains("_setProp"));
+}
+
+#[test]
+fn non_const_binding() {
+  let code = transform(
+    "let cls = 'card'
+    const COLORS = Object.freeze({ nested: { primary: 'red' } })
+    const Comp = (cls) => <div class={cls} />
+    ;<div class={cls} title={COLORS.nested.primary} />",
+    None,
+  )
+  .code;
+  // reassignable, mutable or shadowed bindings stay dynamic
+  assert!(code.contains(r#"_setClass(_n0, cls)"#));
+  assert!(code.contains(r#""title", COLORS.nested.primary"#));
+  assert!(!code.contains("class=card"));
+}
+
+#[test]
+fn inline_dom_prop_writes() {
+  let code = transform(
+    "<div>
+      <input checked={checked} disabled={form.disabled} value={text} />
+      <video muted={muted()} />
+    </div>",
+    Some(TransformOptions {
+      dom_prop_writes: DomPropWrites::Inline,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"const _checked = checked === "" || !!checked;"#));
+  assert!(code.contains(".checked !== _checked && ("));
+  assert!(code.contains("const _value = form.disabled;"));
+  assert!(code.contains("const _muted = _value === \"\" || !!_value;"));
+  assert!(code.contains(r#"const _value = text == null ? "" : text;"#));
+  assert!(!code.contains("_setValue("));
+  assert!(!code.contains("_setProp"));
+}
+
+#[test]
+fn inline_dom_prop_value_writes() {
+  let code = transform(
+    "<div>
+      <textarea value={form.text} readonly={locked} />
+      <select value={selected} />
+    </div>",
+    Some(TransformOptions {
+      dom_prop_writes: DomPropWrites::Inline,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // guarded like `setValue`, which keeps the raw value for `v-model`
+  assert!(code.contains(
+    r#"		{
+			const _value = form.text;
+			_n0._value = _value;
+			const _value1 = _value == null ? "" : _value;
+			_n0.value !== _value1 && (_n0.value = _value1);
+			_value == null && _n0.removeAttribute("value");
+		}"#
+  ));
+  // the attribute spelling writes the property
+  assert!(code.contains("_n0.readOnly !== _readOnly && (_n0.readOnly = _readOnly);"));
+  // only inputs and textareas
+  assert!(code.contains("_setValue(_n1, selected)"));
+}
+
+#[test]
+fn inline_dom_prop_writes_with_clashing_names() {
+  let code = transform(
+    "<div>
+      <input checked={_checked} />
+      <input disabled={_value.disabled || _value1} />
+    </div>",
+    Some(TransformOptions {
+      dom_prop_writes: DomPropWrites::Inline,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"const _checked1 = _checked === "" || !!_checked;"#));
+  assert!(code.contains("const _value2 = _value.disabled || _value1;"));
+  assert!(code.contains(r#"const _disabled = _value2 === "" || !!_value2;"#));
+}
+
+#[test]
+fn inline_dom_prop_writes_on_root() {
+  let code = transform(
+    "<input checked={checked} />",
+    Some(TransformOptions {
+      dom_prop_writes: DomPropWrites::Inline,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"_setProp(_n0, "checked", checked)"#));
+}
+
+#[test]
+fn same_dependencies_share_render_effect() {
+  let code = transform(
+    "<form><input disabled={props.disabled} /><Comp /><button disabled={props.disabled} /></form>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code, @r#"
+  import { createComponent as _createComponent } from "/vue-jsx-vapor/vapor";
+  import { child as _child, next as _next, renderEffect as _renderEffect, setInsertionState as _setInsertionState, setProp as _setProp, template as _template } from "vue";
+  const _t0 = _template("<form><input><!><button>", 1);
+  (() => {
+  	const _n4 = _t0();
+  	const _n0 = _child(_n4);
+  	const _n3 = _next(_n0);
+  	const _n2 = _next(_n3);
+  	_setInsertionState(_n4, _n3);
+  	const _n1 = _createComponent(Comp);
+  	_renderEffect(() => {
+  		_setProp(_n0, "disabled", props.disabled);
+  		_setProp(_n2, "disabled", props.disabled);
+  	});
+  	return _n4;
+  })();
+  "#);
+
+  // on purpose, the input is first disabled once the component is created
+  let create = code.find("_createComponent(Comp)").unwrap();
+  assert!(code.find(r#"_setProp(_n0, "disabled""#).unwrap() > create);
+
+  // other reads, or calls that may have side effects, keep their order
+  for source in [
+    "<form><input disabled={props.disabled} /><Comp /><button disabled={props.busy} /></form>",
+    "<form><input disabled={isDisabled()} /><Comp /><button disabled={isDisabled()} /></form>",
+  ] {
+    assert_eq!(
+      transform(source, None)
+        .code
+        .matches("_renderEffect(")
+        .count(),
+      2
+    );
+  }
+}
@@ -0,0 +1,99 @@
+use common::options::TransformOptions;
+use compiler_rs::transform;
+
+#[test]
+fn basic() {
+  let code = transform("<div id={foo} v-memo={[foo]} />", None).code;
+  assert!(
+    code.contains("import { withVaporMemo as _withVaporMemo } from \"/vue-jsx-vapor/vapor\";")
+  );
+  assert!(
+    code.contains("_renderEffect(_withVaporMemo(() => [foo], () => _setProp(_n0, \"id\", foo)));")
+  );
+}
+
+#[test]
+fn only_memoized_subtree() {
+  let code = transform(
+    "<div id={foo}>
+      <span v-memo={[bar]} class={bar}>
+        <i title={baz} />
+      </span>
+    </div>",
+    None,
+  )
+  .code;
+  assert_eq!(code.matches("_renderEffect(").count(), 2);
+  assert!(code.contains("_renderEffect(() => _setProp("));
+  // one memo check for all effects of the subtree
+  assert_eq!(code.matches("_withVaporMemo(").count(), 1);
+  assert!(code.contains("_renderEffect(_withVaporMemo(() => [bar], () => {"));
+}
+
+#[test]
+fn nested() {
+  let code = transform(
+    "<div v-memo={[foo]} id={foo}>
+      <span v-memo={[bar]} class={bar} />
+    </div>",
+    None,
+  )
+  .code;
+  assert!(code.contains("_withVaporMemo(() => [foo], () => _setProp("));
+  assert!(
+    code.contains("_withVaporMemo(() => [foo], _withVaporMemo(() => [bar], () => _setClass(")
+  );
+}
+
+#[test]
+fn with_v_for() {
+  let code = transform(
+    "<tr
+      v-for={row in rows}
+      key={row.id}
+      v-memo={[row.id === selected]}
+      class={row.id === selected ? 'danger' : ''}
+    />",
+    None,
+  )
+  .code;
+  // memoized rows don't go through the selector
+  assert!(
+    code.contains("_withVaporMemo(() => [_for_item0.value.id === selected], () => _setClassName(")
+  );
+  assert!(!code.contains("_createSelector"));
+}
+
+#[test]
+fn ignored_in_v_once() {
+  let code = transform("<div v-once v-memo={[foo]} id={foo} />", None).code;
+  assert!(!code.contains("_withVaporMemo"));
+}
+
+#[test]
+fn ssr() {
+  let code = transform(
+    "<div v-memo={[foo]} id={foo} />",
+    Some(TransformOptions {
+      ssr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(!code.contains("withVaporMemo"));
+}
+
+#[test]
+fn text_child() {
+  let code = transform(
+    "<tr v-memo={[row.id === selected]}>
+      <td>{row.label}</td>
+    </tr>",
+    None,
+  )
+  .code;
+  // the text is only updated when the memo changes
+  assert!(
+    code.contains("_setNodes(_x0, _withVaporMemo(() => [row.id === selected], () => row.label));")
+  );
+}
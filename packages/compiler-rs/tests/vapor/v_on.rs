@@ -1,7 +1,11 @@
 use std::cell::RefCell;
 
-use common::{error::ErrorCodes, options::TransformOptions};
-use compiler_rs::transform;
+use common::{
+  diagnostic::DiagnosticCode,
+  error::{ErrorCodes, WarningCodes},
+  options::TransformOptions,
+};
+use compiler_rs::{TransformOutput, transform};
 use insta::assert_snapshot;
 
 #[test]
@@ -302,8 +306,7 @@ fn should_delegate_empty_handler() {
       }),
       ..Default::default()
     }),
-  )
-  .code;
+  );
   assert!(error.borrow().is_none());
 }
 
@@ -329,22 +332,16 @@ fn should_fallback_to_direct_listener_with_event_options() {
 
 #[test]
 fn should_fallback_to_direct_listener_for_unsupported_event() {
-  let warning = RefCell::new(None);
-  let code = transform(
-    "<div onScroll_delegate={test} />",
-    Some(TransformOptions {
-      on_warn: Box::new(|message, _| {
-        *warning.borrow_mut() = Some(message.to_string());
-      }),
-      ..Default::default()
-    }),
-  )
-  .code;
+  let TransformOutput {
+    code, diagnostics, ..
+  } = transform("<div onScroll_delegate={test} />", None);
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Warning(WarningCodes::VOnDelegateUnsupportedEvent)
+  );
   assert_eq!(
-    warning.borrow().as_deref(),
-    Some(
-      ".delegate modifier is not supported on the \"scroll\" event. The listener will be attached directly."
-    )
+    diagnostics[0].message,
+    ".delegate modifier is not supported on the \"scroll\" event. The listener will be attached directly."
   );
   assert!(code.contains("_on(_n0, \"scroll\", test)"));
   assert!(!code.contains("_delegateEvents"));
@@ -352,22 +349,23 @@ fn should_fallback_to_direct_listener_for_unsupported_event() {
 
 #[test]
 fn should_ignore_delegate_modifier_on_component_event() {
-  let warning = RefCell::new(None);
-  let code = transform(
-    "<Comp onClick_delegate={test} />",
-    Some(TransformOptions {
-      on_warn: Box::new(|message, _| {
-        *warning.borrow_mut() = Some(message.to_string());
-      }),
-      ..Default::default()
-    }),
-  )
-  .code;
+  let TransformOutput {
+    code, diagnostics, ..
+  } = transform("<Comp onClick_delegate={test} />", None);
+  assert_eq!(
+    diagnostics[0].code,
+    DiagnosticCode::Warning(WarningCodes::VOnDelegateOnComponent)
+  );
+  assert_eq!(
+    diagnostics[0].message,
+    ".delegate modifier is only supported on native DOM elements. The modifier will be ignored."
+  );
   assert_eq!(
-    warning.borrow().as_deref(),
-    Some(
-      ".delegate modifier is only supported on native DOM elements. The modifier will be ignored."
-    )
+    diagnostics[0]
+      .fix
+      .as_ref()
+      .map(|fix| fix.replacement.as_str()),
+    Some("onClick")
   );
   assert!(code.contains("{ onClick: () => test }"));
   assert!(!code.contains("_delegateEvents"));
@@ -459,3 +457,94 @@ fn should_prioritize_right_over_middle_for_click_event_normalization() {
   })();
   "#);
 }
+
+#[test]
+fn inline_modifiers() {
+  let code = transform(
+    "<>
+      <a onClick_stop_ctrl={handleEvent}></a>
+      <a onClick_right={handleEvent}></a>
+      <a onClick_ctrl_exact={handleEvent}></a>
+      <input onKeyup_enter_self={handleEvent} />
+      <input onKeyup_esc={handleEvent} />
+      <input onKeydown_page-down={handleEvent} />
+    </>",
+    Some(TransformOptions {
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains("(_e, ..._args) => {"));
+  assert!(code.contains("_e.stopPropagation();"));
+  assert!(code.contains("!_e.ctrlKey"));
+  assert!(code.contains("return handleEvent(_e, ..._args);"));
+  assert!(code.contains(r#""button" in _e && _e.button !== 2"#));
+  assert!(code.contains("_e.shiftKey || _e.altKey || _e.metaKey"));
+  assert!(code.contains(r#"_e.key !== "Enter""#));
+  assert!(code.contains("_e.target !== _e.currentTarget"));
+  assert!(code.contains("return handleEvent(_e);"));
+  assert!(code.contains(r#"["Esc", "Escape"].includes(_e.key)"#));
+  assert!(code.contains(r#"_e.key !== "PageDown""#));
+  assert!(!code.contains("_withModifiers"));
+  assert!(!code.contains("_withKeys"));
+}
+
+#[test]
+fn inline_modifiers_handlers() {
+  let code = transform(
+    "<div
+      onClick_stop={obj.method}
+      onKeyup_enter={(e) => e.key}
+      onClick_self={() => { if (foo) return; bar() }}
+    />",
+    Some(TransformOptions {
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // called without `this`, like `withModifiers` would
+  assert!(code.contains("return (0, obj.method)(_e, ..._args);"));
+  // the body of an arrow function is run directly
+  assert!(code.contains(
+    r#"_on(_n0, "keyup", (e) => {
+		if (e.key !== "Enter") return;
+		return e.key;
+	});"#
+  ));
+  assert!(code.contains(
+    r#"_on(_n0, "click", (_e) => {
+		if (_e.target !== _e.currentTarget) return;
+		if (foo) return;
+		bar();
+	});"#
+  ));
+}
+
+#[test]
+fn inline_modifiers_component_prop() {
+  let code = transform(
+    "<Comp onClick_stop={foo} />",
+    Some(TransformOptions {
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // the prop getter would return new guards each time
+  assert!(code.contains(r#"onClick: () => _withModifiers(foo, ["stop"])"#));
+}
+
+#[test]
+fn inline_modifiers_call_handler() {
+  let code = transform(
+    "<div onClick_stop={getHandler()} />",
+    Some(TransformOptions {
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert!(code.contains(r#"_withModifiers(getHandler(), ["stop"])"#));
+}
@@ -92,3 +92,26 @@ fn should_raise_error_if_has_no_expression() {
   );
   assert_eq!(*error.borrow(), Some(ErrorCodes::VTextNoExpression));
 }
+
+#[test]
+fn const_binding_without_render_effect() {
+  let code = transform(
+    "const LABEL = 'Total'
+    ;<div v-text={LABEL}></div>",
+    None,
+  )
+  .code;
+  assert!(code.contains("_setText(_x0, _toDisplayString(LABEL))"));
+  assert!(!code.contains("_renderEffect"));
+}
+
+#[test]
+fn let_binding_with_render_effect() {
+  let code = transform(
+    "let label = 'Total'
+    ;<div v-text={label}></div>",
+    None,
+  )
+  .code;
+  assert!(code.contains("_renderEffect(() => _setText(_x0, _toDisplayString(label)))"));
+}
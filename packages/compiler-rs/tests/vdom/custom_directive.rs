@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+
+use common::directive::{
+  CustomDirectiveResult, DirectiveNode, DirectiveTransform, DirectiveTransformContext,
+};
 use compiler_rs::{TransformOptions, transform};
 use insta::assert_snapshot;
+use oxc_allocator::CloneIn;
 
 #[test]
 fn basic() {
@@ -311,3 +317,45 @@ fn array_args_with_arg() {
   })();
   "#);
 }
+
+fn tooltip<'a>(
+  dir: &DirectiveNode<'a>,
+  context: &DirectiveTransformContext<'a>,
+) -> CustomDirectiveResult<'a> {
+  CustomDirectiveResult {
+    props: dir
+      .exp
+      .as_ref()
+      .map(|exp| ("title".to_string(), exp.clone_in(context.ast.allocator)))
+      .into_iter()
+      .collect(),
+    need_runtime: dir.modifiers.iter().any(|modifier| modifier == "runtime"),
+    ..Default::default()
+  }
+}
+
+fn with_tooltip<'a>() -> TransformOptions<'a> {
+  TransformOptions {
+    interop: true,
+    directive_transforms: HashMap::from([(
+      "tooltip".to_string(),
+      Box::new(tooltip) as Box<dyn DirectiveTransform>,
+    )]),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn compile_time_transform() {
+  let code = transform("<div v-tooltip={msg} />", Some(with_tooltip())).code;
+  assert!(code.contains("{ title: msg }"));
+  assert!(!code.contains("resolveDirective"));
+}
+
+#[test]
+fn compile_time_transform_with_runtime() {
+  let code = transform("<div v-tooltip_runtime={msg} />", Some(with_tooltip())).code;
+  assert!(code.contains("{ title: msg }"));
+  assert!(code.contains(r#"const _directive_tooltip = _resolveDirective("tooltip");"#));
+  assert!(code.contains("{ runtime: true }"));
+}
@@ -1,5 +1,6 @@
 mod cache_static;
 mod custom_directive;
+mod stringify_static;
 mod transform_element;
 mod transform_slot_outlet;
 mod transform_text;
@@ -0,0 +1,79 @@
+use common::options::TransformOptions;
+use compiler_rs::transform;
+
+fn compile(source: &str) -> String {
+  transform(
+    source,
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+fn repeat(node: &str, count: usize) -> String {
+  node.repeat(count)
+}
+
+#[test]
+fn should_not_stringify_below_threshold() {
+  let code = compile(&format!(
+    "<div><div>{}</div></div>",
+    repeat(r#"<span class="foo"/>"#, 18)
+  ));
+  assert!(!code.contains("_createStaticVNode"));
+}
+
+#[test]
+fn stringify_node_count_threshold() {
+  let code = compile(&format!(
+    "<div><div>{}</div></div>",
+    repeat(r#"<span class="foo"/>"#, 20)
+  ));
+  assert!(code.contains(&format!(
+    r#"_createStaticVNode("<div>{}</div>", 1)"#,
+    repeat("<span class=foo></span>", 20)
+  )));
+}
+
+#[test]
+fn stringify_element_with_binding_threshold() {
+  let code = compile(&format!(
+    "<div><div>{}</div></div>",
+    repeat(r#"<span id={1} title="a b"/>"#, 5)
+  ));
+  assert!(code.contains("_createStaticVNode("));
+  assert_eq!(code.matches("<span id=1 title=").count(), 5);
+}
+
+#[test]
+fn stringify_sibling_chunk() {
+  let code = compile(&format!(
+    "<div>{}<span>{{foo}}</span></div>",
+    repeat("<p>&lt;hi&gt;</p>", 20)
+  ));
+  assert!(code.contains(&format!(
+    r#"_createStaticVNode("{}", 20)"#,
+    repeat("<p>&lt;hi&gt;</p>", 20)
+  )));
+  assert!(code.contains("foo"));
+}
+
+#[test]
+fn should_not_stringify_table_elements() {
+  let code = compile(&format!(
+    "<div><table><tbody>{}</tbody></table></div>",
+    repeat("<tr><td>1</td></tr>", 20)
+  ));
+  assert!(!code.contains("_createStaticVNode"));
+}
+
+#[test]
+fn should_not_stringify_dom_props() {
+  let code = compile(&format!(
+    "<div><div>{}<input value=\"1\" /></div></div>",
+    repeat(r#"<span class="foo"/>"#, 20)
+  ));
+  assert!(!code.contains("_createStaticVNode"));
+}
@@ -269,8 +269,7 @@ fn error_on_unexpected_cunstom_directive_on_slot() {
       }),
       ..Default::default()
     }),
-  )
-  .code;
+  );
   assert_eq!(
     *error.borrow(),
     Some(ErrorCodes::VSlotUnexpectedDirectiveOnSlotOutlet)
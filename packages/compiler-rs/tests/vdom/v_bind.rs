@@ -236,3 +236,47 @@ fn deduped_props() {
   }, null, 8, _hoisted_1);
   "#);
 }
+
+#[test]
+fn const_binding() {
+  let code = transform(
+    "const cls = 'card'
+    const SIZES = Object.freeze({ small: 'sm' })
+    ;<div><span class={cls} id={SIZES.small}>hello</span><span class={foo} /></div>",
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // the first span only depends on constants, so it is cached
+  assert_snapshot!(code, @r#"
+  import { createVNodeCache as _createVNodeCache, normalizeClass as _normalizeClass } from "/vue-jsx-vapor/vdom";
+  import { createElementBlock as _createElementBlock, createElementVNode as _createElementVNode, openBlock as _openBlock } from "vue";
+  const cls = "card";
+  const SIZES = Object.freeze({ small: "sm" });
+  (() => {
+  	const _cache = _createVNodeCache("631d214bc2c8427c");
+  	return _openBlock(), _createElementBlock("div", null, [_cache[0] || (_cache[0] = _createElementVNode("span", {
+  		class: "card",
+  		id: "sm"
+  	}, "hello", -1)), _createElementVNode("span", { class: _normalizeClass(foo) }, null, 2)]);
+  })();
+  "#);
+}
+
+#[test]
+fn const_binding_without_optimize() {
+  let code = transform(
+    "const cls = 'card'
+    ;<span class={cls} />",
+    Some(TransformOptions {
+      interop: true,
+      optimize: false,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // folded like in Vapor, only the caching is left out
+  assert!(code.contains(r#"_createVNode("span", { class: "card" })"#));
+}
@@ -637,8 +637,7 @@ mod error {
         }),
         ..Default::default()
       }),
-    )
-    .code;
+    );
     assert_eq!(*error.borrow(), Some(ErrorCodes::VForNoExpression));
   }
 
@@ -654,8 +653,7 @@ mod error {
         }),
         ..Default::default()
       }),
-    )
-    .code;
+    );
     assert_eq!(*error.borrow(), Some(ErrorCodes::VForMalformedExpression));
   }
 
@@ -671,8 +669,7 @@ mod error {
         }),
         ..Default::default()
       }),
-    )
-    .code;
+    );
     assert_eq!(*error.borrow(), Some(ErrorCodes::VForMalformedExpression));
   }
 }
@@ -50,8 +50,7 @@ fn should_raise_error_if_has_children() {
       }),
       ..Default::default()
     }),
-  )
-  .code;
+  );
   assert_eq!(*error.borrow(), Some(ErrorCodes::VHtmlWithChildren));
 }
 
@@ -67,7 +66,6 @@ fn should_raise_error_if_has_no_expression() {
       }),
       ..Default::default()
     }),
-  )
-  .code;
+  );
   assert_eq!(*error.borrow(), Some(ErrorCodes::VHtmlNoExpression));
 }
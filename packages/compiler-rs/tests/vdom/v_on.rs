@@ -491,3 +491,93 @@ fn should_not_have_props_patch_flag_for_constant_v_on_handlers_with_modifiers()
   _openBlock(), _createElementBlock("div", { onKeydown: _withKeys(foo, ["up"]) }, null, 40, _hoisted_1);
   "#);
 }
+
+#[test]
+fn inline_modifiers() {
+  let code = transform(
+    "<div
+      onClick_stop_prevent={() => count.value++}
+      onKeyup_enter_ctrl={(e) => { test(e) }}
+      onClick_middle={(e) => test(e)}
+    />",
+    Some(TransformOptions {
+      interop: true,
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // the guards are cached like the handler, and run its body directly
+  assert!(code.contains("onClick: _cache[0] || (_cache[0] = (_e) => {"));
+  assert!(code.contains("_e.stopPropagation();"));
+  assert!(code.contains("_e.preventDefault();"));
+  assert!(code.contains("return count.value++;"));
+  assert!(code.contains("onKeyup: _cache[1] || (_cache[1] = (e) => {"));
+  assert!(code.contains(r#"e.key !== "Enter""#));
+  assert!(code.contains("!e.ctrlKey"));
+  assert!(code.contains("\t\t\ttest(e);"));
+  assert!(code.contains("onMouseup: _cache[2] || (_cache[2] = (e) => {"));
+  assert!(code.contains(r#""button" in e && e.button !== 1"#));
+  assert!(code.contains("return test(e);"));
+  assert!(!code.contains("_withModifiers"));
+  assert!(!code.contains("_withKeys"));
+}
+
+#[test]
+fn inline_modifiers_uncached_handler() {
+  let code = transform(
+    "<div onKeydown_enter={foo} onClick_stop={foo.bar} />",
+    Some(TransformOptions {
+      interop: true,
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // new guards on every render would replace the helpers' cache on the handler
+  assert!(code.contains(r#"onKeydown: _withKeys(foo, ["enter"])"#));
+  assert!(code.contains(r#"onClick: _withModifiers(foo.bar, ["stop"])"#));
+}
+
+#[test]
+fn inline_modifiers_patch_flag() {
+  let transform_inline = |source: &str, inline_modifiers: bool| {
+    transform(
+      source,
+      Some(TransformOptions {
+        interop: true,
+        inline_modifiers,
+        ..Default::default()
+      }),
+    )
+    .code
+  };
+  for source in [
+    "<div onKeydown_enter={foo} />",
+    "<div onKeydown_stop={foo.bar} />",
+    "<div onClick_stop={() => foo()} />",
+  ] {
+    let code = transform_inline(source, true);
+    let helper_code = transform_inline(source, false);
+    let flags = |code: &str| code[code.rfind("}").unwrap()..].to_string();
+    assert_eq!(flags(&code), flags(&helper_code), "{source}");
+  }
+  assert!(
+    transform_inline("<div onKeydown_enter={foo} />", true).contains("null, 40, _hoisted_1)")
+  );
+}
+
+#[test]
+fn inline_modifiers_call_handler() {
+  let code = transform(
+    "<div onClick_stop={getHandler()} />",
+    Some(TransformOptions {
+      interop: true,
+      inline_modifiers: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  // the handler is created once by the helper, not on every click
+  assert!(code.contains(r#"_withModifiers(getHandler(), ["stop"])"#));
+}
@@ -1,4 +1,14 @@
-import { useSSRContext, type ComponentOptions } from 'vue'
+import {
+  isVNode,
+  useSSRContext,
+  type ComponentInternalInstance,
+  type ComponentOptions,
+  type VaporComponent,
+} from 'vue'
+import { ssrInterpolate, ssrRenderVNode } from 'vue/server-renderer'
+
+type SSRBuffer = any[] & { hasAsync?: boolean }
+type PushFn = (item: any) => void
 
 export function ssrRegisterHelper(comp: ComponentOptions, filename: string) {
   if (typeof comp === 'function') {
@@ -18,3 +28,112 @@ export function ssrRegisterHelper(comp: ComponentOptions, filename: string) {
     }
   }
 }
+
+// component
+
+function createSSRSetup(setup: (props: any, ctx: any) => any) {
+  return (props: any, ctx: any) => {
+    const result = setup(props, ctx)
+    // with `__ssrInlineRender` the returned function is used as `ssrRender`
+    return (_ctx: any, push: PushFn, parent: ComponentInternalInstance) =>
+      push(ssrRenderNodes(result, parent))
+  }
+}
+
+/*@__NO_SIDE_EFFECTS__*/
+export function defineVaporSSRComponent(
+  comp: VaporComponent,
+  extraOptions: VaporComponent,
+): VaporComponent {
+  if (typeof comp === 'function') {
+    return Object.assign({ name: comp.name }, extraOptions, {
+      // @ts-ignore
+      setup: createSSRSetup(comp),
+      __ssrInlineRender: true,
+      __vapor: true,
+    })
+  }
+  const setup = comp.setup
+  if (setup) {
+    comp.setup = createSSRSetup(setup) as any
+    // @ts-ignore
+    comp.__ssrInlineRender = true
+  }
+  comp.__vapor = true
+  return comp
+}
+
+// render
+
+/**
+ * Renders the value of a JSX expression: compiled Vapor SSR render functions
+ * `(_push, _parent) => void`, vnodes, arrays, getters and primitives.
+ */
+export function ssrRenderNodes(
+  node: any,
+  parent: ComponentInternalInstance | null,
+): SSRBuffer {
+  const buffer: SSRBuffer = []
+  const push: PushFn = (item) => {
+    const isStringItem = typeof item === 'string'
+    if (isStringItem && typeof buffer.at(-1) === 'string') {
+      buffer[buffer.length - 1] += item
+    } else {
+      buffer.push(item)
+    }
+    if (!isStringItem && (item instanceof Promise || item.hasAsync)) {
+      buffer.hasAsync = true
+    }
+  }
+  renderNode(node, push, parent)
+  return buffer
+}
+
+function renderNode(
+  node: any,
+  push: PushFn,
+  parent: ComponentInternalInstance | null,
+) {
+  if (node == null || typeof node === 'boolean') {
+    push('<!---->')
+  } else if (Array.isArray(node)) {
+    push('<!--[-->')
+    for (const child of node) renderNode(child, push, parent)
+    push('<!--]-->')
+  } else if (typeof node === 'function') {
+    if (node.length) {
+      node(push, parent)
+    } else {
+      renderNode(node(), push, parent)
+    }
+  } else if (isVNode(node)) {
+    ssrRenderVNode(push, node, parent)
+  } else {
+    push(ssrInterpolate(node))
+  }
+}
+
+/**
+ * Normalizes runtime slot objects, e.g. `{{ default: () => <div /> }}`,
+ * into the compiled `(props, push, parent)` signature of `ssrRenderSlot`.
+ */
+export function ssrNormalizeSlots(slots: any) {
+  if (typeof slots === 'function') {
+    slots = { default: slots }
+  }
+  const normalized: Record<string, any> = { _: 1 }
+  for (const key in slots) {
+    const slot = slots[key]
+    if (key === '_' || typeof slot !== 'function') continue
+    normalized[key] = (
+      props: any,
+      push?: PushFn,
+      parent?: ComponentInternalInstance,
+    ) => {
+      const result = slot(props)
+      if (!push) return result
+      push(ssrRenderNodes(result, parent || null))
+    }
+  }
+  return normalized
+}
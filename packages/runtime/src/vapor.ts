@@ -29,32 +29,6 @@ import type {
 
 // component
 
-/*@__NO_SIDE_EFFECTS__*/
-export function defineVaporSSRComponent(
-  comp: VaporComponent,
-  extraOptions: VaporComponent,
-): VaporComponent {
-  if (typeof comp === 'function') {
-    return Object.assign({ name: comp.name }, extraOptions, {
-      setup(props: any, ctx: any) {
-        // @ts-ignore
-        const result = comp(props, ctx)
-        return () => result
-      },
-      __vapor: true,
-    })
-  }
-  const setup = comp.setup
-  if (setup) {
-    comp.setup = (props, ctx) => {
-      const result = setup(props, ctx)
-      return () => result
-    }
-  }
-  comp.__vapor = true
-  return comp
-}
-
 type Tail<T extends any[]> = T extends [any, ...infer R] ? R : never
 
 export const createComponent = (
@@ -230,10 +204,14 @@ function resolveValues(
     const anchor = index === values.length - 1 ? _anchor : undefined
     if (typeof value === 'function') {
       Vue.renderEffect(() => {
+        const scope = new EffectScope()
+        const result = scope.run(value)
+        // an unchanged v-memo keeps the current nodes
+        if (result === memoHit) return scope.stop()
         if (scopes[index]) scopes[index].stop()
-        scopes[index] = new EffectScope()
-        nodes[index] = scopes[index].run(() =>
-          resolveValue(nodes[index], value(), anchor, processFunction),
+        scopes[index] = scope
+        nodes[index] = scope.run(() =>
+          resolveValue(nodes[index], result, anchor, processFunction),
         )!
       })
     } else {
@@ -268,6 +246,26 @@ export function normalizeVaporSlots(slots: any) {
   }
 }
 
+// v-memo
+
+const memoHit = Symbol('memoHit')
+
+export function withVaporMemo<T>(getMemo: () => any[], fn: () => T) {
+  let cached: any[] | undefined
+  return (): T | typeof memoHit => {
+    const memo = getMemo()
+    if (
+      cached &&
+      cached.length === memo.length &&
+      memo.every((value, index) => Object.is(value, cached![index]))
+    ) {
+      return memoHit
+    }
+    cached = memo
+    return fn()
+  }
+}
+
 // defineVaporComponent
 
 type VaporComponentInstanceConstructor<T extends VaporComponentInstance> = {